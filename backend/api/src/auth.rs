@@ -1930,7 +1930,9 @@ mod tests {
                 access_key_id: None,
                 secret_access_key: None,
                 force_path_style: false,
+                download_mode: common::settings::DownloadMode::Presigned,
             },
+            storage: common::settings::StorageSettings::default(),
             github: common::settings::GithubSettings {
                 search_keywords: "topic:agent-skill".to_string(),
                 token: None,
@@ -1938,12 +1940,16 @@ mod tests {
             },
             worker: common::settings::WorkerSettings {
                 scan_interval_seconds: 3600,
+                tag_patterns: common::settings::default_tag_patterns(),
+                gc_grace_period_hours: common::settings::default_gc_grace_period_hours(),
+                ingest_limits: common::settings::IngestLimits::default(),
             },
             temporal: common::settings::TemporalSettings {
                 server_url: "http://localhost:7233".to_string(),
                 task_queue: "test".to_string(),
             },
             auth,
+            signing: common::settings::SigningSettings::default(),
             debug: true,
         }
     }
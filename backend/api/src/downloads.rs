@@ -0,0 +1,488 @@
+use crate::handlers::DownloadParams;
+use crate::models::ApiResponse;
+use crate::AppState;
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use base64::{engine::general_purpose, Engine as _};
+use common::skills::PackageArtifact;
+use common::ServiceError;
+use serde::Deserialize;
+use std::sync::Arc;
+use tokio_util::io::ReaderStream;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PackageKind {
+    Skill,
+    Plugin,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SignedUrlParams {
+    expires: i64,
+    signature: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum RangeRequest {
+    Full,
+    /// Inclusive byte offsets.
+    Partial(u64, u64),
+    Unsatisfiable,
+}
+
+pub async fn stream_repo_skill(
+    State(state): State<Arc<AppState>>,
+    Path((host, org, repo, name)): Path<(String, String, String, String)>,
+    Query(params): Query<DownloadParams>,
+    headers: HeaderMap,
+) -> Response {
+    match state
+        .services
+        .skill_service
+        .resolve_skill_package(&host, &org, &repo, &name, params.version.as_deref())
+        .await
+    {
+        Ok(artifact) => stream_package(&state, PackageKind::Skill, artifact, &headers).await,
+        Err(e) => error_response(e),
+    }
+}
+
+pub async fn stream_repo_skill_version(
+    State(state): State<Arc<AppState>>,
+    Path((host, org, repo, name, version)): Path<(String, String, String, String, String)>,
+    headers: HeaderMap,
+) -> Response {
+    match state
+        .services
+        .skill_service
+        .resolve_skill_package(&host, &org, &repo, &name, Some(&version))
+        .await
+    {
+        Ok(artifact) => stream_package(&state, PackageKind::Skill, artifact, &headers).await,
+        Err(e) => error_response(e),
+    }
+}
+
+pub async fn stream_repo_plugin(
+    State(state): State<Arc<AppState>>,
+    Path((host, org, repo, plugin_name)): Path<(String, String, String, String)>,
+    Query(params): Query<DownloadParams>,
+    headers: HeaderMap,
+) -> Response {
+    match state
+        .services
+        .plugin_service
+        .resolve_plugin_package(&host, &org, &repo, &plugin_name, params.version.as_deref())
+        .await
+    {
+        Ok(artifact) => stream_package(&state, PackageKind::Plugin, artifact, &headers).await,
+        Err(e) => error_response(e),
+    }
+}
+
+pub async fn stream_repo_plugin_version(
+    State(state): State<Arc<AppState>>,
+    Path((host, org, repo, plugin_name, version)): Path<(String, String, String, String, String)>,
+    headers: HeaderMap,
+) -> Response {
+    match state
+        .services
+        .plugin_service
+        .resolve_plugin_package(&host, &org, &repo, &plugin_name, Some(&version))
+        .await
+    {
+        Ok(artifact) => stream_package(&state, PackageKind::Plugin, artifact, &headers).await,
+        Err(e) => error_response(e),
+    }
+}
+
+// Proxy-mode counterpart of the presigned download handlers: answers with the
+// package bytes instead of a JSON envelope.
+async fn stream_package(
+    state: &AppState,
+    kind: PackageKind,
+    artifact: PackageArtifact,
+    headers: &HeaderMap,
+) -> Response {
+    let s3 = &state.services.s3;
+    let info = match s3.head_file(&artifact.s3_key).await {
+        Ok(info) => info,
+        Err(e) => {
+            return error_response(ServiceError::new(
+                500,
+                format!("Failed to read package: {}", e),
+            ))
+        }
+    };
+
+    // Keys are content addressed, so a recorded digest is a stable strong ETag.
+    let etag = artifact
+        .sha256
+        .as_deref()
+        .or(artifact.md5.as_deref())
+        .map(|hash| format!("\"{}\"", hash))
+        .or(info.e_tag);
+
+    if let (Some(etag), Some(if_none_match)) =
+        (etag.as_deref(), header_str(headers, header::IF_NONE_MATCH))
+    {
+        if etag_matches(if_none_match, etag) {
+            let mut response = StatusCode::NOT_MODIFIED.into_response();
+            insert_header(&mut response, header::ETAG, etag);
+            return response;
+        }
+    }
+
+    // A stale If-Range validator means the client must start over.
+    let range_header = header_str(headers, header::RANGE).filter(|_| {
+        header_str(headers, header::IF_RANGE)
+            .map(|validator| etag.as_deref() == Some(validator.trim()))
+            .unwrap_or(true)
+    });
+
+    let (status, range) = match parse_range(range_header, info.size) {
+        RangeRequest::Full => (StatusCode::OK, None),
+        RangeRequest::Partial(start, end) => (StatusCode::PARTIAL_CONTENT, Some((start, end))),
+        RangeRequest::Unsatisfiable => return range_not_satisfiable(info.size),
+    };
+
+    let stream = match s3.open_file(&artifact.s3_key, range).await {
+        Ok(stream) => stream,
+        Err(e) => {
+            return error_response(ServiceError::new(
+                500,
+                format!("Failed to read package: {}", e),
+            ))
+        }
+    };
+
+    // Resumed downloads only count when they start from the beginning.
+    if range.is_none_or(|(start, _)| start == 0) {
+        if let Err(e) = record_install(state, kind, &artifact).await {
+            return error_response(e);
+        }
+    }
+
+    let content_length = range.map_or(info.size, |(start, end)| end - start + 1);
+    let mut response = Response::new(Body::from_stream(ReaderStream::new(
+        stream.into_async_read(),
+    )));
+    *response.status_mut() = status;
+    response.headers_mut().extend(package_digest_headers(
+        artifact.sha256.as_deref(),
+        Some(info.size as i64),
+    ));
+    response.headers_mut().extend(package_signature_headers(
+        artifact.signature.as_deref(),
+        artifact.signature_key_id.as_deref(),
+    ));
+    // Repr-Digest (RFC 9530) describes the whole package even for a range.
+    if let Some(digest) = artifact.sha256.as_deref().and_then(|h| hex::decode(h).ok()) {
+        insert_header(
+            &mut response,
+            HeaderName::from_static("repr-digest"),
+            &format!("sha-256=:{}:", general_purpose::STANDARD.encode(digest)),
+        );
+    }
+    insert_header(&mut response, header::CONTENT_TYPE, "application/zip");
+    insert_header(
+        &mut response,
+        header::CONTENT_LENGTH,
+        &content_length.to_string(),
+    );
+    insert_header(&mut response, header::ACCEPT_RANGES, "bytes");
+    insert_header(
+        &mut response,
+        header::CONTENT_DISPOSITION,
+        &content_disposition(&artifact.file_name),
+    );
+    if let Some(etag) = etag.as_deref() {
+        insert_header(&mut response, header::ETAG, etag);
+    }
+    if let Some((start, end)) = range {
+        insert_header(
+            &mut response,
+            header::CONTENT_RANGE,
+            &format!("bytes {}-{}/{}", start, end, info.size),
+        );
+    }
+    response
+}
+
+/// Serves an object from local storage to holders of a URL the registry
+/// signed, standing in for presigned bucket URLs.
+pub async fn serve_local_object(
+    State(state): State<Arc<AppState>>,
+    Path(key): Path<String>,
+    Query(params): Query<SignedUrlParams>,
+    headers: HeaderMap,
+) -> Response {
+    let Some(local) = state.services.s3.as_local() else {
+        return error_response(ServiceError::new(404, "Not found"));
+    };
+    if !local.verify_url(&key, params.expires, &params.signature, chrono::Utc::now()) {
+        return error_response(ServiceError::new(403, "Download URL is invalid or expired"));
+    }
+
+    let info = match local.head_file(&key).await {
+        Ok(info) => info,
+        Err(_) => return error_response(ServiceError::new(404, "Object not found")),
+    };
+    let (status, range) = match parse_range(header_str(&headers, header::RANGE), info.size) {
+        RangeRequest::Full => (StatusCode::OK, None),
+        RangeRequest::Partial(start, end) => (StatusCode::PARTIAL_CONTENT, Some((start, end))),
+        RangeRequest::Unsatisfiable => return range_not_satisfiable(info.size),
+    };
+    let stream = match local.open_file(&key, range).await {
+        Ok(stream) => stream,
+        Err(e) => {
+            return error_response(ServiceError::new(
+                500,
+                format!("Failed to read object: {}", e),
+            ))
+        }
+    };
+
+    let content_length = range.map_or(info.size, |(start, end)| end - start + 1);
+    let mut response = Response::new(Body::from_stream(ReaderStream::new(
+        stream.into_async_read(),
+    )));
+    *response.status_mut() = status;
+    let content_type = if key.ends_with(".zip") {
+        "application/zip"
+    } else {
+        "application/octet-stream"
+    };
+    insert_header(&mut response, header::CONTENT_TYPE, content_type);
+    insert_header(
+        &mut response,
+        header::CONTENT_LENGTH,
+        &content_length.to_string(),
+    );
+    insert_header(&mut response, header::ACCEPT_RANGES, "bytes");
+    if let Some((start, end)) = range {
+        insert_header(
+            &mut response,
+            header::CONTENT_RANGE,
+            &format!("bytes {}-{}/{}", start, end, info.size),
+        );
+    }
+    response
+}
+
+fn range_not_satisfiable(size: u64) -> Response {
+    let mut response = StatusCode::RANGE_NOT_SATISFIABLE.into_response();
+    insert_header(
+        &mut response,
+        header::CONTENT_RANGE,
+        &format!("bytes */{}", size),
+    );
+    response
+}
+
+async fn record_install(
+    state: &AppState,
+    kind: PackageKind,
+    artifact: &PackageArtifact,
+) -> Result<(), ServiceError> {
+    match kind {
+        PackageKind::Skill => {
+            state
+                .services
+                .skill_service
+                .record_skill_install(artifact)
+                .await
+        }
+        PackageKind::Plugin => {
+            state
+                .services
+                .plugin_service
+                .record_plugin_install(artifact)
+                .await
+        }
+    }
+}
+
+/// Checksum headers sent with every download, in both download modes.
+pub(crate) fn package_digest_headers(sha256: Option<&str>, size: Option<i64>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Some(value) = sha256.and_then(|v| HeaderValue::from_str(v).ok()) {
+        headers.insert(HeaderName::from_static("x-package-sha256"), value);
+    }
+    if let Some(size) = size {
+        headers.insert(
+            HeaderName::from_static("x-package-size"),
+            HeaderValue::from(size),
+        );
+    }
+    headers
+}
+
+pub(crate) fn package_signature_headers(
+    signature: Option<&str>,
+    key_id: Option<&str>,
+) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    let (Some(signature), Some(key_id)) = (signature, key_id) else {
+        return headers;
+    };
+    if let (Ok(signature), Ok(key_id)) = (
+        HeaderValue::from_str(signature),
+        HeaderValue::from_str(key_id),
+    ) {
+        headers.insert(HeaderName::from_static("x-package-signature"), signature);
+        headers.insert(
+            HeaderName::from_static("x-package-signature-key-id"),
+            key_id,
+        );
+    }
+    headers
+}
+
+fn error_response(e: ServiceError) -> Response {
+    let status = StatusCode::from_u16(e.code as u16).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    (status, Json(ApiResponse::<()>::error(e.code, e.message))).into_response()
+}
+
+fn header_str(headers: &HeaderMap, name: HeaderName) -> Option<&str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+fn insert_header(response: &mut Response, name: HeaderName, value: &str) {
+    if let Ok(value) = HeaderValue::from_str(value) {
+        response.headers_mut().insert(name, value);
+    }
+}
+
+/// Weak comparison as required for `If-None-Match`.
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let strip = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = strip(etag);
+    if_none_match
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || strip(candidate) == etag)
+}
+
+/// Parses a single `bytes=` range. Multiple ranges and malformed headers fall
+/// back to the full body, which RFC 9110 permits.
+fn parse_range(header: Option<&str>, size: u64) -> RangeRequest {
+    let Some(spec) = header.and_then(|h| h.trim().strip_prefix("bytes=")) else {
+        return RangeRequest::Full;
+    };
+    if spec.contains(',') {
+        return RangeRequest::Full;
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return RangeRequest::Full;
+    };
+
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", "") => return RangeRequest::Full,
+        ("", suffix) => match suffix.parse::<u64>() {
+            Ok(0) => return RangeRequest::Unsatisfiable,
+            Ok(len) => (size.saturating_sub(len), size.saturating_sub(1)),
+            Err(_) => return RangeRequest::Full,
+        },
+        (start, "") => match start.parse::<u64>() {
+            Ok(start) => (start, size.saturating_sub(1)),
+            Err(_) => return RangeRequest::Full,
+        },
+        (start, end) => match (start.parse::<u64>(), end.parse::<u64>()) {
+            (Ok(start), Ok(end)) if start <= end => (start, end.min(size.saturating_sub(1))),
+            _ => return RangeRequest::Full,
+        },
+    };
+
+    if size == 0 || start >= size {
+        RangeRequest::Unsatisfiable
+    } else {
+        RangeRequest::Partial(start, end)
+    }
+}
+
+fn content_disposition(file_name: &str) -> String {
+    let safe: String = file_name
+        .chars()
+        .map(|c| match c {
+            '"' | '\\' | '/' => '_',
+            c if c.is_control() || !c.is_ascii() => '_',
+            c => c,
+        })
+        .collect();
+    format!("attachment; filename=\"{}\"", safe)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_range_handles_single_ranges() {
+        assert_eq!(parse_range(None, 100), RangeRequest::Full);
+        assert_eq!(
+            parse_range(Some("bytes=0-9"), 100),
+            RangeRequest::Partial(0, 9)
+        );
+        assert_eq!(
+            parse_range(Some("bytes=90-"), 100),
+            RangeRequest::Partial(90, 99)
+        );
+        assert_eq!(
+            parse_range(Some("bytes=-10"), 100),
+            RangeRequest::Partial(90, 99)
+        );
+        assert_eq!(
+            parse_range(Some("bytes=50-500"), 100),
+            RangeRequest::Partial(50, 99)
+        );
+        assert_eq!(
+            parse_range(Some("bytes=100-"), 100),
+            RangeRequest::Unsatisfiable
+        );
+        assert_eq!(parse_range(Some("bytes=0-1,5-6"), 100), RangeRequest::Full);
+        assert_eq!(parse_range(Some("items=0-1"), 100), RangeRequest::Full);
+        assert_eq!(parse_range(Some("bytes=9-1"), 100), RangeRequest::Full);
+    }
+
+    #[test]
+    fn etag_matches_uses_weak_comparison() {
+        assert!(etag_matches("\"abc\"", "\"abc\""));
+        assert!(etag_matches("W/\"abc\"", "\"abc\""));
+        assert!(etag_matches("\"xyz\", \"abc\"", "\"abc\""));
+        assert!(etag_matches("*", "\"abc\""));
+        assert!(!etag_matches("\"xyz\"", "\"abc\""));
+    }
+
+    #[test]
+    fn package_digest_headers_skip_unknown_values() {
+        let headers = package_digest_headers(Some("abc"), Some(42));
+        assert_eq!(headers["x-package-sha256"], "abc");
+        assert_eq!(headers["x-package-size"], "42");
+        assert!(package_digest_headers(None, None).is_empty());
+    }
+
+    #[test]
+    fn package_signature_headers_need_signature_and_key() {
+        let headers = package_signature_headers(Some("c2ln"), Some("key-1"));
+        assert_eq!(headers["x-package-signature"], "c2ln");
+        assert_eq!(headers["x-package-signature-key-id"], "key-1");
+        assert!(package_signature_headers(Some("c2ln"), None).is_empty());
+    }
+
+    #[test]
+    fn content_disposition_escapes_file_names() {
+        assert_eq!(
+            content_disposition("pdf-1.0.0.zip"),
+            "attachment; filename=\"pdf-1.0.0.zip\""
+        );
+        assert_eq!(
+            content_disposition("we\"ird/name.zip"),
+            "attachment; filename=\"we_ird_name.zip\""
+        );
+    }
+}
@@ -1,14 +1,25 @@
+use crate::downloads::{package_digest_headers, package_signature_headers};
 use crate::models::ApiResponse;
 use crate::AppState;
 use axum::{
     extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use chrono::Utc;
-use common::entities::discovery_registries;
+use common::domain::manifest::FileEntry;
+use common::domain::signing::{verification_keys, VerificationKey};
+use common::entities::{discovery_registries, skill_registry};
+use common::infra::github_http::{
+    rate_limit_budget, send_cached_once, HttpCache, RateLimitResource,
+};
 use common::plugins::{PluginListItemDto, SkillSummaryDto};
 use common::repositories::skills::ListSkillsParams;
-use common::skills::{DownloadSkillResult, PaginatedSkillsResponse};
+use common::skills::{
+    DownloadSkillResult, PackageFileContent, PaginatedSkillsResponse, VersionDiff,
+};
+use common::ServiceError;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use std::sync::Arc;
@@ -17,6 +28,9 @@ use temporalio_sdk_core::Url as TemporalUrl;
 use url::Url;
 
 const DEFAULT_GITHUB_API_URL: &str = "https://api.github.com";
+const DEFAULT_GITLAB_API_URL: &str = "https://gitlab.com/api/v4";
+const DEFAULT_GITEA_API_URL: &str = "https://codeberg.org/api/v1";
+pub(crate) const SYNC_REPO_WORKFLOW: &str = "sync_repo_workflow";
 
 #[derive(Deserialize)]
 pub struct SearchParams {
@@ -33,6 +47,17 @@ pub struct SearchParams {
     pub has_version: Option<bool>,
 }
 
+#[derive(Deserialize)]
+pub struct DownloadParams {
+    pub version: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct DiffParams {
+    pub from: String,
+    pub to: String,
+}
+
 #[derive(Serialize)]
 pub struct DiscoveryRegistryDto {
     pub id: i32,
@@ -41,6 +66,7 @@ pub struct DiscoveryRegistryDto {
     pub queries: Vec<String>,
     pub schedule_interval_seconds: i64,
     pub token_configured: bool,
+    pub webhook_secret_configured: bool,
     pub last_health_status: Option<String>,
     pub last_health_message: Option<String>,
     pub last_health_checked_at: Option<chrono::NaiveDateTime>,
@@ -48,6 +74,10 @@ pub struct DiscoveryRegistryDto {
     pub last_run_status: Option<String>,
     pub last_run_message: Option<String>,
     pub next_run_at: Option<chrono::NaiveDateTime>,
+    pub rate_limit_remaining: Option<i32>,
+    pub rate_limit_reset_at: Option<chrono::NaiveDateTime>,
+    pub search_rate_limit_remaining: Option<i32>,
+    pub search_rate_limit_reset_at: Option<chrono::NaiveDateTime>,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
 }
@@ -69,6 +99,12 @@ pub struct UpdateDiscoveryRegistryRequest {
     pub url: String,
 }
 
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateWebhookSecretRequest {
+    pub webhook_secret: Option<String>,
+}
+
 #[derive(Serialize)]
 pub struct DiscoveryRegistryHealthTestDto {
     pub ok: bool,
@@ -85,13 +121,34 @@ pub struct TriggerWorkflowDto {
     pub started_at: chrono::NaiveDateTime,
 }
 
+#[derive(Deserialize)]
+pub struct SubmitRepositoryRequest {
+    pub url: String,
+    pub provider: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct SubmitRepositoryDto {
+    pub registry_id: i32,
+    pub created: bool,
+    pub host: String,
+    pub owner: String,
+    pub name: String,
+    pub url: String,
+    pub workflow_id: String,
+    pub started_at: chrono::NaiveDateTime,
+}
+
 #[derive(Serialize)]
 pub struct DownloadSkillResponse {
     pub download_url: String,
     pub expires_at: String,
     pub md5: Option<String>,
+    pub sha256: Option<String>,
     pub version: String,
     pub file_size: Option<i64>,
+    pub signature: Option<String>,
+    pub signature_key_id: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -108,6 +165,33 @@ pub struct DeleteDiscoveryRegistryRequest {
 fn map_provider(platform: &discovery_registries::Platform) -> String {
     match platform {
         discovery_registries::Platform::Github => "github".to_string(),
+        discovery_registries::Platform::Gitlab => "gitlab".to_string(),
+        discovery_registries::Platform::Gitea => "gitea".to_string(),
+    }
+}
+
+fn parse_provider(raw: &str) -> Option<discovery_registries::Platform> {
+    match raw.trim().to_lowercase().as_str() {
+        "github" => Some(discovery_registries::Platform::Github),
+        "gitlab" => Some(discovery_registries::Platform::Gitlab),
+        "gitea" | "forgejo" => Some(discovery_registries::Platform::Gitea),
+        _ => None,
+    }
+}
+
+fn to_repo_platform(platform: &discovery_registries::Platform) -> skill_registry::Platform {
+    match platform {
+        discovery_registries::Platform::Github => skill_registry::Platform::Github,
+        discovery_registries::Platform::Gitlab => skill_registry::Platform::Gitlab,
+        discovery_registries::Platform::Gitea => skill_registry::Platform::Gitea,
+    }
+}
+
+fn default_api_url(platform: &discovery_registries::Platform) -> &'static str {
+    match platform {
+        discovery_registries::Platform::Github => DEFAULT_GITHUB_API_URL,
+        discovery_registries::Platform::Gitlab => DEFAULT_GITLAB_API_URL,
+        discovery_registries::Platform::Gitea => DEFAULT_GITEA_API_URL,
     }
 }
 
@@ -148,6 +232,10 @@ fn to_registry_dto(
         queries: config.queries.clone(),
         schedule_interval_seconds: config.schedule_interval_seconds,
         token_configured: !config.token.trim().is_empty(),
+        webhook_secret_configured: config
+            .webhook_secret
+            .as_deref()
+            .is_some_and(|s| !s.is_empty()),
         last_health_status: config.last_health_status.clone(),
         last_health_message: config.last_health_message.clone(),
         last_health_checked_at: config.last_health_checked_at,
@@ -155,6 +243,10 @@ fn to_registry_dto(
         last_run_status: config.last_run_status.clone(),
         last_run_message: config.last_run_message.clone(),
         next_run_at: config.next_run_at,
+        rate_limit_remaining: config.rate_limit_remaining,
+        rate_limit_reset_at: config.rate_limit_reset_at,
+        search_rate_limit_remaining: config.search_rate_limit_remaining,
+        search_rate_limit_reset_at: config.search_rate_limit_reset_at,
         created_at: config.created_at,
         updated_at: config.updated_at,
     }
@@ -190,6 +282,43 @@ fn create_json_payload(
     )
 }
 
+pub(crate) async fn start_workflow(
+    temporal: &common::settings::TemporalSettings,
+    workflow_type: &str,
+    workflow_id: String,
+    input: &impl serde::Serialize,
+) -> Result<(), String> {
+    let temporal_url = TemporalUrl::from_str(&temporal.server_url)
+        .map_err(|e| format!("invalid temporal server url: {}", e))?;
+
+    let client_options = ClientOptions::builder()
+        .target_url(temporal_url)
+        .client_name("skillregistry-api")
+        .client_version("0.1.0")
+        .build();
+
+    let client = client_options
+        .connect("default", None)
+        .await
+        .map_err(|e| format!("failed to connect to temporal: {}", e))?;
+
+    let payload = create_json_payload(input)?;
+
+    client
+        .start_workflow(
+            vec![payload],
+            temporal.task_queue.clone(),
+            workflow_id,
+            workflow_type.to_string(),
+            None,
+            WorkflowOptions::default(),
+        )
+        .await
+        .map_err(|e| format!("failed to trigger workflow: {}", e))?;
+
+    Ok(())
+}
+
 pub async fn list_skills(
     State(state): State<Arc<AppState>>,
     Query(params): Query<SearchParams>,
@@ -244,29 +373,66 @@ pub async fn get_repo_skill_version(
     }
 }
 
+type DownloadResponse = (HeaderMap, Json<ApiResponse<DownloadSkillResponse>>);
+
 fn to_download_response(result: DownloadSkillResult) -> DownloadSkillResponse {
     DownloadSkillResponse {
         download_url: result.download_url,
         expires_at: result.expires_at.to_rfc3339(),
         md5: result.md5,
+        sha256: result.sha256,
         version: result.version,
         file_size: result.file_size,
+        signature: result.signature,
+        signature_key_id: result.signature_key_id,
+    }
+}
+
+fn download_response(result: Result<DownloadSkillResult, ServiceError>) -> DownloadResponse {
+    match result {
+        Ok(result) => {
+            let mut headers = package_digest_headers(result.sha256.as_deref(), result.file_size);
+            headers.extend(package_signature_headers(
+                result.signature.as_deref(),
+                result.signature_key_id.as_deref(),
+            ));
+            (
+                headers,
+                Json(ApiResponse::success(to_download_response(result))),
+            )
+        }
+        Err(e) => (
+            HeaderMap::new(),
+            Json(ApiResponse::error(e.code, e.message)),
+        ),
     }
 }
 
 pub async fn download_repo_skill(
     State(state): State<Arc<AppState>>,
     Path((host, org, repo, name)): Path<(String, String, String, String)>,
-) -> Json<ApiResponse<DownloadSkillResponse>> {
-    match state
-        .services
-        .skill_service
-        .download_skill(&host, &org, &repo, &name)
-        .await
-    {
-        Ok(result) => Json(ApiResponse::success(to_download_response(result))),
-        Err(e) => Json(ApiResponse::error(e.code, e.message)),
-    }
+    Query(params): Query<DownloadParams>,
+) -> DownloadResponse {
+    download_response(
+        state
+            .services
+            .skill_service
+            .download_skill(&host, &org, &repo, &name, params.version.as_deref())
+            .await,
+    )
+}
+
+pub async fn download_repo_skill_version(
+    State(state): State<Arc<AppState>>,
+    Path((host, org, repo, name, version)): Path<(String, String, String, String, String)>,
+) -> DownloadResponse {
+    download_response(
+        state
+            .services
+            .skill_service
+            .download_skill(&host, &org, &repo, &name, Some(&version))
+            .await,
+    )
 }
 
 pub async fn list_repo_plugins(
@@ -305,6 +471,163 @@ pub async fn get_repo_plugin(
     }
 }
 
+pub async fn download_repo_plugin(
+    State(state): State<Arc<AppState>>,
+    Path((host, org, repo, plugin_name)): Path<(String, String, String, String)>,
+    Query(params): Query<DownloadParams>,
+) -> DownloadResponse {
+    download_response(
+        state
+            .services
+            .plugin_service
+            .download_plugin(&host, &org, &repo, &plugin_name, params.version.as_deref())
+            .await,
+    )
+}
+
+pub async fn download_repo_plugin_version(
+    State(state): State<Arc<AppState>>,
+    Path((host, org, repo, plugin_name, version)): Path<(String, String, String, String, String)>,
+) -> DownloadResponse {
+    download_response(
+        state
+            .services
+            .plugin_service
+            .download_plugin(&host, &org, &repo, &plugin_name, Some(&version))
+            .await,
+    )
+}
+
+pub async fn list_repo_skill_files(
+    State(state): State<Arc<AppState>>,
+    Path((host, org, repo, name, version)): Path<(String, String, String, String, String)>,
+) -> Json<ApiResponse<Vec<FileEntry>>> {
+    match state
+        .services
+        .skill_service
+        .list_skill_files(&host, &org, &repo, &name, &version)
+        .await
+    {
+        Ok(result) => Json(ApiResponse::success(result)),
+        Err(e) => Json(ApiResponse::error(e.code, e.message)),
+    }
+}
+
+pub async fn get_repo_skill_file(
+    State(state): State<Arc<AppState>>,
+    Path((host, org, repo, name, version, path)): Path<(
+        String,
+        String,
+        String,
+        String,
+        String,
+        String,
+    )>,
+) -> Json<ApiResponse<PackageFileContent>> {
+    match state
+        .services
+        .skill_service
+        .get_skill_file(&host, &org, &repo, &name, &version, &path)
+        .await
+    {
+        Ok(result) => Json(ApiResponse::success(result)),
+        Err(e) => Json(ApiResponse::error(e.code, e.message)),
+    }
+}
+
+pub async fn list_repo_plugin_files(
+    State(state): State<Arc<AppState>>,
+    Path((host, org, repo, plugin_name, version)): Path<(String, String, String, String, String)>,
+) -> Json<ApiResponse<Vec<FileEntry>>> {
+    match state
+        .services
+        .plugin_service
+        .list_plugin_files(&host, &org, &repo, &plugin_name, &version)
+        .await
+    {
+        Ok(result) => Json(ApiResponse::success(result)),
+        Err(e) => Json(ApiResponse::error(e.code, e.message)),
+    }
+}
+
+pub async fn get_repo_plugin_file(
+    State(state): State<Arc<AppState>>,
+    Path((host, org, repo, plugin_name, version, path)): Path<(
+        String,
+        String,
+        String,
+        String,
+        String,
+        String,
+    )>,
+) -> Json<ApiResponse<PackageFileContent>> {
+    match state
+        .services
+        .plugin_service
+        .get_plugin_file(&host, &org, &repo, &plugin_name, &version, &path)
+        .await
+    {
+        Ok(result) => Json(ApiResponse::success(result)),
+        Err(e) => Json(ApiResponse::error(e.code, e.message)),
+    }
+}
+
+pub async fn diff_repo_skill(
+    State(state): State<Arc<AppState>>,
+    Path((host, org, repo, name)): Path<(String, String, String, String)>,
+    Query(params): Query<DiffParams>,
+) -> Json<ApiResponse<VersionDiff>> {
+    match state
+        .services
+        .skill_service
+        .diff_skill_versions(&host, &org, &repo, &name, &params.from, &params.to)
+        .await
+    {
+        Ok(result) => Json(ApiResponse::success(result)),
+        Err(e) => Json(ApiResponse::error(e.code, e.message)),
+    }
+}
+
+pub async fn diff_repo_plugin(
+    State(state): State<Arc<AppState>>,
+    Path((host, org, repo, plugin_name)): Path<(String, String, String, String)>,
+    Query(params): Query<DiffParams>,
+) -> Json<ApiResponse<VersionDiff>> {
+    match state
+        .services
+        .plugin_service
+        .diff_plugin_versions(&host, &org, &repo, &plugin_name, &params.from, &params.to)
+        .await
+    {
+        Ok(result) => Json(ApiResponse::success(result)),
+        Err(e) => Json(ApiResponse::error(e.code, e.message)),
+    }
+}
+
+#[derive(Serialize)]
+pub struct SigningKeysResponse {
+    pub keys: Vec<VerificationKey>,
+}
+
+/// Public keys that verify package signatures. Served as a plain document
+/// so clients can pin and cache it.
+pub async fn signing_keys(State(state): State<Arc<AppState>>) -> Response {
+    match verification_keys(&state.settings.signing) {
+        Ok(keys) => Json(SigningKeysResponse { keys }).into_response(),
+        Err(e) => {
+            tracing::error!(error = ?e, "Invalid signing configuration");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error(
+                    500,
+                    "Signing keys are misconfigured".to_string(),
+                )),
+            )
+                .into_response()
+        }
+    }
+}
+
 pub async fn list_repo_skills(
     State(state): State<Arc<AppState>>,
     Path((host, org, repo)): Path<(String, String, String)>,
@@ -401,6 +724,60 @@ pub async fn get_repo_plugin_command(
     }
 }
 
+pub async fn submit_repository(
+    State(state): State<Arc<AppState>>,
+    _user: crate::auth::AuthUser,
+    Json(req): Json<SubmitRepositoryRequest>,
+) -> Json<ApiResponse<SubmitRepositoryDto>> {
+    let platform = match req.provider.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(raw) => match parse_provider(raw) {
+            Some(platform) => Some(to_repo_platform(&platform)),
+            None => {
+                return Json(ApiResponse::error(
+                    400,
+                    "provider must be one of: github, gitlab, gitea".to_string(),
+                ))
+            }
+        },
+    };
+
+    let submitted = match state
+        .services
+        .registry_service
+        .submit_repository(&req.url, platform)
+        .await
+    {
+        Ok(submitted) => submitted,
+        Err(e) => return Json(ApiResponse::error(e.code, e.message)),
+    };
+
+    let repo = submitted.repo;
+    let started_at = Utc::now().naive_utc();
+    let workflow_id = format!("submit-sync-{}-{}", repo.id, uuid::Uuid::new_v4());
+    if let Err(e) = start_workflow(
+        &state.settings.temporal,
+        SYNC_REPO_WORKFLOW,
+        workflow_id.clone(),
+        &repo.id,
+    )
+    .await
+    {
+        return Json(ApiResponse::error(500, e));
+    }
+
+    Json(ApiResponse::success(SubmitRepositoryDto {
+        registry_id: repo.id,
+        created: submitted.created,
+        host: repo.host.unwrap_or_default(),
+        owner: repo.owner,
+        name: repo.name,
+        url: repo.url,
+        workflow_id,
+        started_at,
+    }))
+}
+
 pub async fn list_discovery_registries(
     State(state): State<Arc<AppState>>,
     user: crate::auth::AuthUser,
@@ -427,12 +804,12 @@ pub async fn create_discovery_registry(
         return Json(ApiResponse::error(403, "admin access required".to_string()));
     }
 
-    if req.provider.trim().to_lowercase() != "github" {
+    let Some(platform) = parse_provider(&req.provider) else {
         return Json(ApiResponse::error(
             400,
-            "only github provider is supported".to_string(),
+            "provider must be one of: github, gitlab, gitea".to_string(),
         ));
-    }
+    };
 
     let token = req.token.trim().to_string();
     if token.is_empty() {
@@ -454,7 +831,13 @@ pub async fn create_discovery_registry(
         ));
     }
 
-    let api_url = match normalize_api_url(req.url.as_deref()) {
+    let raw_url = req
+        .url
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .unwrap_or(default_api_url(&platform));
+    let api_url = match normalize_api_url(Some(raw_url)) {
         Ok(url) => url,
         Err(msg) => return Json(ApiResponse::error(400, msg)),
     };
@@ -462,7 +845,13 @@ pub async fn create_discovery_registry(
     match state
         .services
         .discovery_registry_service
-        .create_github_registry(token, queries, req.schedule_interval_seconds, api_url)
+        .create_registry(
+            platform,
+            token,
+            queries,
+            req.schedule_interval_seconds,
+            api_url,
+        )
         .await
     {
         Ok(config) => Json(ApiResponse::success(to_registry_dto(&config))),
@@ -512,6 +901,35 @@ pub async fn update_discovery_registry(
     }
 }
 
+pub async fn update_discovery_registry_webhook_secret(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    user: crate::auth::AuthUser,
+    Json(req): Json<UpdateWebhookSecretRequest>,
+) -> Json<ApiResponse<DiscoveryRegistryDto>> {
+    if !is_admin(&user) {
+        return Json(ApiResponse::error(403, "admin access required".to_string()));
+    }
+
+    let secret = req
+        .webhook_secret
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    let service = &state.services.discovery_registry_service;
+    match service.update_webhook_secret(id, secret).await {
+        Ok(true) => {}
+        Ok(false) => return Json(ApiResponse::error(404, "registry not found".to_string())),
+        Err(e) => return Json(ApiResponse::error(500, e.to_string())),
+    }
+
+    match service.find_by_id(id).await {
+        Ok(Some(config)) => Json(ApiResponse::success(to_registry_dto(&config))),
+        Ok(None) => Json(ApiResponse::error(404, "registry not found".to_string())),
+        Err(e) => Json(ApiResponse::error(500, e.to_string())),
+    }
+}
+
 pub async fn validate_delete_discovery_registry(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i32>,
@@ -583,6 +1001,57 @@ pub async fn delete_discovery_registry(
     }
 }
 
+async fn check_registry_health(
+    config: &common::services::discovery_registries::DiscoveryRegistryConfig,
+    cache: &dyn HttpCache,
+) -> (bool, String) {
+    let client = reqwest::Client::new();
+    let api_url = config.api_url.trim_end_matches('/');
+
+    let (label, request) = match config.platform {
+        discovery_registries::Platform::Github => (
+            "GitHub",
+            client
+                .get(format!("{}/rate_limit", api_url))
+                .header("Accept", "application/vnd.github+json")
+                .bearer_auth(&config.token),
+        ),
+        discovery_registries::Platform::Gitlab => (
+            "GitLab",
+            client
+                .get(format!("{}/user", api_url))
+                .header("PRIVATE-TOKEN", &config.token),
+        ),
+        discovery_registries::Platform::Gitea => (
+            "Gitea",
+            client
+                .get(format!("{}/user", api_url))
+                .header("Authorization", format!("token {}", config.token)),
+        ),
+    };
+
+    let request = request.header("User-Agent", "skillregistry");
+    let result = match config.platform {
+        discovery_registries::Platform::Github => send_cached_once(request, cache)
+            .await
+            .map(|(status, _)| status),
+        _ => request
+            .send()
+            .await
+            .map(|res| res.status())
+            .map_err(anyhow::Error::from),
+    };
+
+    match result {
+        Ok(status) if status.is_success() => (true, format!("{} API reachable", label)),
+        Ok(status) => (
+            false,
+            format!("{} API returned status {}", label, status.as_u16()),
+        ),
+        Err(e) => (false, format!("{} API request failed: {}", label, e)),
+    }
+}
+
 pub async fn test_discovery_registry_health(
     State(state): State<Arc<AppState>>,
     Path(id): Path<i32>,
@@ -604,26 +1073,8 @@ pub async fn test_discovery_registry_health(
     };
 
     let started_at = Utc::now().naive_utc();
+    let (ok, message) = check_registry_health(&config, state.repos.http_cache.as_ref()).await;
     let checked_at = Utc::now().naive_utc();
-    let client = reqwest::Client::new();
-    let health_url = format!("{}/rate_limit", config.api_url.trim_end_matches('/'));
-
-    let response = client
-        .get(health_url)
-        .header("User-Agent", "skillregistry")
-        .header("Accept", "application/vnd.github+json")
-        .bearer_auth(config.token)
-        .send()
-        .await;
-
-    let (ok, message) = match response {
-        Ok(res) if res.status().is_success() => (true, "GitHub API reachable".to_string()),
-        Ok(res) => (
-            false,
-            format!("GitHub API returned status {}", res.status().as_u16()),
-        ),
-        Err(e) => (false, format!("GitHub API request failed: {}", e)),
-    };
 
     let status = if ok { "ok" } else { "error" }.to_string();
     if let Err(e) = state
@@ -635,6 +1086,30 @@ pub async fn test_discovery_registry_health(
         return Json(ApiResponse::error(500, e.to_string()));
     }
 
+    for resource in [RateLimitResource::Core, RateLimitResource::Search] {
+        let Some(budget) = rate_limit_budget(&config.api_url, Some(&config.token), resource) else {
+            continue;
+        };
+        let remaining = i32::try_from(budget.remaining).unwrap_or(i32::MAX);
+        if let Err(e) = state
+            .services
+            .discovery_registry_service
+            .update_rate_limit(
+                id,
+                resource,
+                Some(remaining),
+                Some(budget.reset_at.naive_utc()),
+            )
+            .await
+        {
+            tracing::warn!(
+                registry_id = id,
+                "Failed to record rate limit budget: {}",
+                e
+            );
+        }
+    }
+
     Json(ApiResponse::success(DiscoveryRegistryHealthTestDto {
         ok,
         message,
@@ -669,56 +1144,16 @@ pub async fn trigger_discovery_registry(
 
     let started_at = Utc::now().naive_utc();
     let workflow_id = format!("trigger-registry-{}-{}", id, uuid::Uuid::new_v4());
-    let task_queue = state.settings.temporal.task_queue.clone();
-
-    let temporal_url = match TemporalUrl::from_str(&state.settings.temporal.server_url) {
-        Ok(url) => url,
-        Err(e) => {
-            return Json(ApiResponse::error(
-                500,
-                format!("invalid temporal server url: {}", e),
-            ))
-        }
-    };
-
-    let client_options = ClientOptions::builder()
-        .target_url(temporal_url)
-        .client_name("skillregistry-api")
-        .client_version("0.1.0")
-        .build();
-
-    let client = match client_options.connect("default", None).await {
-        Ok(client) => client,
-        Err(e) => {
-            return Json(ApiResponse::error(
-                500,
-                format!("failed to connect to temporal: {}", e),
-            ))
-        }
-    };
-
-    let payload = match create_json_payload(&id) {
-        Ok(payload) => payload,
-        Err(e) => return Json(ApiResponse::error(500, e)),
-    };
-
-    let opts = WorkflowOptions::default();
-    if let Err(e) = client
-        .start_workflow(
-            vec![payload],
-            task_queue,
-            workflow_id.clone(),
-            "trigger_registry_workflow".to_string(),
-            None,
-            opts,
-        )
-        .await
-    {
-        return Json(ApiResponse::error(
-            500,
-            format!("failed to trigger workflow: {}", e),
-        ));
-    }
+    if let Err(e) = start_workflow(
+        &state.settings.temporal,
+        "trigger_registry_workflow",
+        workflow_id.clone(),
+        &id,
+    )
+    .await
+    {
+        return Json(ApiResponse::error(500, e));
+    }
 
     Json(ApiResponse::success(TriggerWorkflowDto {
         ok: true,
@@ -737,7 +1172,7 @@ mod tests {
         plugin_components, plugin_versions, plugins, skill_registry, skill_versions, skills,
     };
     use migration::MigratorTrait;
-    use sea_orm::{ActiveModelTrait, Database, Set};
+    use sea_orm::{ActiveModelTrait, ColumnTrait, Database, EntityTrait, QueryFilter, Set};
 
     fn test_settings() -> common::settings::Settings {
         common::settings::Settings {
@@ -752,7 +1187,9 @@ mod tests {
                 access_key_id: Some("test-access".to_string()),
                 secret_access_key: Some("test-secret".to_string()),
                 force_path_style: false,
+                download_mode: common::settings::DownloadMode::Presigned,
             },
+            storage: common::settings::StorageSettings::default(),
             github: common::settings::GithubSettings {
                 search_keywords: "topic:agent-skill".to_string(),
                 token: None,
@@ -760,12 +1197,16 @@ mod tests {
             },
             worker: common::settings::WorkerSettings {
                 scan_interval_seconds: 3600,
+                tag_patterns: common::settings::default_tag_patterns(),
+                gc_grace_period_hours: common::settings::default_gc_grace_period_hours(),
+                ingest_limits: common::settings::IngestLimits::default(),
             },
             temporal: common::settings::TemporalSettings {
                 server_url: "http://localhost:7233".to_string(),
                 task_queue: "test".to_string(),
             },
             auth: common::settings::AuthSettings::default(),
+            signing: common::settings::SigningSettings::default(),
             debug: true,
         }
     }
@@ -844,11 +1285,38 @@ mod tests {
                 "https://oss.local/skills/demo-skill/1.0.0.zip".to_string(),
             )),
             file_hash: Set(Some("abc123".to_string())),
+            sha256: Set(Some(
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855".to_string(),
+            )),
+            file_size: Set(Some(2048)),
             metadata: Set(Some(serde_json::json!({
                 "compatibility": ["claude", "codex"],
                 "allowed-tools": ["bash", "rg"],
                 "license": "MIT"
             }))),
+            file_manifest: Set(Some(serde_json::json!([
+                {
+                    "path": "demo-skill/SKILL.md",
+                    "size": 6,
+                    "sha256": "a".repeat(64),
+                    "mime_type": "text/markdown"
+                },
+                {
+                    "path": "demo-skill/logo.png",
+                    "size": 128,
+                    "sha256": "b".repeat(64),
+                    "mime_type": "image/png"
+                },
+                {
+                    "path": "demo-skill/data.csv",
+                    "size": 4 * 1024 * 1024,
+                    "sha256": "c".repeat(64),
+                    "mime_type": "text/csv"
+                }
+            ]))),
+            commit_sha: Set(Some("0123456789abcdef0123456789abcdef01234567".to_string())),
+            git_ref: Set(Some("refs/heads/main".to_string())),
+            source_path: Set(Some("skills/demo".to_string())),
             created_at: Set(now),
             ..Default::default()
         }
@@ -975,7 +1443,15 @@ mod tests {
         )
         .await;
         assert_eq!(skill_detail.0.code, 200);
-        assert_eq!(skill_detail.0.data.unwrap().skill["name"], "demo-skill");
+        let skill_detail = skill_detail.0.data.unwrap();
+        assert_eq!(skill_detail.skill["name"], "demo-skill");
+        let source = skill_detail.source.unwrap();
+        assert_eq!(
+            source.commit_sha,
+            "0123456789abcdef0123456789abcdef01234567"
+        );
+        assert_eq!(source.git_ref.as_deref(), Some("refs/heads/main"));
+        assert_eq!(source.source_path.as_deref(), Some("skills/demo"));
 
         let missing_skill_detail = get_repo_skill_detail(
             State(state.clone()),
@@ -1002,6 +1478,23 @@ mod tests {
         .await;
         assert_eq!(version_detail.0.code, 200);
 
+        let latest_channel = get_repo_skill_version(
+            State(state.clone()),
+            Path((
+                "github.com".to_string(),
+                "acme".to_string(),
+                "skills-repo".to_string(),
+                "demo-skill".to_string(),
+                "latest".to_string(),
+            )),
+        )
+        .await;
+        assert_eq!(latest_channel.0.code, 200);
+        assert_eq!(
+            latest_channel.0.data.unwrap().skill_version["version"],
+            "1.0.0"
+        );
+
         let missing_version = get_repo_skill_version(
             State(state.clone()),
             Path((
@@ -1023,15 +1516,90 @@ mod tests {
                 "skills-repo".to_string(),
                 "demo-skill".to_string(),
             )),
+            Query(DownloadParams { version: None }),
         )
         .await;
-        assert_eq!(download.0.code, 200);
+        assert_eq!(download.1 .0.code, 200);
+        assert_eq!(
+            download.0["x-package-sha256"],
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(download.0["x-package-size"], "2048");
+        let download = download.1 .0.data.unwrap();
         assert!(download
-            .0
-            .data
-            .unwrap()
             .download_url
             .contains("skills/demo-skill/1.0.0.zip"));
+        assert_eq!(download.file_size, Some(2048));
+        assert_eq!(
+            download.sha256.as_deref(),
+            Some("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+        );
+
+        let range_download = download_repo_skill(
+            State(state.clone()),
+            Path((
+                "github.com".to_string(),
+                "acme".to_string(),
+                "skills-repo".to_string(),
+                "demo-skill".to_string(),
+            )),
+            Query(DownloadParams {
+                version: Some("^1".to_string()),
+            }),
+        )
+        .await;
+        assert_eq!(range_download.1 .0.code, 200);
+        assert_eq!(range_download.1 .0.data.unwrap().version, "1.0.0");
+
+        let unmatched_range = download_repo_skill(
+            State(state.clone()),
+            Path((
+                "github.com".to_string(),
+                "acme".to_string(),
+                "skills-repo".to_string(),
+                "demo-skill".to_string(),
+            )),
+            Query(DownloadParams {
+                version: Some("^2".to_string()),
+            }),
+        )
+        .await;
+        assert_eq!(unmatched_range.1 .0.code, 404);
+
+        let pinned_download = download_repo_skill_version(
+            State(state.clone()),
+            Path((
+                "github.com".to_string(),
+                "acme".to_string(),
+                "skills-repo".to_string(),
+                "demo-skill".to_string(),
+                "1.0.0".to_string(),
+            )),
+        )
+        .await;
+        assert_eq!(pinned_download.1 .0.code, 200);
+        assert_eq!(pinned_download.1 .0.data.unwrap().version, "1.0.0");
+
+        let missing_pinned = download_repo_skill_version(
+            State(state.clone()),
+            Path((
+                "github.com".to_string(),
+                "acme".to_string(),
+                "skills-repo".to_string(),
+                "demo-skill".to_string(),
+                "9.9.9".to_string(),
+            )),
+        )
+        .await;
+        assert_eq!(missing_pinned.1 .0.code, 404);
+
+        let downloaded = skill_versions::Entity::find()
+            .filter(skill_versions::Column::Version.eq("1.0.0"))
+            .one(state.db.as_ref())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(downloaded.install_count, 3);
 
         let plugin_list = list_repo_plugins(
             State(state.clone()),
@@ -1056,6 +1624,7 @@ mod tests {
         )
         .await;
         assert_eq!(plugin_detail.0.code, 200);
+        assert!(plugin_detail.0.data.unwrap()["source"].is_null());
 
         let missing_plugin = get_repo_plugin(
             State(state.clone()),
@@ -1069,6 +1638,58 @@ mod tests {
         .await;
         assert_eq!(missing_plugin.0.code, 404);
 
+        let plugin_download = download_repo_plugin(
+            State(state.clone()),
+            Path((
+                "github.com".to_string(),
+                "acme".to_string(),
+                "skills-repo".to_string(),
+                "demo-plugin".to_string(),
+            )),
+            Query(DownloadParams { version: None }),
+        )
+        .await;
+        assert_eq!(plugin_download.1 .0.code, 200);
+        let plugin_download = plugin_download.1 .0.data.unwrap();
+        assert_eq!(plugin_download.version, "0.1.0");
+        assert!(plugin_download
+            .download_url
+            .contains("plugins/demo-plugin/0.1.0.zip"));
+
+        let pinned_plugin_download = download_repo_plugin_version(
+            State(state.clone()),
+            Path((
+                "github.com".to_string(),
+                "acme".to_string(),
+                "skills-repo".to_string(),
+                "demo-plugin".to_string(),
+                "0.1.0".to_string(),
+            )),
+        )
+        .await;
+        assert_eq!(pinned_plugin_download.1 .0.code, 200);
+
+        let missing_plugin_version = download_repo_plugin_version(
+            State(state.clone()),
+            Path((
+                "github.com".to_string(),
+                "acme".to_string(),
+                "skills-repo".to_string(),
+                "demo-plugin".to_string(),
+                "^1".to_string(),
+            )),
+        )
+        .await;
+        assert_eq!(missing_plugin_version.1 .0.code, 404);
+
+        let downloaded_plugin = plugins::Entity::find()
+            .filter(plugins::Column::Name.eq("demo-plugin"))
+            .one(state.db.as_ref())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(downloaded_plugin.install_count, 2);
+
         let repo_skills = list_repo_skills(
             State(state.clone()),
             Path((
@@ -1142,6 +1763,165 @@ mod tests {
         assert_eq!(missing_component.0.code, 404);
     }
 
+    #[tokio::test]
+    async fn package_file_handlers_list_manifest_and_refuse_unreadable_files() {
+        let state = setup_state().await;
+        seed_skill_and_plugin_graph(&state).await;
+        let file_path = |version: &str, path: &str| {
+            Path((
+                "github.com".to_string(),
+                "acme".to_string(),
+                "skills-repo".to_string(),
+                "demo-skill".to_string(),
+                version.to_string(),
+                path.to_string(),
+            ))
+        };
+
+        let listed = list_repo_skill_files(
+            State(state.clone()),
+            Path((
+                "github.com".to_string(),
+                "acme".to_string(),
+                "skills-repo".to_string(),
+                "demo-skill".to_string(),
+                "latest".to_string(),
+            )),
+        )
+        .await;
+        assert_eq!(listed.0.code, 200);
+        let files = listed.0.data.unwrap();
+        assert_eq!(files.len(), 3);
+        assert_eq!(files[1].path, "demo-skill/logo.png");
+        assert_eq!(files[1].mime_type, "image/png");
+
+        let binary = get_repo_skill_file(
+            State(state.clone()),
+            file_path("1.0.0", "demo-skill/logo.png"),
+        )
+        .await;
+        assert_eq!(binary.0.code, 415);
+
+        let oversized = get_repo_skill_file(
+            State(state.clone()),
+            file_path("1.0.0", "demo-skill/data.csv"),
+        )
+        .await;
+        assert_eq!(oversized.0.code, 413);
+
+        let missing = get_repo_skill_file(
+            State(state.clone()),
+            file_path("1.0.0", "demo-skill/nope.md"),
+        )
+        .await;
+        assert_eq!(missing.0.code, 404);
+
+        let missing_version = list_repo_plugin_files(
+            State(state.clone()),
+            Path((
+                "github.com".to_string(),
+                "acme".to_string(),
+                "skills-repo".to_string(),
+                "demo-plugin".to_string(),
+                "9.9.9".to_string(),
+            )),
+        )
+        .await;
+        assert_eq!(missing_version.0.code, 404);
+    }
+
+    #[tokio::test]
+    async fn diff_handlers_report_metadata_changes_and_unknown_versions() {
+        let state = setup_state().await;
+        seed_skill_and_plugin_graph(&state).await;
+        let skill = skills::Entity::find()
+            .filter(skills::Column::Name.eq("demo-skill"))
+            .one(state.db.as_ref())
+            .await
+            .unwrap()
+            .unwrap();
+        // Same content hash, so the diff never needs to fetch the packages.
+        skill_versions::ActiveModel {
+            skill_id: Set(skill.id),
+            version: Set("1.1.0".to_string()),
+            description: Set(Some("Demo skill".to_string())),
+            file_hash: Set(Some("abc123".to_string())),
+            metadata: Set(Some(serde_json::json!({
+                "compatibility": ["claude", "codex"],
+                "allowed-tools": ["bash", "rg", "python"],
+                "license": "MIT"
+            }))),
+            created_at: Set(Utc::now().naive_utc()),
+            ..Default::default()
+        }
+        .insert(state.db.as_ref())
+        .await
+        .unwrap();
+        let mut head: skills::ActiveModel = skill.into();
+        head.head_version = Set(Some("1.1.0".to_string()));
+        head.update(state.db.as_ref()).await.unwrap();
+        let path = |name: &str| {
+            Path((
+                "github.com".to_string(),
+                "acme".to_string(),
+                "skills-repo".to_string(),
+                name.to_string(),
+            ))
+        };
+        let params = |from: &str, to: &str| {
+            Query(DiffParams {
+                from: from.to_string(),
+                to: to.to_string(),
+            })
+        };
+
+        let diff = diff_repo_skill(
+            State(state.clone()),
+            path("demo-skill"),
+            params("1.0.0", "^1.1"),
+        )
+        .await;
+        assert_eq!(diff.0.code, 200);
+        let diff = diff.0.data.unwrap();
+        assert_eq!((diff.from.as_str(), diff.to.as_str()), ("1.0.0", "1.1.0"));
+        assert!(diff.files.added.is_empty() && diff.files.modified.is_empty());
+        assert_eq!(diff.metadata.len(), 1);
+        assert_eq!(diff.metadata[0].field, "allowed-tools");
+        assert_eq!(
+            diff.metadata[0].to,
+            Some(serde_json::json!(["bash", "rg", "python"]))
+        );
+
+        let channels = diff_repo_skill(
+            State(state.clone()),
+            path("demo-skill"),
+            params("latest", "main"),
+        )
+        .await;
+        assert_eq!(channels.0.code, 200);
+        let channels = channels.0.data.unwrap();
+        assert_eq!(
+            (channels.from.as_str(), channels.to.as_str()),
+            ("1.0.0", "1.1.0")
+        );
+
+        let missing = diff_repo_skill(
+            State(state.clone()),
+            path("demo-skill"),
+            params("1.0.0", "2.0.0"),
+        )
+        .await;
+        assert_eq!(missing.0.code, 404);
+
+        let missing_plugin = diff_repo_plugin(
+            State(state.clone()),
+            path("missing-plugin"),
+            params("0.1.0", "0.2.0"),
+        )
+        .await;
+        assert_eq!(missing_plugin.0.code, 404);
+    }
+
     #[test]
     fn normalize_api_url_defaults_to_public_github() {
         let normalized = normalize_api_url(None).expect("default URL should be valid");
@@ -1183,6 +1963,23 @@ mod tests {
             map_provider(&discovery_registries::Platform::Github),
             "github"
         );
+        assert_eq!(
+            map_provider(&discovery_registries::Platform::Gitlab),
+            "gitlab"
+        );
+        assert_eq!(
+            parse_provider(" GitLab "),
+            Some(discovery_registries::Platform::Gitlab)
+        );
+        assert_eq!(
+            parse_provider("forgejo"),
+            Some(discovery_registries::Platform::Gitea)
+        );
+        assert_eq!(parse_provider("bitbucket"), None);
+        assert_eq!(
+            default_api_url(&discovery_registries::Platform::Gitlab),
+            "https://gitlab.com/api/v4"
+        );
     }
 
     #[test]
@@ -1291,7 +2088,7 @@ mod tests {
             State(state.clone()),
             admin_user(),
             Json(CreateDiscoveryRegistryRequest {
-                provider: "gitlab".to_string(),
+                provider: "bitbucket".to_string(),
                 token: "token".to_string(),
                 url: None,
                 queries: vec!["q".to_string()],
@@ -1404,4 +2201,260 @@ mod tests {
         let missing = trigger_discovery_registry(State(state), Path(9999), admin_user()).await;
         assert_eq!(missing.0.code, 404);
     }
+
+    #[tokio::test]
+    async fn create_gitea_registry_uses_gitea_provider() {
+        let state = setup_state().await;
+
+        let created = create_discovery_registry(
+            State(state.clone()),
+            admin_user(),
+            Json(CreateDiscoveryRegistryRequest {
+                provider: "forgejo".to_string(),
+                token: "gitea-token".to_string(),
+                url: Some("http://127.0.0.1:1/api/v1".to_string()),
+                queries: vec!["topic:agent-skill".to_string()],
+                schedule_interval_seconds: 60,
+            }),
+        )
+        .await;
+        assert_eq!(created.0.code, 200);
+        let dto = created.0.data.unwrap();
+        assert_eq!(dto.provider, "gitea");
+
+        let health =
+            test_discovery_registry_health(State(state.clone()), Path(dto.id), admin_user()).await;
+        let health_data = health.0.data.unwrap();
+        assert!(!health_data.ok);
+        assert!(health_data.message.starts_with("Gitea API"));
+    }
+
+    #[tokio::test]
+    async fn create_gitlab_registry_defaults_url_and_reports_health() {
+        let state = setup_state().await;
+
+        let created = create_discovery_registry(
+            State(state.clone()),
+            admin_user(),
+            Json(CreateDiscoveryRegistryRequest {
+                provider: "gitlab".to_string(),
+                token: "glpat-token".to_string(),
+                url: None,
+                queries: vec!["topic:agent-skill".to_string()],
+                schedule_interval_seconds: 60,
+            }),
+        )
+        .await;
+        assert_eq!(created.0.code, 200);
+        let dto = created.0.data.unwrap();
+        assert_eq!(dto.provider, "gitlab");
+        assert_eq!(dto.url, "https://gitlab.com/api/v4");
+
+        let updated = update_discovery_registry(
+            State(state.clone()),
+            Path(dto.id),
+            admin_user(),
+            Json(UpdateDiscoveryRegistryRequest {
+                queries: vec!["topic:agent-skill".to_string()],
+                schedule_interval_seconds: 60,
+                url: "http://127.0.0.1:1/api/v4".to_string(),
+            }),
+        )
+        .await;
+        assert_eq!(updated.0.code, 200);
+
+        let health =
+            test_discovery_registry_health(State(state.clone()), Path(dto.id), admin_user()).await;
+        assert_eq!(health.0.code, 200);
+        let health_data = health.0.data.unwrap();
+        assert!(!health_data.ok);
+        assert!(health_data.message.starts_with("GitLab API"));
+    }
+
+    #[tokio::test]
+    async fn registry_dto_exposes_rate_limit_budget() {
+        let state = setup_state().await;
+        let service = &state.services.discovery_registry_service;
+        let config = service
+            .create_github_registry(
+                "ghp_token".to_string(),
+                vec!["topic:agent-skill".to_string()],
+                60,
+                DEFAULT_GITHUB_API_URL.to_string(),
+            )
+            .await
+            .unwrap();
+        let dto = to_registry_dto(&config);
+        assert!(dto.rate_limit_remaining.is_none());
+        assert!(dto.rate_limit_reset_at.is_none());
+        assert!(dto.search_rate_limit_remaining.is_none());
+
+        let reset_at = Utc::now().naive_utc() + chrono::Duration::minutes(15);
+        service
+            .update_rate_limit(config.id, RateLimitResource::Core, Some(0), Some(reset_at))
+            .await
+            .unwrap();
+        service
+            .update_rate_limit(
+                config.id,
+                RateLimitResource::Search,
+                Some(12),
+                Some(reset_at),
+            )
+            .await
+            .unwrap();
+
+        let Json(listed) = list_discovery_registries(State(state.clone()), admin_user()).await;
+        let dto = listed
+            .data
+            .unwrap()
+            .into_iter()
+            .find(|r| r.id == config.id)
+            .unwrap();
+        assert_eq!(dto.rate_limit_remaining, Some(0));
+        assert_eq!(dto.rate_limit_reset_at, Some(reset_at));
+        assert_eq!(dto.search_rate_limit_remaining, Some(12));
+        assert_eq!(dto.search_rate_limit_reset_at, Some(reset_at));
+    }
+
+    #[tokio::test]
+    async fn webhook_secret_is_stored_but_never_returned() {
+        let state = setup_state().await;
+        let config = state
+            .services
+            .discovery_registry_service
+            .create_github_registry(
+                "ghp_token".to_string(),
+                vec!["topic:agent-skill".to_string()],
+                60,
+                DEFAULT_GITHUB_API_URL.to_string(),
+            )
+            .await
+            .unwrap();
+        assert!(!to_registry_dto(&config).webhook_secret_configured);
+
+        let denied = update_discovery_registry_webhook_secret(
+            State(state.clone()),
+            Path(config.id),
+            regular_user(),
+            Json(UpdateWebhookSecretRequest {
+                webhook_secret: Some("whsec".to_string()),
+            }),
+        )
+        .await;
+        assert_eq!(denied.0.code, 403);
+
+        let updated = update_discovery_registry_webhook_secret(
+            State(state.clone()),
+            Path(config.id),
+            admin_user(),
+            Json(UpdateWebhookSecretRequest {
+                webhook_secret: Some("  whsec  ".to_string()),
+            }),
+        )
+        .await;
+        assert!(updated.0.data.unwrap().webhook_secret_configured);
+        let stored = state
+            .services
+            .discovery_registry_service
+            .find_by_id(config.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.webhook_secret.as_deref(), Some("whsec"));
+
+        let cleared = update_discovery_registry_webhook_secret(
+            State(state.clone()),
+            Path(config.id),
+            admin_user(),
+            Json(UpdateWebhookSecretRequest {
+                webhook_secret: None,
+            }),
+        )
+        .await;
+        assert!(!cleared.0.data.unwrap().webhook_secret_configured);
+
+        let missing = update_discovery_registry_webhook_secret(
+            State(state),
+            Path(9999),
+            admin_user(),
+            Json(UpdateWebhookSecretRequest {
+                webhook_secret: None,
+            }),
+        )
+        .await;
+        assert_eq!(missing.0.code, 404);
+    }
+
+    #[tokio::test]
+    async fn submit_repository_registers_row_before_starting_sync() {
+        let state = setup_state().await;
+        let mut settings = state.settings.clone();
+        settings.temporal.server_url = "not-a-url".to_string();
+        let state = Arc::new(AppState {
+            db: state.db.clone(),
+            settings,
+            services: state.services.clone(),
+            repos: state.repos.clone(),
+            allowed_frontend_origins: state.allowed_frontend_origins.clone(),
+        });
+
+        let submit = |url: &str, provider: Option<&str>| {
+            submit_repository(
+                State(state.clone()),
+                regular_user(),
+                Json(SubmitRepositoryRequest {
+                    url: url.to_string(),
+                    provider: provider.map(str::to_string),
+                }),
+            )
+        };
+
+        let invalid = submit("acme/skills", None).await;
+        assert_eq!(invalid.0.code, 400);
+
+        let bad_provider = submit("https://github.com/acme/skills", Some("svn")).await;
+        assert_eq!(bad_provider.0.code, 400);
+
+        state
+            .services
+            .registry_service
+            .upsert_blacklist("https://github.com/acme/blocked", "spam")
+            .await
+            .unwrap();
+        let blocked = submit("https://github.com/acme/blocked.git", None).await;
+        assert_eq!(blocked.0.code, 403);
+
+        let submitted = submit("https://GitHub.com/acme/skills/tree/main", None).await;
+        assert_eq!(submitted.0.code, 500);
+        assert!(submitted.0.message.contains("invalid temporal server url"));
+
+        let repo = state
+            .services
+            .registry_service
+            .find_by_host("github.com", "acme", "skills")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(repo.url, "https://github.com/acme/skills");
+        assert_eq!(repo.platform, skill_registry::Platform::Github);
+
+        submit("git@github.com:acme/skills.git", None).await;
+        let rows = skill_registry::Entity::find()
+            .all(state.db.as_ref())
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+
+        let self_hosted = submit("https://git.example.com/team/skills", Some("forgejo")).await;
+        assert_eq!(self_hosted.0.code, 500);
+        let repo = state
+            .services
+            .registry_service
+            .find_by_host("git.example.com", "team", "skills")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(repo.platform, skill_registry::Platform::Gitea);
+    }
 }
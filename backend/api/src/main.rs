@@ -1,15 +1,18 @@
 mod auth;
+mod downloads;
 mod handlers;
 mod models;
 mod origin;
+mod webhooks;
 
 use axum::{
     http::{request::Parts, HeaderValue},
-    routing::{get, patch, post},
+    routing::{get, patch, post, put},
     Router,
 };
 use common::build_all;
-use common::settings::Settings;
+use common::infra::local_storage::LOCAL_OBJECT_ROUTE;
+use common::settings::{DownloadMode, Settings};
 use origin::{is_origin_allowed, parse_frontend_origins};
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -30,6 +33,7 @@ pub struct AppState {
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let settings = Settings::new()?;
+    common::domain::signing::verification_keys(&settings.signing)?;
 
     tracing_subscriber::registry()
         .with(tracing_subscriber::EnvFilter::new(
@@ -44,6 +48,13 @@ async fn main() -> anyhow::Result<()> {
     let db = Arc::new(db);
 
     let (repos, services) = build_all(db.clone(), &settings).await?;
+    if services
+        .s3
+        .as_local()
+        .is_some_and(|local| !local.can_sign_urls())
+    {
+        anyhow::bail!("storage.url_signing_key is required when storage.backend is local");
+    }
 
     let allowed_frontend_origins = Arc::new(parse_frontend_origins(
         settings.auth.frontend_origin.as_deref(),
@@ -72,10 +83,18 @@ fn build_app(state: Arc<AppState>) -> Router {
         state.settings.debug,
         Arc::clone(&state.allowed_frontend_origins),
     );
+    let proxy_downloads = state.settings.s3.download_mode == DownloadMode::Proxy;
 
     Router::new()
         .route("/", get(|| async { "Skill Registry API" }))
+        .route(
+            "/.well-known/skillregistry-keys.json",
+            get(handlers::signing_keys),
+        )
         .route("/api/skills", get(handlers::list_skills))
+        // `:org` is a single segment, so GitLab subgroup owners such as
+        // `platform/ai` are sent percent-encoded (`platform%2Fai`); `Path`
+        // decodes them.
         .route(
             "/api/:host/:org/:repo/skill/:name",
             get(handlers::get_repo_skill_detail),
@@ -86,7 +105,31 @@ fn build_app(state: Arc<AppState>) -> Router {
         )
         .route(
             "/api/:host/:org/:repo/skill/:name/download",
-            get(handlers::download_repo_skill),
+            if proxy_downloads {
+                get(downloads::stream_repo_skill)
+            } else {
+                get(handlers::download_repo_skill)
+            },
+        )
+        .route(
+            "/api/:host/:org/:repo/skill/:name/versions/:version/download",
+            if proxy_downloads {
+                get(downloads::stream_repo_skill_version)
+            } else {
+                get(handlers::download_repo_skill_version)
+            },
+        )
+        .route(
+            "/api/:host/:org/:repo/skill/:name/diff",
+            get(handlers::diff_repo_skill),
+        )
+        .route(
+            "/api/:host/:org/:repo/skill/:name/versions/:version/files",
+            get(handlers::list_repo_skill_files),
+        )
+        .route(
+            "/api/:host/:org/:repo/skill/:name/versions/:version/files/*path",
+            get(handlers::get_repo_skill_file),
         )
         .route(
             "/api/:host/:org/:repo/plugin",
@@ -96,6 +139,34 @@ fn build_app(state: Arc<AppState>) -> Router {
             "/api/:host/:org/:repo/plugin/:plugin_name",
             get(handlers::get_repo_plugin),
         )
+        .route(
+            "/api/:host/:org/:repo/plugin/:plugin_name/download",
+            if proxy_downloads {
+                get(downloads::stream_repo_plugin)
+            } else {
+                get(handlers::download_repo_plugin)
+            },
+        )
+        .route(
+            "/api/:host/:org/:repo/plugin/:plugin_name/versions/:version/download",
+            if proxy_downloads {
+                get(downloads::stream_repo_plugin_version)
+            } else {
+                get(handlers::download_repo_plugin_version)
+            },
+        )
+        .route(
+            "/api/:host/:org/:repo/plugin/:plugin_name/diff",
+            get(handlers::diff_repo_plugin),
+        )
+        .route(
+            "/api/:host/:org/:repo/plugin/:plugin_name/versions/:version/files",
+            get(handlers::list_repo_plugin_files),
+        )
+        .route(
+            "/api/:host/:org/:repo/plugin/:plugin_name/versions/:version/files/*path",
+            get(handlers::get_repo_plugin_file),
+        )
         .route(
             "/api/:host/:org/:repo/plugin/:plugin_name/agent/:agent_name",
             get(handlers::get_repo_plugin_agent),
@@ -112,6 +183,7 @@ fn build_app(state: Arc<AppState>) -> Router {
             "/api/:host/:org/:repo/skill",
             get(handlers::list_repo_skills),
         )
+        .route("/api/repos", post(handlers::submit_repository))
         .route("/api/me", get(auth::me))
         .route(
             "/api/admin/discovery-registries",
@@ -133,6 +205,15 @@ fn build_app(state: Arc<AppState>) -> Router {
             "/api/admin/discovery-registries/:id/trigger",
             post(handlers::trigger_discovery_registry),
         )
+        .route(
+            "/api/admin/discovery-registries/:id/webhook-secret",
+            put(handlers::update_discovery_registry_webhook_secret),
+        )
+        .route(
+            &format!("{}/*key", LOCAL_OBJECT_ROUTE),
+            get(downloads::serve_local_object),
+        )
+        .route("/api/webhooks/github", post(webhooks::github_webhook))
         .nest("/api/auth", auth::router())
         .layer(cors)
         .with_state(state)
@@ -171,14 +252,21 @@ mod tests {
     use migration::MigratorTrait;
     use tower::ServiceExt;
 
-    async fn setup_state() -> Arc<AppState> {
-        let db = sea_orm::Database::connect("sqlite::memory:").await.unwrap();
-        migration::Migrator::up(&db, None).await.unwrap();
-
+    fn test_settings() -> Settings {
         let mut settings = Settings::default();
         settings.database.url = "sqlite::memory:".to_string();
         settings.auth.jwt.signing_key = Some("test-signing-key".to_string());
         settings.debug = true;
+        settings
+    }
+
+    async fn setup_state() -> Arc<AppState> {
+        setup_state_with(test_settings()).await
+    }
+
+    async fn setup_state_with(settings: Settings) -> Arc<AppState> {
+        let db = sea_orm::Database::connect("sqlite::memory:").await.unwrap();
+        migration::Migrator::up(&db, None).await.unwrap();
 
         let db_arc = Arc::new(db);
         let (repos, services) = common::build_all(db_arc.clone(), &settings).await.unwrap();
@@ -205,6 +293,161 @@ mod tests {
         assert_eq!(body, "Skill Registry API");
     }
 
+    #[tokio::test]
+    async fn proxy_download_mode_reports_http_status() {
+        let mut state = (*setup_state().await).clone();
+        state.settings.s3.download_mode = DownloadMode::Proxy;
+        let app = build_app(Arc::new(state));
+
+        let req = Request::builder()
+            .uri("/api/github.com/acme/missing/skill/demo/download?version=%5E1")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), axum::http::StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn local_storage_serves_objects_through_signed_urls() {
+        let root = std::env::temp_dir().join(format!("skillregistry-api-{}", uuid::Uuid::new_v4()));
+        let mut settings = test_settings();
+        settings.storage.backend = common::settings::StorageBackend::Local;
+        settings.storage.local_path = root.display().to_string();
+        settings.storage.url_signing_key = Some("test-url-key".to_string());
+        let state = setup_state_with(settings).await;
+
+        let s3 = &state.services.s3;
+        s3.upload_file("skills/demo/a.zip", b"package".to_vec())
+            .await
+            .unwrap();
+        let url = s3
+            .get_presigned_url("skills/demo/a.zip", std::time::Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert!(url.starts_with("/api/storage/skills/demo/a.zip?expires="));
+        let app = build_app(Arc::clone(&state));
+
+        let req = Request::builder().uri(&url).body(Body::empty()).unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), axum::http::StatusCode::OK);
+        assert_eq!(resp.headers()["content-type"], "application/zip");
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, "package");
+
+        let req = Request::builder()
+            .uri(&url)
+            .header("range", "bytes=1-3")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), axum::http::StatusCode::PARTIAL_CONTENT);
+        assert_eq!(resp.headers()["content-range"], "bytes 1-3/7");
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, "ack");
+
+        let tampered = url.replace("expires=", "expires=1");
+        let req = Request::builder()
+            .uri(&tampered)
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), axum::http::StatusCode::FORBIDDEN);
+
+        let req = Request::builder()
+            .uri("/api/storage/skills/demo/a.zip")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), axum::http::StatusCode::BAD_REQUEST);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn well_known_keys_list_active_and_rotated_keys() {
+        let mut state = (*setup_state().await).clone();
+        state.settings.signing.private_key =
+            Some("AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=".to_string());
+        state.settings.signing.key_id = Some("current".to_string());
+        state
+            .settings
+            .signing
+            .verification_keys
+            .push(common::settings::VerificationKeySettings {
+                key_id: "previous".to_string(),
+                public_key: "gTl3Dqh9F19Wo1Rmw0x+zMuNipG07jeiXfYPW4/Js5Q=".to_string(),
+            });
+        let app = build_app(Arc::new(state));
+
+        let req = Request::builder()
+            .uri("/.well-known/skillregistry-keys.json")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let ids: Vec<&str> = json["keys"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|k| k["key_id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["current", "previous"]);
+        assert_eq!(json["keys"][0]["algorithm"], "ed25519");
+    }
+
+    #[tokio::test]
+    async fn repo_routes_accept_percent_encoded_nested_group_owners() {
+        use common::entities::{skill_registry, skills};
+        use sea_orm::{ActiveModelTrait, Set};
+
+        let state = setup_state().await;
+        let now = chrono::Utc::now().naive_utc();
+        let registry = skill_registry::ActiveModel {
+            platform: Set(skill_registry::Platform::Gitlab),
+            owner: Set("platform/ai".to_string()),
+            name: Set("skills".to_string()),
+            url: Set("https://gitlab.example.com/platform/ai/skills".to_string()),
+            host: Set(Some("gitlab.example.com".to_string())),
+            status: Set("active".to_string()),
+            stars: Set(0),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        }
+        .insert(state.db.as_ref())
+        .await
+        .unwrap();
+        skills::ActiveModel {
+            skill_registry_id: Set(registry.id),
+            name: Set("demo".to_string()),
+            install_count: Set(0),
+            is_active: Set(1),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        }
+        .insert(state.db.as_ref())
+        .await
+        .unwrap();
+        let app = build_app(state);
+
+        let req = Request::builder()
+            .uri("/api/gitlab.example.com/platform%2Fai/skills/skill/demo")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), axum::http::StatusCode::OK);
+
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], 200);
+        assert_eq!(json["data"]["skill"]["name"], "demo");
+        assert_eq!(json["data"]["registry"]["owner"], "platform/ai");
+    }
+
     #[test]
     fn build_cors_is_permissive_for_debug_or_empty_allowlist() {
         let _ = build_cors(true, Arc::new(vec!["https://app.example.com".to_string()]));
@@ -0,0 +1,431 @@
+use crate::handlers::{start_workflow, SYNC_REPO_WORKFLOW};
+use crate::models::ApiResponse;
+use crate::AppState;
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::future::Future;
+use std::sync::Arc;
+use url::Url;
+
+type WebhookResponse = (StatusCode, Json<ApiResponse<WebhookAckDto>>);
+
+#[derive(Serialize)]
+pub struct WebhookAckDto {
+    pub event: String,
+    pub triggered: bool,
+    pub message: String,
+    pub workflow_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PushEvent {
+    #[serde(rename = "ref")]
+    git_ref: String,
+    repository: PushRepository,
+}
+
+#[derive(Deserialize)]
+struct PushRepository {
+    name: String,
+    html_url: String,
+    default_branch: String,
+    owner: PushOwner,
+}
+
+#[derive(Deserialize)]
+struct PushOwner {
+    #[serde(alias = "name")]
+    login: String,
+}
+
+fn ack(event: &str, message: &str) -> WebhookResponse {
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(WebhookAckDto {
+            event: event.to_string(),
+            triggered: false,
+            message: message.to_string(),
+            workflow_id: None,
+        })),
+    )
+}
+
+fn reject(status: StatusCode, message: &str) -> WebhookResponse {
+    (
+        status,
+        Json(ApiResponse::error(
+            status.as_u16() as i32,
+            message.to_string(),
+        )),
+    )
+}
+
+/// Unknown repositories, repositories without a secret and bad signatures
+/// get the same answer, so the endpoint does not reveal what is registered.
+fn unverified() -> WebhookResponse {
+    reject(StatusCode::UNAUTHORIZED, "invalid webhook signature")
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    let Some(hex_sig) = signature.trim().strip_prefix("sha256=") else {
+        return false;
+    };
+    let Ok(expected) = hex::decode(hex_sig) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+pub async fn github_webhook(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> WebhookResponse {
+    let temporal = &state.settings.temporal;
+    handle_github_webhook(
+        &state,
+        &headers,
+        &body,
+        |workflow_type, workflow_id, registry_id| async move {
+            start_workflow(temporal, workflow_type, workflow_id, &registry_id).await
+        },
+    )
+    .await
+}
+
+async fn handle_github_webhook<F, Fut>(
+    state: &AppState,
+    headers: &HeaderMap,
+    body: &[u8],
+    start: F,
+) -> WebhookResponse
+where
+    F: FnOnce(&'static str, String, i32) -> Fut,
+    Fut: Future<Output = Result<(), String>>,
+{
+    let Some(event) = header_str(headers, "x-github-event") else {
+        return reject(StatusCode::BAD_REQUEST, "missing X-GitHub-Event header");
+    };
+
+    match event {
+        "ping" => return ack(event, "pong"),
+        "push" => {}
+        _ => return ack(event, "event ignored"),
+    }
+
+    let push: PushEvent = match serde_json::from_slice(body) {
+        Ok(push) => push,
+        Err(e) => {
+            return reject(
+                StatusCode::BAD_REQUEST,
+                &format!("invalid push payload: {}", e),
+            )
+        }
+    };
+
+    let Some(host) = Url::parse(&push.repository.html_url)
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_string))
+    else {
+        return reject(StatusCode::BAD_REQUEST, "repository html_url has no host");
+    };
+
+    let repo = match state
+        .services
+        .registry_service
+        .find_by_host(&host, &push.repository.owner.login, &push.repository.name)
+        .await
+    {
+        Ok(Some(repo)) => repo,
+        Ok(None) => {
+            tracing::debug!(%host, "Push webhook for unknown repository");
+            return unverified();
+        }
+        Err(e) => return reject(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+    };
+
+    let secret = match repo.discovery_registry_id {
+        Some(registry_id) => match state
+            .services
+            .discovery_registry_service
+            .find_by_id(registry_id)
+            .await
+        {
+            Ok(config) => config.and_then(|c| c.webhook_secret),
+            Err(e) => return reject(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+        },
+        None => None,
+    };
+
+    let Some(secret) = secret.filter(|s| !s.is_empty()) else {
+        tracing::debug!(
+            registry_id = repo.id,
+            "Push webhook without a configured secret"
+        );
+        return unverified();
+    };
+
+    let signature = header_str(headers, "x-hub-signature-256").unwrap_or_default();
+    if !verify_signature(&secret, body, signature) {
+        return unverified();
+    }
+
+    if push.git_ref != format!("refs/heads/{}", push.repository.default_branch) {
+        return ack(event, "push is not to the default branch");
+    }
+
+    let workflow_id = format!("webhook-sync-{}-{}", repo.id, uuid::Uuid::new_v4());
+    if let Err(e) = start(SYNC_REPO_WORKFLOW, workflow_id.clone(), repo.id).await {
+        return reject(StatusCode::INTERNAL_SERVER_ERROR, &e);
+    }
+
+    tracing::info!(
+        registry_id = repo.id,
+        workflow_id,
+        "Started sync workflow from push webhook"
+    );
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(WebhookAckDto {
+            event: event.to_string(),
+            triggered: true,
+            message: "Sync workflow triggered".to_string(),
+            workflow_id: Some(workflow_id),
+        })),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use common::entities::{discovery_registries, skill_registry};
+    use common::settings::Settings;
+    use migration::MigratorTrait;
+    use sea_orm::{ActiveModelTrait, Set};
+
+    const SECRET: &str = "whsec_test";
+
+    async fn setup_state() -> Arc<AppState> {
+        let db = sea_orm::Database::connect("sqlite::memory:").await.unwrap();
+        migration::Migrator::up(&db, None).await.unwrap();
+
+        let mut settings = Settings::default();
+        settings.database.url = "sqlite::memory:".to_string();
+
+        let db_arc = Arc::new(db);
+        let (repos, services) = common::build_all(db_arc.clone(), &settings).await.unwrap();
+        Arc::new(AppState {
+            db: db_arc,
+            settings,
+            services,
+            repos,
+            allowed_frontend_origins: Arc::new(vec![]),
+        })
+    }
+
+    async fn seed_repo(state: &Arc<AppState>, secret: Option<&str>) -> i32 {
+        let service = &state.services.discovery_registry_service;
+        let config = service
+            .create_registry(
+                discovery_registries::Platform::Github,
+                "token".to_string(),
+                vec!["topic:agent-skill".to_string()],
+                3600,
+                "https://api.github.com".to_string(),
+            )
+            .await
+            .unwrap();
+        service
+            .update_webhook_secret(config.id, secret.map(str::to_string))
+            .await
+            .unwrap();
+
+        let now = Utc::now().naive_utc();
+        skill_registry::ActiveModel {
+            discovery_registry_id: Set(Some(config.id)),
+            platform: Set(skill_registry::Platform::Github),
+            owner: Set("acme".to_string()),
+            name: Set("skills".to_string()),
+            url: Set("https://github.com/acme/skills".to_string()),
+            host: Set(Some("github.com".to_string())),
+            status: Set("active".to_string()),
+            stars: Set(0),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        }
+        .insert(state.db.as_ref())
+        .await
+        .unwrap()
+        .id
+    }
+
+    fn push_body(owner: &str, git_ref: &str) -> Bytes {
+        Bytes::from(
+            serde_json::to_vec(&serde_json::json!({
+                "ref": git_ref,
+                "repository": {
+                    "name": "skills",
+                    "html_url": format!("https://github.com/{}/skills", owner),
+                    "default_branch": "main",
+                    "owner": { "login": owner }
+                }
+            }))
+            .unwrap(),
+        )
+    }
+
+    fn sign(secret: &str, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    fn headers(event: &str, signature: Option<&str>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-github-event", event.parse().unwrap());
+        if let Some(sig) = signature {
+            headers.insert("x-hub-signature-256", sig.parse().unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn verify_signature_accepts_only_matching_digest() {
+        let body = b"{\"zen\":\"hi\"}";
+        let sig = sign(SECRET, body);
+
+        assert!(verify_signature(SECRET, body, &sig));
+        assert!(!verify_signature("other", body, &sig));
+        assert!(!verify_signature(SECRET, b"tampered", &sig));
+        assert!(!verify_signature(
+            SECRET,
+            body,
+            sig.trim_start_matches("sha256=")
+        ));
+        assert!(!verify_signature(SECRET, body, "sha256=not-hex"));
+    }
+
+    #[tokio::test]
+    async fn ping_is_acknowledged_and_unknown_repos_look_unverified() {
+        let state = setup_state().await;
+        seed_repo(&state, Some(SECRET)).await;
+
+        let (status, Json(resp)) = github_webhook(
+            State(state.clone()),
+            headers("ping", None),
+            Bytes::from_static(b"{\"zen\":\"hi\"}"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(!resp.data.unwrap().triggered);
+
+        let body = push_body("someone-else", "refs/heads/main");
+        let sig = sign(SECRET, &body);
+        let (status, Json(unknown)) =
+            github_webhook(State(state.clone()), headers("push", Some(&sig)), body).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let body = push_body("acme", "refs/heads/main");
+        let bad = sign("wrong-secret", &body);
+        let (_, Json(unverified)) =
+            github_webhook(State(state), headers("push", Some(&bad)), body).await;
+        assert_eq!(unknown.code, unverified.code);
+        assert_eq!(unknown.message, unverified.message);
+    }
+
+    #[tokio::test]
+    async fn push_with_bad_or_missing_signature_is_rejected() {
+        let state = setup_state().await;
+        seed_repo(&state, Some(SECRET)).await;
+        let body = push_body("acme", "refs/heads/main");
+
+        let (status, _) =
+            github_webhook(State(state.clone()), headers("push", None), body.clone()).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let bad = sign("wrong-secret", &body);
+        let (status, Json(resp)) =
+            github_webhook(State(state), headers("push", Some(&bad)), body).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(resp.code, 401);
+    }
+
+    #[tokio::test]
+    async fn push_without_configured_secret_is_rejected() {
+        let state = setup_state().await;
+        seed_repo(&state, None).await;
+        let body = push_body("acme", "refs/heads/main");
+        let sig = sign(SECRET, &body);
+
+        let (status, Json(resp)) =
+            github_webhook(State(state), headers("push", Some(&sig)), body).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(resp.message, "invalid webhook signature");
+    }
+
+    #[tokio::test]
+    async fn signed_push_to_other_branch_is_ignored() {
+        let state = setup_state().await;
+        seed_repo(&state, Some(SECRET)).await;
+        let body = push_body("acme", "refs/heads/feature");
+        let sig = sign(SECRET, &body);
+
+        let (status, Json(resp)) =
+            github_webhook(State(state), headers("push", Some(&sig)), body).await;
+        assert_eq!(status, StatusCode::OK);
+        let data = resp.data.unwrap();
+        assert!(!data.triggered);
+        assert!(data.workflow_id.is_none());
+    }
+
+    #[tokio::test]
+    async fn signed_push_to_default_branch_starts_sync_workflow() {
+        let state = setup_state().await;
+        let repo_id = seed_repo(&state, Some(SECRET)).await;
+        let body = push_body("acme", "refs/heads/main");
+        let sig = sign(SECRET, &body);
+
+        let started = std::sync::Mutex::new(Vec::new());
+        let (status, Json(resp)) = handle_github_webhook(
+            &state,
+            &headers("push", Some(&sig)),
+            &body,
+            |workflow_type, workflow_id, registry_id| {
+                started
+                    .lock()
+                    .unwrap()
+                    .push((workflow_type, workflow_id, registry_id));
+                async { Ok(()) }
+            },
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        let data = resp.data.unwrap();
+        assert!(data.triggered);
+        let started = started.into_inner().unwrap();
+        assert_eq!(started.len(), 1);
+        let (workflow_type, workflow_id, registry_id) = &started[0];
+        assert_eq!(*workflow_type, SYNC_REPO_WORKFLOW);
+        assert_eq!(*registry_id, repo_id);
+        assert_eq!(data.workflow_id.as_ref(), Some(workflow_id));
+    }
+}
@@ -1,14 +1,15 @@
 use crate::github::GithubClient;
+use crate::infra::github_http::HttpCache;
 use crate::repositories::{
-    discovery_registries::DiscoveryRegistryRepositoryImpl, plugins::PluginRepositoryImpl,
-    registry::RegistryRepositoryImpl, skills::SkillRepositoryImpl,
+    discovery_registries::DiscoveryRegistryRepositoryImpl, http_cache::HttpCacheRepositoryImpl,
+    plugins::PluginRepositoryImpl, registry::RegistryRepositoryImpl, skills::SkillRepositoryImpl,
 };
-use crate::s3::S3Service;
+use crate::s3::{LocalStorage, ObjectStore, S3Service};
 use crate::services::{
     discovery_registries::DiscoveryRegistryServiceImpl, github::GithubService,
     plugins::PluginServiceImpl, registry::RegistryServiceImpl, skills::SkillServiceImpl,
 };
-use crate::settings::{S3Settings, Settings};
+use crate::settings::{S3Settings, Settings, StorageBackend};
 use anyhow::Result;
 use sea_orm::DatabaseConnection;
 use std::sync::Arc;
@@ -20,6 +21,7 @@ pub struct Repositories {
     pub registry_repo: Arc<dyn crate::repositories::registry::RegistryRepository>,
     pub discovery_registry_repo:
         Arc<dyn crate::repositories::discovery_registries::DiscoveryRegistryRepository>,
+    pub http_cache: Arc<dyn HttpCache>,
 }
 
 #[derive(Clone)]
@@ -30,7 +32,7 @@ pub struct Services {
     pub discovery_registry_service:
         Arc<dyn crate::services::discovery_registries::DiscoveryRegistryService>,
     pub github_service: Arc<dyn GithubService>,
-    pub s3: Arc<S3Service>,
+    pub s3: Arc<ObjectStore>,
 }
 
 pub fn build_repositories(db: Arc<DatabaseConnection>) -> Repositories {
@@ -39,18 +41,22 @@ pub fn build_repositories(db: Arc<DatabaseConnection>) -> Repositories {
         plugin_repo: Arc::new(PluginRepositoryImpl::new(db.clone())),
         registry_repo: Arc::new(RegistryRepositoryImpl::new(db.clone())),
         discovery_registry_repo: Arc::new(DiscoveryRegistryRepositoryImpl::new(db.clone())),
+        http_cache: Arc::new(HttpCacheRepositoryImpl::new(db.clone())),
     }
 }
 
 pub fn build_github_service(
     token: Option<String>,
     api_url: String,
+    cache: Arc<dyn HttpCache>,
 ) -> Result<Arc<dyn GithubService>> {
-    Ok(Arc::new(GithubClient::new(token, api_url)?))
+    Ok(Arc::new(
+        GithubClient::new(token, api_url)?.with_cache(Some(cache)),
+    ))
 }
 
 pub async fn build_services(repos: &Repositories, settings: &Settings) -> Result<Services> {
-    let s3 = build_s3_service(&settings.s3).await;
+    let s3 = build_object_store(settings).await;
 
     let skill_service = Arc::new(SkillServiceImpl::new(
         repos.skill_repo.clone(),
@@ -62,6 +68,7 @@ pub async fn build_services(repos: &Repositories, settings: &Settings) -> Result
         repos.plugin_repo.clone(),
         repos.registry_repo.clone(),
         repos.skill_repo.clone(),
+        s3.clone(),
     ));
 
     let registry_service = Arc::new(RegistryServiceImpl::new(repos.registry_repo.clone()));
@@ -73,6 +80,7 @@ pub async fn build_services(repos: &Repositories, settings: &Settings) -> Result
     let github_service = build_github_service(
         settings.github.token.clone(),
         settings.github.api_url.clone(),
+        repos.http_cache.clone(),
     )?;
 
     Ok(Services {
@@ -86,17 +94,30 @@ pub async fn build_services(repos: &Repositories, settings: &Settings) -> Result
 }
 
 pub async fn build_s3_service(s3_settings: &S3Settings) -> Arc<S3Service> {
-    Arc::new(
-        S3Service::new(
-            s3_settings.bucket.clone(),
-            s3_settings.region.clone(),
-            s3_settings.endpoint.clone(),
-            s3_settings.access_key_id.clone(),
-            s3_settings.secret_access_key.clone(),
-            s3_settings.force_path_style,
-        )
-        .await,
+    Arc::new(new_s3_service(s3_settings).await)
+}
+
+async fn new_s3_service(s3_settings: &S3Settings) -> S3Service {
+    S3Service::new(
+        s3_settings.bucket.clone(),
+        s3_settings.region.clone(),
+        s3_settings.endpoint.clone(),
+        s3_settings.access_key_id.clone(),
+        s3_settings.secret_access_key.clone(),
+        s3_settings.force_path_style,
     )
+    .await
+}
+
+pub async fn build_object_store(settings: &Settings) -> Arc<ObjectStore> {
+    match settings.storage.backend {
+        StorageBackend::S3 => Arc::new(ObjectStore::S3(new_s3_service(&settings.s3).await)),
+        StorageBackend::Local => Arc::new(ObjectStore::Local(LocalStorage::new(
+            &settings.storage.local_path,
+            settings.storage.public_url.clone(),
+            settings.storage.url_signing_key.clone(),
+        ))),
+    }
 }
 
 pub async fn build_all(
@@ -67,12 +67,16 @@ pub struct Settings {
     pub port: u16,
     pub database: DatabaseSettings,
     pub s3: S3Settings,
+    #[serde(default)]
+    pub storage: StorageSettings,
     pub github: GithubSettings,
     pub worker: WorkerSettings,
     pub temporal: TemporalSettings,
     #[serde(default)]
     pub auth: AuthSettings,
     #[serde(default)]
+    pub signing: SigningSettings,
+    #[serde(default)]
     pub debug: bool,
 }
 
@@ -90,11 +94,71 @@ pub struct S3Settings {
     pub secret_access_key: Option<String>,
     #[serde(default)]
     pub force_path_style: bool,
+    #[serde(default)]
+    pub download_mode: DownloadMode,
+}
+
+/// How package downloads are served to clients.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadMode {
+    /// Return a short-lived presigned bucket URL.
+    #[default]
+    Presigned,
+    /// Stream the object through the API, for clients that can only reach
+    /// the registry host.
+    Proxy,
+}
+
+/// Where packages and repo snapshots are kept.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageBackend {
+    /// The bucket configured under `s3`.
+    #[default]
+    S3,
+    /// A directory on the local filesystem, for installs without an
+    /// S3-compatible service.
+    Local,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct StorageSettings {
+    #[serde(default)]
+    pub backend: StorageBackend,
+    /// Root directory of the `local` backend.
+    #[serde(default = "default_local_storage_path")]
+    pub local_path: String,
+    /// Secret the API signs local download URLs with. Required by the API
+    /// when the backend is `local`.
+    pub url_signing_key: Option<String>,
+    /// Public base URL of the API, prefixed to local download URLs. They are
+    /// returned relative to the API when unset.
+    pub public_url: Option<String>,
+}
+
+pub fn default_local_storage_path() -> String {
+    "./data/storage".to_string()
+}
+
+impl Default for StorageSettings {
+    fn default() -> Self {
+        Self {
+            backend: StorageBackend::default(),
+            local_path: default_local_storage_path(),
+            url_signing_key: None,
+            public_url: None,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct GithubSettings {
     pub search_keywords: String,
+    /// Remaining rate-limit budgets for this token are tracked in memory by
+    /// each worker process. Discovery registries persist theirs, but a
+    /// restarted or additional worker only learns that repo syncs are over
+    /// budget from its own first rejected request.
     pub token: Option<String>,
     pub api_url: String,
 }
@@ -102,6 +166,56 @@ pub struct GithubSettings {
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct WorkerSettings {
     pub scan_interval_seconds: u64,
+    /// Git tag patterns indexed as skill and plugin versions. `{version}` is
+    /// required; `{name}` restricts the tag to the skill or plugin it names.
+    #[serde(default = "default_tag_patterns")]
+    pub tag_patterns: Vec<String>,
+    /// Unreferenced bucket objects younger than this are never collected, so
+    /// uploads from in-flight syncs survive until their rows are written.
+    #[serde(default = "default_gc_grace_period_hours")]
+    pub gc_grace_period_hours: u64,
+    #[serde(default)]
+    pub ingest_limits: IngestLimits,
+}
+
+/// Bounds on what one repository checkout or snapshot may load into worker
+/// memory. Repositories over a limit are blacklisted.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct IngestLimits {
+    pub max_repo_bytes: u64,
+    pub max_file_bytes: u64,
+    pub max_files: usize,
+    /// Snapshot zips inflating to more than this multiple of their own size
+    /// are rejected as zip bombs.
+    pub max_compression_ratio: u64,
+}
+
+impl Default for IngestLimits {
+    fn default() -> Self {
+        Self {
+            max_repo_bytes: 256 * 1024 * 1024,
+            max_file_bytes: 32 * 1024 * 1024,
+            max_files: 20_000,
+            max_compression_ratio: 100,
+        }
+    }
+}
+
+pub fn default_tag_patterns() -> Vec<String> {
+    [
+        "v{version}",
+        "{version}",
+        "{name}@{version}",
+        "{name}-v{version}",
+    ]
+    .into_iter()
+    .map(ToString::to_string)
+    .collect()
+}
+
+pub fn default_gc_grace_period_hours() -> u64 {
+    72
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -110,6 +224,26 @@ pub struct TemporalSettings {
     pub task_queue: String,
 }
 
+/// Ed25519 keys used to sign uploaded packages.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct SigningSettings {
+    /// Base64 32-byte seed of the active key. Packages are not signed when unset.
+    pub private_key: Option<String>,
+    /// Published with every signature; derived from the public key when unset.
+    pub key_id: Option<String>,
+    /// Public keys that remain valid besides the active one, e.g. the previous
+    /// key while packages signed with it are still served.
+    #[serde(default)]
+    pub verification_keys: Vec<VerificationKeySettings>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct VerificationKeySettings {
+    pub key_id: String,
+    /// Base64 32-byte Ed25519 public key.
+    pub public_key: String,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct AuthSettings {
     pub frontend_origin: Option<String>,
@@ -243,7 +377,9 @@ impl Default for Settings {
                 access_key_id: None,
                 secret_access_key: None,
                 force_path_style: false,
+                download_mode: DownloadMode::Presigned,
             },
+            storage: StorageSettings::default(),
             github: GithubSettings {
                 search_keywords: "topic:agent-skill".to_string(),
                 token: None,
@@ -251,12 +387,16 @@ impl Default for Settings {
             },
             worker: WorkerSettings {
                 scan_interval_seconds: 3600,
+                tag_patterns: default_tag_patterns(),
+                gc_grace_period_hours: default_gc_grace_period_hours(),
+                ingest_limits: IngestLimits::default(),
             },
             temporal: TemporalSettings {
                 server_url: "http://localhost:7233".to_string(),
                 task_queue: "skill-registry-queue".to_string(),
             },
             auth: AuthSettings::default(),
+            signing: SigningSettings::default(),
         }
     }
 }
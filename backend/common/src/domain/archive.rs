@@ -1,17 +1,57 @@
 use anyhow::Result;
+use ignore::gitignore::{Gitignore, GitignoreBuilder, Glob};
+use ignore::Match;
 use md5;
-use std::collections::BTreeMap;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::Write;
 
+const FILE_MODE: u32 = 0o644;
+const EXECUTABLE_MODE: u32 = 0o755;
+
+/// SHA-256 and byte size of a packaged zip, i.e. of exactly what a client
+/// downloads. `compute_hash` covers the source files instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageDigest {
+    pub sha256: String,
+    pub size: i64,
+}
+
+impl PackageDigest {
+    pub fn of(zip: &[u8]) -> Self {
+        Self {
+            sha256: hex::encode(Sha256::digest(zip)),
+            size: zip.len() as i64,
+        }
+    }
+}
+
 pub fn package_zip(file_map: &BTreeMap<String, Vec<u8>>) -> Result<Vec<u8>> {
+    package_zip_with_executables(file_map, &BTreeSet::new())
+}
+
+/// Zips `file_map` so the same files always produce the same bytes: entries
+/// are written in path order with a fixed timestamp (the zip epoch,
+/// 1980-01-01) and normalized permissions. Paths in `executables` are stored
+/// as 0755, everything else as 0644.
+pub fn package_zip_with_executables(
+    file_map: &BTreeMap<String, Vec<u8>>,
+    executables: &BTreeSet<String>,
+) -> Result<Vec<u8>> {
     let mut new_zip_buffer = Vec::new();
     {
         let mut zip_writer = zip::ZipWriter::new(std::io::Cursor::new(&mut new_zip_buffer));
-        let options =
-            zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+        let options = zip::write::FileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .last_modified_time(zip::DateTime::default());
 
         for (path, content) in file_map {
-            zip_writer.start_file(path, options)?;
+            let mode = if executables.contains(path) {
+                EXECUTABLE_MODE
+            } else {
+                FILE_MODE
+            };
+            zip_writer.start_file(path, options.unix_permissions(mode))?;
             zip_writer.write_all(content)?;
         }
         zip_writer.finish()?;
@@ -34,30 +74,122 @@ pub fn normalize_dir_prefix(prefix: &str) -> String {
     p
 }
 
+/// Gitignore-style exclude rules, read from the repository root and from the
+/// directory a package is built from. Files with this name elsewhere are not
+/// read, but are still left out of packages.
+pub const SKILLIGNORE_FILE: &str = ".skillignore";
+
+/// Files under `dir_prefix`, relative to it, minus those excluded by a
+/// `.skillignore`. No `.skillignore` file is packaged, wherever it is.
 pub fn subtree_file_map(
     all_files: &BTreeMap<String, Vec<u8>>,
     dir_prefix: &str,
 ) -> BTreeMap<String, Vec<u8>> {
     let prefix = normalize_dir_prefix(dir_prefix);
+    let ignore = SkillIgnore::load(all_files, &prefix);
     let mut out = BTreeMap::new();
     for (path, bytes) in all_files {
-        if prefix.is_empty() {
-            out.insert(path.clone(), bytes.clone());
+        let Some(rel) = subtree_path(path, &prefix) else {
+            continue;
+        };
+        if rel.rsplit('/').next() == Some(SKILLIGNORE_FILE) || ignore.excludes(path, &rel) {
             continue;
         }
-        if path.starts_with(&prefix) {
-            let rel = path
-                .trim_start_matches(&prefix)
-                .trim_start_matches('/')
-                .to_string();
-            if !rel.is_empty() {
-                out.insert(rel, bytes.clone());
+        out.insert(rel, bytes.clone());
+    }
+    out
+}
+
+/// Rules from the package directory's `.skillignore` take precedence over
+/// the repository root's, so a package can re-include (`!path`) a file the
+/// repository excludes.
+struct SkillIgnore {
+    repo: Option<Gitignore>,
+    package: Option<Gitignore>,
+}
+
+impl SkillIgnore {
+    fn load(all_files: &BTreeMap<String, Vec<u8>>, prefix: &str) -> Self {
+        let rules = |path: &str| {
+            all_files
+                .get(path)
+                .map(|content| parse_skillignore(path, content))
+        };
+        Self {
+            repo: rules(SKILLIGNORE_FILE),
+            package: if prefix.is_empty() {
+                None
+            } else {
+                rules(&format!("{}{}", prefix, SKILLIGNORE_FILE))
+            },
+        }
+    }
+
+    fn excludes(&self, repo_path: &str, rel: &str) -> bool {
+        fn matched<'a>(rules: &'a Option<Gitignore>, path: &str) -> Match<&'a Glob> {
+            match rules {
+                Some(rules) => rules.matched_path_or_any_parents(path, false),
+                None => Match::None,
             }
         }
+        match matched(&self.package, rel) {
+            Match::Ignore(_) => true,
+            Match::Whitelist(_) => false,
+            Match::None => matched(&self.repo, repo_path).is_ignore(),
+        }
     }
-    out
 }
 
+/// Invalid patterns are skipped so one typo does not fail the whole sync.
+fn parse_skillignore(path: &str, content: &[u8]) -> Gitignore {
+    let mut builder = GitignoreBuilder::new("");
+    for line in String::from_utf8_lossy(content).lines() {
+        if let Err(e) = builder.add_line(None, line) {
+            tracing::warn!(path, error = %e, "Skipping invalid .skillignore pattern");
+        }
+    }
+    builder.build().unwrap_or_else(|e| {
+        tracing::warn!(path, error = %e, "Ignoring invalid .skillignore");
+        Gitignore::empty()
+    })
+}
+
+/// Moves the paths under `dir_prefix` to live under `package_prefix` instead,
+/// the way package files are laid out in their zip. Other paths are dropped.
+pub fn rebase_paths(
+    paths: &BTreeSet<String>,
+    dir_prefix: &str,
+    package_prefix: &str,
+) -> BTreeSet<String> {
+    let prefix = normalize_dir_prefix(dir_prefix);
+    let package_prefix = package_prefix.trim_matches('/');
+    paths
+        .iter()
+        .filter_map(|path| subtree_path(path, &prefix))
+        .map(|rel| {
+            if package_prefix.is_empty() {
+                rel
+            } else {
+                format!("{}/{}", package_prefix, rel)
+            }
+        })
+        .collect()
+}
+
+/// `path` relative to the normalized `prefix`, matching `subtree_file_map`.
+fn subtree_path(path: &str, prefix: &str) -> Option<String> {
+    if prefix.is_empty() {
+        return Some(path.to_string());
+    }
+    if !path.starts_with(prefix) {
+        return None;
+    }
+    let rel = path.trim_start_matches(prefix).trim_start_matches('/');
+    (!rel.is_empty()).then(|| rel.to_string())
+}
+
+/// Hash of the files in `file_map`. Callers pass what is packaged, so files
+/// excluded by a `.skillignore` never change it.
 pub fn compute_hash(file_map: &BTreeMap<String, Vec<u8>>) -> String {
     let mut context = md5::Context::new();
     for (path, content) in file_map {
@@ -67,6 +199,24 @@ pub fn compute_hash(file_map: &BTreeMap<String, Vec<u8>>) -> String {
     format!("{:x}", context.compute())
 }
 
+/// Hash of a package's files and the paths it stores as executable, so a
+/// change that only flips `chmod +x` yields a new hash. Without executables it
+/// equals `compute_hash`, keeping existing hashes stable.
+pub fn compute_package_hash(
+    file_map: &BTreeMap<String, Vec<u8>>,
+    executables: &BTreeSet<String>,
+) -> String {
+    let mut context = md5::Context::new();
+    for (path, content) in file_map {
+        context.consume(path.as_bytes());
+        if executables.contains(path) {
+            context.consume(format!("\0{:o}\0", EXECUTABLE_MODE).as_bytes());
+        }
+        context.consume(content);
+    }
+    format!("{:x}", context.compute())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,6 +232,37 @@ mod tests {
         assert_ne!(h1, h2);
     }
 
+    #[test]
+    fn compute_package_hash_covers_executable_bits() {
+        let mut files = BTreeMap::new();
+        files.insert("demo/run.sh".to_string(), b"#!/bin/sh".to_vec());
+        files.insert("demo/SKILL.md".to_string(), b"skill".to_vec());
+        let executables = BTreeSet::from(["demo/run.sh".to_string()]);
+
+        assert_eq!(
+            compute_package_hash(&files, &BTreeSet::new()),
+            compute_hash(&files)
+        );
+        assert_ne!(
+            compute_package_hash(&files, &executables),
+            compute_hash(&files)
+        );
+        assert_eq!(
+            compute_package_hash(&files, &executables),
+            compute_package_hash(&files, &executables)
+        );
+    }
+
+    #[test]
+    fn package_digest_covers_zip_bytes() {
+        let digest = PackageDigest::of(b"abc");
+        assert_eq!(
+            digest.sha256,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(digest.size, 3);
+    }
+
     #[test]
     fn package_zip_contains_expected_files() -> Result<()> {
         let mut m = BTreeMap::new();
@@ -100,6 +281,65 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn package_zip_is_deterministic() -> Result<()> {
+        let mut m = BTreeMap::new();
+        m.insert("demo/SKILL.md".to_string(), b"# Demo".to_vec());
+        m.insert(
+            "demo/scripts/run.sh".to_string(),
+            b"#!/bin/sh\necho ok".to_vec(),
+        );
+        let executables = BTreeSet::from(["demo/scripts/run.sh".to_string()]);
+
+        let first = package_zip_with_executables(&m, &executables)?;
+        let second = package_zip_with_executables(&m, &executables)?;
+        assert_eq!(first, second);
+        assert_eq!(PackageDigest::of(&first), PackageDigest::of(&second));
+
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(first))?;
+        let names: Vec<String> = archive.file_names().map(str::to_string).collect();
+        assert_eq!(names.len(), 2);
+        for (i, (path, mode)) in [("demo/SKILL.md", 0o644), ("demo/scripts/run.sh", 0o755)]
+            .into_iter()
+            .enumerate()
+        {
+            let file = archive.by_index(i)?;
+            assert_eq!(file.name(), path);
+            assert_eq!(file.unix_mode().map(|m| m & 0o777), Some(mode));
+            let modified = file.last_modified();
+            assert_eq!(
+                (modified.year(), modified.month(), modified.day()),
+                (1980, 1, 1)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn rebase_paths_moves_subtree_under_package_prefix() {
+        let paths = BTreeSet::from([
+            "skills/demo/run.sh".to_string(),
+            "skills/other/run.sh".to_string(),
+            "build.sh".to_string(),
+        ]);
+        assert_eq!(
+            rebase_paths(&paths, "skills/demo", "demo-skill"),
+            BTreeSet::from(["demo-skill/run.sh".to_string()])
+        );
+        assert_eq!(
+            rebase_paths(&paths, "", "/pkg/"),
+            BTreeSet::from([
+                "pkg/build.sh".to_string(),
+                "pkg/skills/demo/run.sh".to_string(),
+                "pkg/skills/other/run.sh".to_string(),
+            ])
+        );
+        assert_eq!(
+            rebase_paths(&paths, "skills/demo", ""),
+            BTreeSet::from(["run.sh".to_string()])
+        );
+    }
+
     #[test]
     fn normalize_dir_prefix_trims_and_adds_slash() {
         assert_eq!(normalize_dir_prefix("foo"), "foo/");
@@ -188,6 +428,61 @@ mod tests {
         assert_eq!(result.get("d.txt"), Some(&b"other".to_vec()));
     }
 
+    #[test]
+    fn subtree_file_map_honours_skillignore() {
+        let mut files = BTreeMap::new();
+        files.insert(
+            ".skillignore".to_string(),
+            b"node_modules/\n*.csv\n".to_vec(),
+        );
+        files.insert(
+            "demo/.skillignore".to_string(),
+            b"/tests\n!keep.csv\n".to_vec(),
+        );
+        files.insert("demo/SKILL.md".to_string(), b"# Demo".to_vec());
+        files.insert("demo/keep.csv".to_string(), b"a,b".to_vec());
+        files.insert("demo/data/sample.csv".to_string(), b"a,b".to_vec());
+        files.insert("demo/node_modules/x/index.js".to_string(), b"x".to_vec());
+        files.insert("demo/tests/fixture.md".to_string(), b"one".to_vec());
+        files.insert("demo/docs/tests/guide.md".to_string(), b"guide".to_vec());
+
+        let result = subtree_file_map(&files, "demo");
+        assert_eq!(
+            result.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["SKILL.md", "docs/tests/guide.md", "keep.csv"]
+        );
+
+        let hash = compute_hash(&result);
+        files.insert("demo/tests/fixture.md".to_string(), b"two".to_vec());
+        assert_eq!(compute_hash(&subtree_file_map(&files, "demo")), hash);
+
+        let root = subtree_file_map(&files, "");
+        assert!(!root.contains_key(".skillignore"));
+        assert!(root.contains_key("demo/tests/fixture.md"));
+        assert!(!root.contains_key("demo/keep.csv"));
+    }
+
+    #[test]
+    fn subtree_file_map_drops_nested_skillignore_files() {
+        let mut files = BTreeMap::new();
+        files.insert("demo/SKILL.md".to_string(), b"# Demo".to_vec());
+        files.insert("demo/.skillignore".to_string(), b"*.log\n".to_vec());
+        files.insert("demo/docs/.skillignore".to_string(), b"*.md\n".to_vec());
+        files.insert("demo/docs/guide.md".to_string(), b"guide".to_vec());
+
+        let result = subtree_file_map(&files, "demo");
+        assert_eq!(
+            result.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["SKILL.md", "docs/guide.md"]
+        );
+
+        let root = subtree_file_map(&files, "");
+        assert_eq!(
+            root.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["demo/SKILL.md", "demo/docs/guide.md"]
+        );
+    }
+
     #[test]
     fn subtree_file_map_empty_input_returns_empty() {
         let files = BTreeMap::new();
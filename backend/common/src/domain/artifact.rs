@@ -0,0 +1,106 @@
+use super::repo_url::{normalize_host, RepoLocator};
+use crate::entities::skill_registry;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactKind {
+    Skill,
+    Plugin,
+}
+
+impl ArtifactKind {
+    fn prefix(self) -> &'static str {
+        match self {
+            ArtifactKind::Skill => "skills",
+            ArtifactKind::Plugin => "plugins",
+        }
+    }
+}
+
+/// Host/owner/name of a registry row, falling back to the URL host for rows
+/// created before `host` was recorded.
+pub fn registry_locator(repo: &skill_registry::Model) -> RepoLocator {
+    let host = repo
+        .host
+        .as_deref()
+        .map(|host| host.trim().to_ascii_lowercase())
+        .filter(|host| !host.is_empty())
+        .or_else(|| normalize_host(&repo.url))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    RepoLocator {
+        host,
+        owner: repo.owner.clone(),
+        name: repo.name.clone(),
+    }
+}
+
+// Keys are scoped to the source repository so packages with the same name in
+// different repos never collide, and named after `archive::compute_package_hash`
+// of the packaged source files and their executable bits (an MD5, not a digest
+// of the zip) so a key is never reused for different content.
+pub fn artifact_key(
+    kind: ArtifactKind,
+    repo: &RepoLocator,
+    name: &str,
+    content_hash: &str,
+) -> String {
+    format!(
+        "{}/{}/{}/{}/{}/{}.zip",
+        kind.prefix(),
+        repo.host,
+        repo.owner,
+        repo.name,
+        name.trim_matches('/'),
+        content_hash
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry(host: Option<&str>, url: &str) -> skill_registry::Model {
+        skill_registry::Model {
+            id: 1,
+            discovery_registry_id: None,
+            platform: skill_registry::Platform::Github,
+            owner: "acme".to_string(),
+            name: "skills".to_string(),
+            url: url.to_string(),
+            host: host.map(ToString::to_string),
+            description: None,
+            repo_type: None,
+            status: "active".to_string(),
+            blacklist_reason: None,
+            blacklisted_at: None,
+            stars: 0,
+            last_scanned_at: None,
+            last_commit_sha: None,
+            indexed_tags: None,
+            created_at: chrono::Utc::now().naive_utc(),
+            updated_at: chrono::Utc::now().naive_utc(),
+        }
+    }
+
+    #[test]
+    fn artifact_key_is_scoped_to_repo_and_content() {
+        let repo = registry_locator(&registry(
+            Some("GitHub.com"),
+            "https://github.com/acme/skills",
+        ));
+        assert_eq!(
+            artifact_key(ArtifactKind::Skill, &repo, "pdf", "abc123"),
+            "skills/github.com/acme/skills/pdf/abc123.zip"
+        );
+        assert_eq!(
+            artifact_key(ArtifactKind::Plugin, &repo, "pdf", "abc123"),
+            "plugins/github.com/acme/skills/pdf/abc123.zip"
+        );
+    }
+
+    #[test]
+    fn registry_locator_falls_back_to_url_host() {
+        let repo = registry_locator(&registry(None, "https://gitlab.example.com/acme/skills"));
+        assert_eq!(repo.host, "gitlab.example.com");
+    }
+}
@@ -0,0 +1,171 @@
+use super::manifest::is_text;
+use serde::Serialize;
+use serde_json::Value;
+use similar::TextDiff;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Files larger than this on either side are reported without a text diff.
+pub const MAX_DIFF_FILE_BYTES: usize = 256 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffOmitted {
+    Binary,
+    TooLarge,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModifiedFile {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+    /// Why `diff` is missing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub omitted: Option<DiffOmitted>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FileChanges {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<ModifiedFile>,
+}
+
+/// A top-level metadata field whose value differs between two versions.
+/// `None` means the field is absent on that side.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetadataChange {
+    pub field: String,
+    pub from: Option<Value>,
+    pub to: Option<Value>,
+}
+
+pub fn diff_files(from: &BTreeMap<String, Vec<u8>>, to: &BTreeMap<String, Vec<u8>>) -> FileChanges {
+    let mut changes = FileChanges::default();
+    for path in from.keys().filter(|path| !to.contains_key(*path)) {
+        changes.removed.push(path.clone());
+    }
+    for (path, new) in to {
+        match from.get(path) {
+            None => changes.added.push(path.clone()),
+            Some(old) if old != new => changes.modified.push(diff_file(path, old, new)),
+            Some(_) => {}
+        }
+    }
+    changes
+}
+
+fn diff_file(path: &str, old: &[u8], new: &[u8]) -> ModifiedFile {
+    let omitted = if !is_text(old) || !is_text(new) {
+        Some(DiffOmitted::Binary)
+    } else if old.len() > MAX_DIFF_FILE_BYTES || new.len() > MAX_DIFF_FILE_BYTES {
+        Some(DiffOmitted::TooLarge)
+    } else {
+        None
+    };
+    let diff = match omitted {
+        Some(_) => None,
+        None => {
+            // Both sides were checked to be UTF-8 above.
+            let old = String::from_utf8_lossy(old);
+            let new = String::from_utf8_lossy(new);
+            Some(
+                TextDiff::from_lines(old.as_ref(), new.as_ref())
+                    .unified_diff()
+                    .header(&format!("a/{}", path), &format!("b/{}", path))
+                    .to_string(),
+            )
+        }
+    };
+    ModifiedFile {
+        path: path.to_string(),
+        diff,
+        omitted,
+    }
+}
+
+/// Compares the top-level fields of two metadata objects. Anything other than
+/// an object is treated as having no fields.
+pub fn diff_metadata(from: &Value, to: &Value) -> Vec<MetadataChange> {
+    let empty = serde_json::Map::new();
+    let from = from.as_object().unwrap_or(&empty);
+    let to = to.as_object().unwrap_or(&empty);
+    from.keys()
+        .chain(to.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|field| from.get(*field) != to.get(*field))
+        .map(|field| MetadataChange {
+            field: field.clone(),
+            from: from.get(field).cloned(),
+            to: to.get(field).cloned(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn files(entries: &[(&str, &[u8])]) -> BTreeMap<String, Vec<u8>> {
+        entries
+            .iter()
+            .map(|(path, content)| (path.to_string(), content.to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn diff_files_classifies_changes() {
+        let big = vec![b'a'; MAX_DIFF_FILE_BYTES + 1];
+        let from = files(&[
+            ("demo/SKILL.md", b"# Demo\nold line\n"),
+            ("demo/old.sh", b"echo old"),
+            ("demo/logo.png", b"\x89PNG\0a"),
+            ("demo/big.txt", b"small"),
+            ("demo/same.txt", b"same"),
+        ]);
+        let to = files(&[
+            ("demo/SKILL.md", b"# Demo\nnew line\n"),
+            ("demo/new.sh", b"echo new"),
+            ("demo/logo.png", b"\x89PNG\0b"),
+            ("demo/big.txt", &big),
+            ("demo/same.txt", b"same"),
+        ]);
+
+        let changes = diff_files(&from, &to);
+        assert_eq!(changes.added, vec!["demo/new.sh"]);
+        assert_eq!(changes.removed, vec!["demo/old.sh"]);
+        let modified: Vec<(&str, Option<DiffOmitted>)> = changes
+            .modified
+            .iter()
+            .map(|m| (m.path.as_str(), m.omitted))
+            .collect();
+        assert_eq!(
+            modified,
+            vec![
+                ("demo/SKILL.md", None),
+                ("demo/big.txt", Some(DiffOmitted::TooLarge)),
+                ("demo/logo.png", Some(DiffOmitted::Binary)),
+            ]
+        );
+        let diff = changes.modified[0].diff.as_deref().unwrap();
+        assert!(diff.starts_with("--- a/demo/SKILL.md\n+++ b/demo/SKILL.md\n"));
+        assert!(diff.contains("-old line\n+new line\n"));
+    }
+
+    #[test]
+    fn diff_metadata_lists_changed_fields_only() {
+        let changes = diff_metadata(
+            &json!({"allowed-tools": ["bash"], "license": "MIT", "homepage": "https://a"}),
+            &json!({"allowed-tools": ["bash", "rg"], "license": "MIT", "version": "2"}),
+        );
+        let fields: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["allowed-tools", "homepage", "version"]);
+        assert_eq!(changes[0].to, Some(json!(["bash", "rg"])));
+        assert_eq!(changes[1].to, None);
+        assert_eq!(changes[2].from, None);
+
+        assert!(diff_metadata(&Value::Null, &json!({})).is_empty());
+    }
+}
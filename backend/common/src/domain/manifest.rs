@@ -0,0 +1,180 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::{Cursor, Read};
+
+const OCTET_STREAM: &str = "application/octet-stream";
+
+const MIME_TYPES: &[(&str, &str)] = &[
+    ("md", "text/markdown"),
+    ("markdown", "text/markdown"),
+    ("txt", "text/plain"),
+    ("py", "text/x-python"),
+    ("sh", "text/x-shellscript"),
+    ("bash", "text/x-shellscript"),
+    ("js", "text/javascript"),
+    ("mjs", "text/javascript"),
+    ("ts", "text/x-typescript"),
+    ("rs", "text/x-rust"),
+    ("go", "text/x-go"),
+    ("rb", "text/x-ruby"),
+    ("html", "text/html"),
+    ("css", "text/css"),
+    ("csv", "text/csv"),
+    ("json", "application/json"),
+    ("yaml", "application/yaml"),
+    ("yml", "application/yaml"),
+    ("toml", "application/toml"),
+    ("xml", "application/xml"),
+    ("svg", "image/svg+xml"),
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("ico", "image/x-icon"),
+    ("pdf", "application/pdf"),
+    ("zip", "application/zip"),
+    ("gz", "application/gzip"),
+    ("wasm", "application/wasm"),
+    ("woff2", "font/woff2"),
+];
+
+const TEXT_APPLICATION_TYPES: &[&str] = &[
+    "application/json",
+    "application/yaml",
+    "application/toml",
+    "application/xml",
+    "image/svg+xml",
+];
+
+/// One file inside a package zip, as listed to users before they install it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileEntry {
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+    pub mime_type: String,
+}
+
+/// Manifest of the files packaged from `files`, keyed by their path in the zip.
+pub fn file_manifest(files: &BTreeMap<String, Vec<u8>>) -> Vec<FileEntry> {
+    files
+        .iter()
+        .map(|(path, content)| FileEntry {
+            path: path.clone(),
+            size: content.len() as u64,
+            sha256: hex::encode(Sha256::digest(content)),
+            mime_type: detect_mime_type(path, content).to_string(),
+        })
+        .collect()
+}
+
+/// Manifest read back from a stored zip, for packages uploaded before
+/// manifests were recorded.
+pub fn zip_manifest(zip: &[u8]) -> Result<Vec<FileEntry>> {
+    Ok(file_manifest(&unzip_files(zip)?))
+}
+
+/// Every file in a package zip, keyed by its path in the zip.
+pub fn unzip_files(zip: &[u8]) -> Result<BTreeMap<String, Vec<u8>>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(zip))?;
+    let mut files = BTreeMap::new();
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        if file.is_dir() {
+            continue;
+        }
+        let mut content = Vec::new();
+        file.read_to_end(&mut content)?;
+        files.insert(file.name().to_string(), content);
+    }
+    Ok(files)
+}
+
+pub fn read_zip_file(zip: &[u8], path: &str) -> Result<Option<Vec<u8>>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(zip))?;
+    let mut file = match archive.by_name(path) {
+        Ok(file) if !file.is_dir() => file,
+        Ok(_) | Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let mut content = Vec::new();
+    file.read_to_end(&mut content)?;
+    Ok(Some(content))
+}
+
+/// MIME type from the file extension, falling back to sniffing the content.
+/// Text extensions with binary content are reported as binary.
+pub fn detect_mime_type(path: &str, content: &[u8]) -> &'static str {
+    let extension = path
+        .rsplit('/')
+        .next()
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, ext)| ext.to_ascii_lowercase());
+    let known = extension.and_then(|ext| {
+        MIME_TYPES
+            .iter()
+            .find(|(candidate, _)| *candidate == ext)
+            .map(|(_, mime)| *mime)
+    });
+    match known {
+        Some(mime) if !is_text_mime_type(mime) || is_text(content) => mime,
+        Some(_) => OCTET_STREAM,
+        None if is_text(content) => "text/plain",
+        None => OCTET_STREAM,
+    }
+}
+
+pub fn is_text_mime_type(mime: &str) -> bool {
+    mime.starts_with("text/") || TEXT_APPLICATION_TYPES.contains(&mime)
+}
+
+/// Valid UTF-8 without NUL bytes.
+pub fn is_text(content: &[u8]) -> bool {
+    !content.contains(&0) && std::str::from_utf8(content).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::archive::package_zip;
+
+    #[test]
+    fn detect_mime_type_uses_extension_and_content() {
+        assert_eq!(
+            detect_mime_type("demo/SKILL.md", b"# Demo"),
+            "text/markdown"
+        );
+        assert_eq!(
+            detect_mime_type("demo/run.sh", b"echo ok"),
+            "text/x-shellscript"
+        );
+        assert_eq!(detect_mime_type("demo/logo.PNG", b"\x89PNG\0"), "image/png");
+        assert_eq!(detect_mime_type("demo/LICENSE", b"MIT"), "text/plain");
+        assert_eq!(detect_mime_type("demo/tool", b"\x7fELF\0\0"), OCTET_STREAM);
+        assert_eq!(detect_mime_type("demo/fake.txt", b"\0\0"), OCTET_STREAM);
+    }
+
+    #[test]
+    fn zip_manifest_matches_file_manifest() -> Result<()> {
+        let mut files = BTreeMap::new();
+        files.insert("demo/SKILL.md".to_string(), b"# Demo".to_vec());
+        files.insert("demo/scripts/run.sh".to_string(), b"echo ok".to_vec());
+        let zip = package_zip(&files)?;
+
+        let manifest = file_manifest(&files);
+        assert_eq!(zip_manifest(&zip)?, manifest);
+        assert_eq!(manifest[1].path, "demo/scripts/run.sh");
+        assert_eq!(manifest[1].size, 7);
+        assert_eq!(manifest[1].sha256.len(), 64);
+
+        assert_eq!(
+            read_zip_file(&zip, "demo/scripts/run.sh")?,
+            Some(b"echo ok".to_vec())
+        );
+        assert_eq!(read_zip_file(&zip, "demo/missing.sh")?, None);
+        Ok(())
+    }
+}
@@ -1,4 +1,10 @@
 pub mod archive;
+pub mod artifact;
+pub mod diff;
 pub mod json;
+pub mod manifest;
 pub mod markdown;
+pub mod repo_url;
+pub mod signing;
 pub mod skill;
+pub mod version;
@@ -0,0 +1,127 @@
+use url::Url;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoLocator {
+    pub host: String,
+    pub owner: String,
+    pub name: String,
+}
+
+impl RepoLocator {
+    pub fn canonical_url(&self) -> String {
+        format!("https://{}/{}/{}", self.host, self.owner, self.name)
+    }
+}
+
+pub fn normalize_host(url: &str) -> Option<String> {
+    let parsed = Url::parse(url).ok()?;
+    let host = parsed.host_str()?.trim();
+    if host.is_empty() {
+        None
+    } else {
+        Some(host.to_ascii_lowercase())
+    }
+}
+
+// Accepts web URLs (optionally pointing below the repo root, e.g. `/tree/main`
+// or GitLab's `/-/` pages), `.git` clone URLs and `git@host:owner/repo` SSH URLs.
+// GitLab owners may be nested groups, so everything before the repo name is kept.
+pub fn parse_repo_url(raw: &str) -> Option<RepoLocator> {
+    let raw = raw.trim();
+    let normalized = match raw.strip_prefix("git@") {
+        Some(rest) => format!("ssh://git@{}", rest.replacen(':', "/", 1)),
+        None => raw.to_string(),
+    };
+
+    let parsed = Url::parse(&normalized).ok()?;
+    if !matches!(parsed.scheme(), "http" | "https" | "ssh") {
+        return None;
+    }
+    let host = normalize_host(&normalized)?;
+
+    let mut segments: Vec<&str> = parsed
+        .path_segments()?
+        .filter(|s| !s.is_empty())
+        .take_while(|s| *s != "-")
+        .collect();
+
+    if host == "github.com" {
+        segments.truncate(2);
+    }
+    if segments.len() < 2 {
+        return None;
+    }
+
+    let name = segments.pop()?.trim_end_matches(".git");
+    if name.is_empty() {
+        return None;
+    }
+
+    Some(RepoLocator {
+        host,
+        owner: segments.join("/"),
+        name: name.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locator(host: &str, owner: &str, name: &str) -> RepoLocator {
+        RepoLocator {
+            host: host.to_string(),
+            owner: owner.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn normalize_host_lowercases_and_rejects_invalid_urls() {
+        assert_eq!(
+            normalize_host("https://GitHub.com/acme/repo"),
+            Some("github.com".to_string())
+        );
+        assert_eq!(normalize_host("not-a-url"), None);
+    }
+
+    #[test]
+    fn parse_repo_url_handles_web_clone_and_ssh_forms() {
+        let expected = locator("github.com", "acme", "skills");
+        assert_eq!(
+            parse_repo_url("https://GitHub.com/acme/skills"),
+            Some(expected.clone())
+        );
+        assert_eq!(
+            parse_repo_url("https://github.com/acme/skills.git"),
+            Some(expected.clone())
+        );
+        assert_eq!(
+            parse_repo_url("https://github.com/acme/skills/tree/main/skills"),
+            Some(expected.clone())
+        );
+        assert_eq!(
+            parse_repo_url("git@github.com:acme/skills.git"),
+            Some(expected.clone())
+        );
+        assert_eq!(
+            expected.canonical_url(),
+            "https://github.com/acme/skills".to_string()
+        );
+    }
+
+    #[test]
+    fn parse_repo_url_keeps_nested_gitlab_groups() {
+        assert_eq!(
+            parse_repo_url("https://gitlab.example.com/platform/ai/skills/-/tree/main"),
+            Some(locator("gitlab.example.com", "platform/ai", "skills"))
+        );
+    }
+
+    #[test]
+    fn parse_repo_url_rejects_incomplete_or_unsupported_urls() {
+        assert_eq!(parse_repo_url("https://github.com/acme"), None);
+        assert_eq!(parse_repo_url("ftp://github.com/acme/skills"), None);
+        assert_eq!(parse_repo_url("acme/skills"), None);
+    }
+}
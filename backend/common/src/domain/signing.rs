@@ -0,0 +1,210 @@
+use crate::settings::SigningSettings;
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+pub const SIGNATURE_ALGORITHM: &str = "ed25519";
+
+/// Detached signature over the exact zip bytes of a package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageSignature {
+    pub key_id: String,
+    pub signature: [u8; 64],
+}
+
+impl PackageSignature {
+    pub fn to_base64(&self) -> String {
+        general_purpose::STANDARD.encode(self.signature)
+    }
+}
+
+/// A public key clients may use to verify package signatures.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VerificationKey {
+    pub key_id: String,
+    pub algorithm: &'static str,
+    /// Base64 of the 32-byte Ed25519 public key.
+    pub public_key: String,
+}
+
+pub struct PackageSigner {
+    key_id: String,
+    key: SigningKey,
+}
+
+impl PackageSigner {
+    pub fn new(seed: [u8; 32], key_id: Option<String>) -> Self {
+        let key = SigningKey::from_bytes(&seed);
+        let key_id = key_id.unwrap_or_else(|| derive_key_id(&key.verifying_key()));
+        Self { key_id, key }
+    }
+
+    /// The configured signer, or `None` when no private key is set.
+    pub fn from_settings(settings: &SigningSettings) -> Result<Option<Self>> {
+        let Some(raw) = settings
+            .private_key
+            .as_deref()
+            .map(str::trim)
+            .filter(|raw| !raw.is_empty())
+        else {
+            return Ok(None);
+        };
+        let seed = decode_key(raw).context("Invalid signing.private_key")?;
+        let key_id = settings
+            .key_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(ToString::to_string);
+        Ok(Some(Self::new(seed, key_id)))
+    }
+
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    pub fn verification_key(&self) -> VerificationKey {
+        VerificationKey {
+            key_id: self.key_id.clone(),
+            algorithm: SIGNATURE_ALGORITHM,
+            public_key: general_purpose::STANDARD.encode(self.key.verifying_key().as_bytes()),
+        }
+    }
+
+    pub fn sign(&self, package: &[u8]) -> PackageSignature {
+        PackageSignature {
+            key_id: self.key_id.clone(),
+            signature: self.key.sign(package).to_bytes(),
+        }
+    }
+}
+
+/// Object key of the detached signature stored next to a package.
+pub fn signature_key(package_key: &str) -> String {
+    format!("{}.sig", package_key)
+}
+
+/// Every key a client should accept: the active signing key followed by the
+/// configured rotation keys. The first entry wins when key ids repeat.
+pub fn verification_keys(settings: &SigningSettings) -> Result<Vec<VerificationKey>> {
+    let mut keys = Vec::new();
+    if let Some(signer) = PackageSigner::from_settings(settings)? {
+        keys.push(signer.verification_key());
+    }
+    for configured in &settings.verification_keys {
+        let public = decode_key(configured.public_key.trim())
+            .with_context(|| format!("Invalid public key for '{}'", configured.key_id))?;
+        VerifyingKey::from_bytes(&public)
+            .with_context(|| format!("Invalid public key for '{}'", configured.key_id))?;
+        if keys.iter().any(|k| k.key_id == configured.key_id) {
+            continue;
+        }
+        keys.push(VerificationKey {
+            key_id: configured.key_id.clone(),
+            algorithm: SIGNATURE_ALGORITHM,
+            public_key: general_purpose::STANDARD.encode(public),
+        });
+    }
+    Ok(keys)
+}
+
+/// Checks `signature` against the key it names.
+pub fn verify_package(
+    keys: &[VerificationKey],
+    package: &[u8],
+    signature: &PackageSignature,
+) -> bool {
+    let Some(key) = keys.iter().find(|k| k.key_id == signature.key_id) else {
+        return false;
+    };
+    let Ok(public) = decode_key(&key.public_key) else {
+        return false;
+    };
+    let Ok(public) = VerifyingKey::from_bytes(&public) else {
+        return false;
+    };
+    public
+        .verify(package, &Signature::from_bytes(&signature.signature))
+        .is_ok()
+}
+
+fn derive_key_id(public: &VerifyingKey) -> String {
+    hex::encode(&Sha256::digest(public.as_bytes())[..8])
+}
+
+fn decode_key(raw: &str) -> Result<[u8; 32]> {
+    let bytes = general_purpose::STANDARD.decode(raw)?;
+    bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| anyhow::anyhow!("expected 32 bytes, got {}", bytes.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::VerificationKeySettings;
+
+    fn settings(seed: [u8; 32]) -> SigningSettings {
+        SigningSettings {
+            private_key: Some(general_purpose::STANDARD.encode(seed)),
+            key_id: None,
+            verification_keys: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn signing_is_disabled_without_a_private_key() {
+        let settings = SigningSettings::default();
+        assert!(PackageSigner::from_settings(&settings).unwrap().is_none());
+        assert!(verification_keys(&settings).unwrap().is_empty());
+    }
+
+    #[test]
+    fn signatures_verify_only_for_the_signed_bytes() {
+        let settings = settings([7; 32]);
+        let signer = PackageSigner::from_settings(&settings).unwrap().unwrap();
+        let keys = verification_keys(&settings).unwrap();
+        assert_eq!(keys, vec![signer.verification_key()]);
+        assert_eq!(signer.key_id().len(), 16);
+
+        let signature = signer.sign(b"package");
+        assert!(verify_package(&keys, b"package", &signature));
+        assert!(!verify_package(&keys, b"tampered", &signature));
+    }
+
+    #[test]
+    fn rotated_keys_keep_old_signatures_valid() {
+        let old = PackageSigner::new([1; 32], Some("2025".to_string()));
+        let mut settings = settings([2; 32]);
+        settings.key_id = Some("2026".to_string());
+        settings.verification_keys.push(VerificationKeySettings {
+            key_id: old.key_id().to_string(),
+            public_key: old.verification_key().public_key,
+        });
+
+        let keys = verification_keys(&settings).unwrap();
+        let ids: Vec<&str> = keys.iter().map(|k| k.key_id.as_str()).collect();
+        assert_eq!(ids, vec!["2026", "2025"]);
+        assert!(verify_package(&keys, b"zip", &old.sign(b"zip")));
+
+        let forged = PackageSignature {
+            key_id: "2026".to_string(),
+            ..old.sign(b"zip")
+        };
+        assert!(!verify_package(&keys, b"zip", &forged));
+    }
+
+    #[test]
+    fn invalid_keys_are_rejected() {
+        let mut settings = SigningSettings {
+            private_key: Some("not base64".to_string()),
+            ..Default::default()
+        };
+        assert!(PackageSigner::from_settings(&settings).is_err());
+
+        settings.private_key = Some(general_purpose::STANDARD.encode([0u8; 16]));
+        assert!(PackageSigner::from_settings(&settings).is_err());
+    }
+}
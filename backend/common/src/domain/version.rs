@@ -0,0 +1,154 @@
+use semver::{Version, VersionReq};
+use std::cmp::Ordering;
+
+/// Parses a version string as semver, accepting a leading `v` and missing
+/// minor/patch components (`v1.2` reads as `1.2.0`).
+pub fn parse_version(raw: &str) -> Option<Version> {
+    let trimmed = raw.trim();
+    let trimmed = trimmed.strip_prefix(['v', 'V']).unwrap_or(trimmed);
+    if let Ok(version) = Version::parse(trimmed) {
+        return Some(version);
+    }
+
+    let (core, suffix) = trimmed.split_at(trimmed.find(['-', '+']).unwrap_or(trimmed.len()));
+    let components = core.split('.').count();
+    if components >= 3 {
+        return None;
+    }
+    Version::parse(&format!(
+        "{}{}{}",
+        core,
+        ".0".repeat(3 - components),
+        suffix
+    ))
+    .ok()
+}
+
+/// Total ascending order over version strings.
+///
+/// Semver versions compare by precedence (build metadata is ignored) and sort
+/// above every non-semver string. Non-semver strings compare lexicographically
+/// among themselves. Remaining ties fall back to the raw string.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    match (parse_version(a), parse_version(b)) {
+        (Some(x), Some(y)) => x.cmp_precedence(&y).then_with(|| a.cmp(b)),
+        (Some(_), None) => Ordering::Greater,
+        (None, Some(_)) => Ordering::Less,
+        (None, None) => a.cmp(b),
+    }
+}
+
+/// Sorts newest first according to [`compare_versions`].
+pub fn sort_versions_desc<T>(items: &mut [T], version: impl Fn(&T) -> &str) {
+    items.sort_by(|a, b| compare_versions(version(b), version(a)));
+}
+
+/// The highest non-prerelease semver version, or the highest version of any
+/// kind when there is no stable release.
+pub fn select_latest<'a>(versions: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let versions: Vec<&str> = versions.into_iter().collect();
+    let stable = versions
+        .iter()
+        .copied()
+        .filter(|v| parse_version(v).is_some_and(|parsed| parsed.pre.is_empty()));
+    max_version(stable).or_else(|| max_version(versions.iter().copied()))
+}
+
+fn max_version<'a>(versions: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    versions.max_by(|a, b| compare_versions(a, b))
+}
+
+/// Resolves a requested version against the available version strings.
+///
+/// An exact string match always wins. A complete version such as `1.2.0` or
+/// `v1.2.0` only matches that version; anything else is read as a semver
+/// requirement (`^1.2`, `~1.2.3`, `>=1.0, <2`) and resolves to the highest
+/// matching version. Returns `None` when nothing matches or the request is
+/// neither a version nor a requirement.
+pub fn resolve_version<'a>(
+    requested: &str,
+    available: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let available: Vec<&str> = available.into_iter().collect();
+    let requested = requested.trim();
+    if let Some(exact) = available.iter().copied().find(|v| *v == requested) {
+        return Some(exact);
+    }
+
+    let bare = requested.strip_prefix(['v', 'V']).unwrap_or(requested);
+    if let Ok(pinned) = Version::parse(bare) {
+        return available
+            .into_iter()
+            .find(|v| parse_version(v).is_some_and(|parsed| parsed == pinned));
+    }
+
+    let requirement = VersionReq::parse(requested).ok()?;
+    max_version(
+        available
+            .into_iter()
+            .filter(|v| parse_version(v).is_some_and(|parsed| requirement.matches(&parsed))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version_accepts_prefixes_and_short_forms() {
+        assert_eq!(parse_version("v1.2.3"), Some(Version::new(1, 2, 3)));
+        assert_eq!(parse_version("1.2"), Some(Version::new(1, 2, 0)));
+        assert_eq!(
+            parse_version("2-beta.1").map(|v| v.pre.to_string()),
+            Some("beta.1".to_string())
+        );
+        assert_eq!(parse_version("latest"), None);
+        assert_eq!(parse_version("1.2.3.4"), None);
+    }
+
+    #[test]
+    fn sort_versions_desc_orders_semver_before_other_strings() {
+        let mut versions = vec!["1.10.0", "nightly", "1.2.0", "2.0.0-rc.1", "alpha", "1.9"];
+        sort_versions_desc(&mut versions, |v| *v);
+        assert_eq!(
+            versions,
+            vec!["2.0.0-rc.1", "1.10.0", "1.9", "1.2.0", "nightly", "alpha"]
+        );
+    }
+
+    #[test]
+    fn resolve_version_handles_pins_and_ranges() {
+        let available = [
+            "1.0.0",
+            "1.2.0",
+            "1.2.5",
+            "1.3.0-beta.1",
+            "2.0.0",
+            "nightly",
+        ];
+        assert_eq!(resolve_version("1.2.0", available), Some("1.2.0"));
+        assert_eq!(resolve_version("v1.2.0", available), Some("1.2.0"));
+        assert_eq!(resolve_version("1.1.0", available), None);
+        assert_eq!(resolve_version("nightly", available), Some("nightly"));
+        assert_eq!(resolve_version("^1.2", available), Some("1.2.5"));
+        assert_eq!(resolve_version("~1.2.0", available), Some("1.2.5"));
+        assert_eq!(resolve_version(">=1.0, <2", available), Some("1.2.5"));
+        assert_eq!(resolve_version("*", available), Some("2.0.0"));
+        assert_eq!(resolve_version("^3", available), None);
+        assert_eq!(resolve_version("not a version", available), None);
+    }
+
+    #[test]
+    fn select_latest_prefers_highest_stable_release() {
+        assert_eq!(
+            select_latest(["1.0.0", "1.2.0", "1.1.0", "2.0.0-rc.1"]),
+            Some("1.2.0")
+        );
+        assert_eq!(
+            select_latest(["1.0.0-beta", "1.0.0-rc.1"]),
+            Some("1.0.0-rc.1")
+        );
+        assert_eq!(select_latest(["main", "dev"]), Some("main"));
+        assert_eq!(select_latest(std::iter::empty()), None);
+    }
+}
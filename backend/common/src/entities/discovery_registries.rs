@@ -6,6 +6,10 @@ use serde::{Deserialize, Serialize};
 pub enum Platform {
     #[sea_orm(string_value = "github")]
     Github,
+    #[sea_orm(string_value = "gitlab")]
+    Gitlab,
+    #[sea_orm(string_value = "gitea")]
+    Gitea,
 }
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -30,6 +34,12 @@ pub struct Model {
     #[sea_orm(column_type = "Text", nullable)]
     pub last_run_message: Option<String>,
     pub next_run_at: Option<DateTime>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub webhook_secret: Option<String>,
+    pub rate_limit_remaining: Option<i32>,
+    pub rate_limit_reset_at: Option<DateTime>,
+    pub search_rate_limit_remaining: Option<i32>,
+    pub search_rate_limit_reset_at: Option<DateTime>,
     pub created_at: DateTime,
     pub updated_at: DateTime,
 }
@@ -1,15 +1,18 @@
 pub mod auth_identities;
 pub mod blacklist;
 pub mod discovery_registries;
+pub mod http_cache;
 pub mod local_credentials;
 pub mod org_memberships;
 pub mod organizations;
 pub mod plugin_components;
+pub mod plugin_version_revisions;
 pub mod plugin_versions;
 pub mod plugins;
 pub mod prelude;
 pub mod refresh_tokens;
 pub mod skill_registry;
+pub mod skill_version_revisions;
 pub mod skill_versions;
 pub mod skills;
 pub mod sso_connections;
@@ -37,10 +40,14 @@ mod tests {
         assert_roundtrip(&auth_identities::AuthProvider::Github);
         assert_roundtrip(&auth_identities::AuthProvider::Google);
         assert_roundtrip(&discovery_registries::Platform::Github);
+        assert_roundtrip(&discovery_registries::Platform::Gitlab);
+        assert_roundtrip(&discovery_registries::Platform::Gitea);
         assert_roundtrip(&org_memberships::OrgRole::Owner);
         assert_roundtrip(&org_memberships::OrgRole::Admin);
         assert_roundtrip(&org_memberships::OrgRole::Member);
         assert_roundtrip(&skill_registry::Platform::Github);
+        assert_roundtrip(&skill_registry::Platform::Gitlab);
+        assert_roundtrip(&skill_registry::Platform::Gitea);
         assert_roundtrip(&sso_connections::SsoProtocol::Oidc);
         assert_roundtrip(&sso_connections::SsoProtocol::Saml);
         assert_roundtrip(&users::UserStatus::Active);
@@ -58,12 +65,14 @@ mod tests {
         let _ = <org_memberships::Entity as Related<users::Entity>>::to();
         let _ = <organizations::Entity as Related<org_memberships::Entity>>::to();
         let _ = <plugin_components::Entity as Related<plugin_versions::Entity>>::to();
+        let _ = <plugin_version_revisions::Entity as Related<plugin_versions::Entity>>::to();
         let _ = <plugin_versions::Entity as Related<plugins::Entity>>::to();
         let _ = <plugin_versions::Entity as Related<plugin_components::Entity>>::to();
         let _ = <plugins::Entity as Related<skill_registry::Entity>>::to();
         let _ = <plugins::Entity as Related<plugin_versions::Entity>>::to();
         let _ = <refresh_tokens::Entity as Related<users::Entity>>::to();
         let _ = <skill_registry::Entity as Related<skills::Entity>>::to();
+        let _ = <skill_version_revisions::Entity as Related<skill_versions::Entity>>::to();
         let _ = <skill_versions::Entity as Related<skills::Entity>>::to();
         let _ = <skills::Entity as Related<skill_registry::Entity>>::to();
         let _ = <skills::Entity as Related<skill_versions::Entity>>::to();
@@ -15,8 +15,20 @@ pub struct Model {
     pub s3_key: Option<String>,
     pub oss_url: Option<String>,
     pub file_hash: Option<String>,
+    pub sha256: Option<String>,
+    pub file_size: Option<i64>,
+    pub signature: Option<String>,
+    pub signature_key_id: Option<String>,
     #[sea_orm(column_type = "Json", nullable)]
     pub metadata: Option<serde_json::Value>,
+    #[sea_orm(column_type = "Json", nullable)]
+    pub file_manifest: Option<serde_json::Value>,
+    pub commit_sha: Option<String>,
+    pub git_ref: Option<String>,
+    pub committed_at: Option<DateTime>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub source_path: Option<String>,
+    pub install_count: i32,
     pub created_at: DateTime,
 }
 
@@ -8,6 +8,8 @@ pub enum Platform {
     Github,
     #[sea_orm(string_value = "gitlab")]
     Gitlab,
+    #[sea_orm(string_value = "gitea")]
+    Gitea,
 }
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
@@ -30,6 +32,10 @@ pub struct Model {
     pub blacklisted_at: Option<DateTime>,
     pub stars: i32,
     pub last_scanned_at: Option<DateTime>,
+    pub last_commit_sha: Option<String>,
+    /// Release tags already indexed as versions, so each tag is cloned once.
+    #[sea_orm(column_type = "Json", nullable)]
+    pub indexed_tags: Option<serde_json::Value>,
     pub created_at: DateTime,
     pub updated_at: DateTime,
 }
@@ -0,0 +1,43 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Content published under an already released version string. The released
+/// `skill_versions` row is never rewritten; each distinct rebuild lands here.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "skill_version_revisions")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub skill_version_id: i32,
+    /// Starts at 2; revision 1 is the released `skill_versions` row itself.
+    pub revision: i32,
+    pub file_hash: String,
+    pub s3_key: Option<String>,
+    pub oss_url: Option<String>,
+    pub sha256: Option<String>,
+    pub file_size: Option<i64>,
+    pub signature: Option<String>,
+    pub signature_key_id: Option<String>,
+    pub commit_sha: Option<String>,
+    pub git_ref: Option<String>,
+    pub committed_at: Option<DateTime>,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::skill_versions::Entity",
+        from = "Column::SkillVersionId",
+        to = "super::skill_versions::Column::Id"
+    )]
+    SkillVersion,
+}
+
+impl Related<super::skill_versions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SkillVersion.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
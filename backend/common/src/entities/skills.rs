@@ -9,8 +9,12 @@ pub struct Model {
     pub name: String,
     pub skill_registry_id: i32,
     pub latest_version: Option<String>,
+    /// Version last built from the default branch; backs the `main` channel.
+    pub head_version: Option<String>,
     pub install_count: i32,
     pub is_active: i32,
+    /// Set when a released version string was republished with different content.
+    pub reused_version: Option<String>,
     pub created_at: DateTime,
     pub updated_at: DateTime,
 }
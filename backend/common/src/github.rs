@@ -1,8 +1,11 @@
-use crate::infra::github_http::{build_github_client, send_request_with_retry};
+use crate::infra::github_http::{
+    build_github_client, send_cached_request, send_request_with_retry, with_github_token, HttpCache,
+};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder};
 use serde::Deserialize;
+use std::sync::Arc;
 
 #[derive(Deserialize, Debug)]
 pub struct GithubSearchResponse {
@@ -40,12 +43,35 @@ pub struct GithubOwner {
 pub struct GithubClient {
     client: Client,
     api_url: String,
+    token: Option<String>,
+    cache: Option<Arc<dyn HttpCache>>,
 }
 
 impl GithubClient {
     pub fn new(token: Option<String>, api_url: String) -> Result<Self> {
-        let client = build_github_client(token.as_deref())?;
-        Ok(Self { client, api_url })
+        let client = build_github_client(None)?;
+        Ok(Self {
+            client,
+            api_url,
+            token,
+            cache: None,
+        })
+    }
+
+    fn get(&self, url: &str) -> RequestBuilder {
+        with_github_token(self.client.get(url), self.token.as_deref())
+    }
+
+    pub fn with_cache(mut self, cache: Option<Arc<dyn HttpCache>>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    pub async fn get_repository(&self, owner: &str, repo: &str) -> Result<GithubRepo> {
+        let url = format!("{}/repos/{}/{}", self.api_url, owner, repo);
+        let req = self.get(&url);
+        let body = send_cached_request(req, self.cache.as_deref(), "get repository").await?;
+        Ok(serde_json::from_slice(&body)?)
     }
 
     pub async fn search_repositories(&self, query: &str) -> Result<Vec<GithubRepo>> {
@@ -59,13 +85,14 @@ impl GithubClient {
 
             let per_page_str = per_page.to_string();
             let page_str = page.to_string();
-            let req = self.client.get(&url).query(&[
+            let req = self.get(&url).query(&[
                 ("q", query),
                 ("per_page", per_page_str.as_str()),
                 ("page", page_str.as_str()),
             ]);
-            let resp = send_request_with_retry(req, "search repositories").await?;
-            let search_resp: GithubSearchResponse = resp.json().await?;
+            let body =
+                send_cached_request(req, self.cache.as_deref(), "search repositories").await?;
+            let search_resp: GithubSearchResponse = serde_json::from_slice(&body)?;
 
             if search_resp.items.is_empty() {
                 break;
@@ -94,13 +121,13 @@ impl GithubClient {
 
             let per_page_str = per_page.to_string();
             let page_str = page.to_string();
-            let req = self.client.get(&url).query(&[
+            let req = self.get(&url).query(&[
                 ("q", query),
                 ("per_page", per_page_str.as_str()),
                 ("page", page_str.as_str()),
             ]);
-            let resp = send_request_with_retry(req, "search code").await?;
-            let search_resp: GithubCodeSearchResponse = resp.json().await?;
+            let body = send_cached_request(req, self.cache.as_deref(), "search code").await?;
+            let search_resp: GithubCodeSearchResponse = serde_json::from_slice(&body)?;
 
             if search_resp.items.is_empty() {
                 break;
@@ -122,7 +149,7 @@ impl GithubClient {
 
     pub async fn download_zipball(&self, owner: &str, repo: &str) -> Result<Vec<u8>> {
         let url = format!("{}/repos/{}/{}/zipball", self.api_url, owner, repo);
-        let req = self.client.get(&url);
+        let req = self.get(&url);
         let resp = send_request_with_retry(req, "download zipball").await?;
         let bytes = resp.bytes().await?;
         Ok(bytes.to_vec())
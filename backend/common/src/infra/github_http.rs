@@ -1,9 +1,14 @@
 use anyhow::Result;
-use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::{header, Client, RequestBuilder, Response, StatusCode};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
 use std::time::Duration;
 
 const DEFAULT_USER_AGENT: &str = "SkillRegistry/1.0";
 const GITHUB_ACCEPT_HEADER: &str = "application/vnd.github.v3+json";
+const DEFAULT_RATE_LIMIT_WAIT_SECS: i64 = 60;
 
 pub fn build_github_client(token: Option<&str>) -> Result<Client> {
     let mut headers = reqwest::header::HeaderMap::new();
@@ -23,40 +28,280 @@ pub fn build_github_client(token: Option<&str>) -> Result<Client> {
     Ok(Client::builder().default_headers(headers).build()?)
 }
 
+/// Attaches the GitHub token to a single request. Default client headers are
+/// only merged at send time, so cache and rate-limit keys need it explicitly.
+pub fn with_github_token(req: RequestBuilder, token: Option<&str>) -> RequestBuilder {
+    match token.map(str::trim).filter(|t| !t.is_empty()) {
+        Some(token) => req.bearer_auth(token),
+        None => req,
+    }
+}
+
+/// Request budget a provider tracks separately. GitHub gives search its own,
+/// much smaller, budget; GitLab and Gitea only report `Core`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitResource {
+    Core,
+    Search,
+}
+
+impl RateLimitResource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RateLimitResource::Core => "core",
+            RateLimitResource::Search => "search",
+        }
+    }
+}
+
+/// Remaining request budget for one token on one API host and resource, taken
+/// from the `X-RateLimit-*` (GitHub, Gitea) or `RateLimit-*` (GitLab) headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitBudget {
+    pub limit: Option<u32>,
+    pub remaining: u32,
+    pub reset_at: DateTime<Utc>,
+}
+
+impl RateLimitBudget {
+    pub fn is_exhausted(&self, now: DateTime<Utc>) -> bool {
+        self.remaining == 0 && self.reset_at > now
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("rate limit exhausted until {reset_at} ({context})")]
+pub struct RateLimited {
+    pub reset_at: DateTime<Utc>,
+    pub context: String,
+}
+
+impl RateLimited {
+    pub fn retry_after(&self, now: DateTime<Utc>) -> Duration {
+        (self.reset_at - now).to_std().unwrap_or_default() + Duration::from_secs(1)
+    }
+}
+
+// Process-local: budgets are not shared between workers or kept across
+// restarts, see `GithubSettings::token`.
+fn rate_limits() -> &'static Mutex<HashMap<String, RateLimitBudget>> {
+    static RATE_LIMITS: OnceLock<Mutex<HashMap<String, RateLimitBudget>>> = OnceLock::new();
+    RATE_LIMITS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn credential_digest(credential: Option<&str>) -> String {
+    format!("{:x}", md5::compute(credential.unwrap_or_default()))
+}
+
+fn request_credential(req: &reqwest::Request) -> Option<&str> {
+    if let Some(auth) = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+    {
+        return Some(
+            auth.strip_prefix("Bearer ")
+                .or_else(|| auth.strip_prefix("token "))
+                .unwrap_or(auth)
+                .trim(),
+        );
+    }
+    req.headers()
+        .get("PRIVATE-TOKEN")
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+}
+
+fn rate_limit_key(host: &str, credential: Option<&str>, resource: &str) -> String {
+    format!(
+        "{}:{}:{}",
+        host.to_ascii_lowercase(),
+        credential_digest(credential),
+        resource
+    )
+}
+
+/// Resource a request is expected to be charged to, before a response names
+/// it in `X-RateLimit-Resource`. Mirrors GitHub's split of the search API.
+fn request_resource(req: &reqwest::Request) -> &'static str {
+    let path = req.url().path();
+    if path.contains("/search/code") {
+        "code_search"
+    } else if path.contains("/search/") {
+        RateLimitResource::Search.as_str()
+    } else {
+        RateLimitResource::Core.as_str()
+    }
+}
+
+fn request_rate_limit_key(req: &reqwest::Request, resource: &str) -> String {
+    rate_limit_key(
+        req.url().host_str().unwrap_or_default(),
+        request_credential(req),
+        resource,
+    )
+}
+
+/// Key for the budget a response was charged to, preferring the resource the
+/// provider names over the one guessed from the path.
+fn response_rate_limit_key(req: &reqwest::Request, headers: &header::HeaderMap) -> String {
+    match response_resource(headers) {
+        Some(resource) => request_rate_limit_key(req, &resource),
+        None => request_rate_limit_key(req, request_resource(req)),
+    }
+}
+
+/// Last budget observed for `token` against `resource` of the API at `api_url`.
+pub fn rate_limit_budget(
+    api_url: &str,
+    token: Option<&str>,
+    resource: RateLimitResource,
+) -> Option<RateLimitBudget> {
+    let host = url::Url::parse(api_url).ok()?.host_str()?.to_string();
+    let token = token.map(str::trim).filter(|t| !t.is_empty());
+    let limits = rate_limits().lock().unwrap_or_else(|e| e.into_inner());
+    limits
+        .get(&rate_limit_key(&host, token, resource.as_str()))
+        .copied()
+}
+
+/// Fails with [`RateLimited`] while the `resource` budget for `token` is
+/// exhausted, so callers can defer work instead of spending requests that
+/// will be rejected.
+pub fn ensure_rate_limit_budget(
+    api_url: &str,
+    token: Option<&str>,
+    resource: RateLimitResource,
+    context: &str,
+) -> Result<()> {
+    match rate_limit_budget(api_url, token, resource) {
+        Some(budget) if budget.is_exhausted(Utc::now()) => Err(RateLimited {
+            reset_at: budget.reset_at,
+            context: context.to_string(),
+        }
+        .into()),
+        _ => Ok(()),
+    }
+}
+
+fn header_value<T: std::str::FromStr>(headers: &header::HeaderMap, names: &[&str]) -> Option<T> {
+    names.iter().find_map(|name| {
+        headers
+            .get(*name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse().ok())
+    })
+}
+
+fn response_resource(headers: &header::HeaderMap) -> Option<String> {
+    headers
+        .get("x-ratelimit-resource")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim().to_ascii_lowercase())
+        .filter(|v| !v.is_empty())
+}
+
+fn parse_rate_limit(headers: &header::HeaderMap, now: DateTime<Utc>) -> Option<RateLimitBudget> {
+    let remaining: u32 = header_value(headers, &["x-ratelimit-remaining", "ratelimit-remaining"])?;
+    let limit = header_value(headers, &["x-ratelimit-limit", "ratelimit-limit"]);
+    let reset: i64 = header_value(headers, &["x-ratelimit-reset", "ratelimit-reset"])?;
+    // GitHub and GitLab send an epoch timestamp; Gitea sends seconds until reset.
+    let reset_at = if reset > 1_000_000_000 {
+        DateTime::from_timestamp(reset, 0)?
+    } else {
+        now + chrono::Duration::seconds(reset)
+    };
+
+    Some(RateLimitBudget {
+        limit,
+        remaining,
+        reset_at,
+    })
+}
+
+/// Reset time for a rejected request, or `None` when a 403 is a plain
+/// permission error. Secondary limits only carry `Retry-After`.
+fn rate_limited_until(
+    status: StatusCode,
+    headers: &header::HeaderMap,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    if let Some(secs) = header_value::<i64>(headers, &["retry-after"]) {
+        return Some(now + chrono::Duration::seconds(secs));
+    }
+    if let Some(budget) = parse_rate_limit(headers, now).filter(|b| b.remaining == 0) {
+        return Some(budget.reset_at);
+    }
+    (status == StatusCode::TOO_MANY_REQUESTS)
+        .then(|| now + chrono::Duration::seconds(DEFAULT_RATE_LIMIT_WAIT_SECS))
+}
+
+fn record_rate_limit(key: &str, budget: RateLimitBudget) {
+    let mut limits = rate_limits().lock().unwrap_or_else(|e| e.into_inner());
+    limits.insert(key.to_string(), budget);
+}
+
+/// Sends `req`, retrying transient failures. Rate-limited responses are not
+/// slept on: they record the budget and fail with [`RateLimited`] so the
+/// caller (or Temporal's retry policy) can come back after the reset.
 pub async fn send_request_with_retry(req: RequestBuilder, context: &str) -> Result<Response> {
+    let built = req
+        .try_clone()
+        .ok_or_else(|| anyhow::anyhow!("failed to clone request"))?
+        .build()?;
+    let key = request_rate_limit_key(&built, request_resource(&built));
+
     let mut attempts = 0;
     loop {
         attempts += 1;
+
+        let now = Utc::now();
+        let exhausted = {
+            let limits = rate_limits().lock().unwrap_or_else(|e| e.into_inner());
+            limits.get(&key).filter(|b| b.is_exhausted(now)).copied()
+        };
+        if let Some(budget) = exhausted {
+            return Err(RateLimited {
+                reset_at: budget.reset_at,
+                context: context.to_string(),
+            }
+            .into());
+        }
+
         let response = req
             .try_clone()
             .ok_or_else(|| anyhow::anyhow!("failed to clone request"))?
             .send()
             .await?;
 
+        let now = Utc::now();
+        let charged_key = response_rate_limit_key(&built, response.headers());
+        if let Some(budget) = parse_rate_limit(response.headers(), now) {
+            record_rate_limit(&charged_key, budget);
+        }
+
         match response.status() {
-            StatusCode::OK => return Ok(response),
-            StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS => {
-                if attempts >= 5 {
-                    return Err(anyhow::anyhow!(
-                        "Rate limit exceeded after {} attempts ({})",
-                        attempts,
-                        context
-                    ));
-                }
+            StatusCode::OK | StatusCode::NOT_MODIFIED => return Ok(response),
+            status @ (StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS) => {
+                let Some(reset_at) = rate_limited_until(status, response.headers(), now) else {
+                    return Err(anyhow::anyhow!("API returned 403 Forbidden ({})", context));
+                };
 
-                let wait_time = response
-                    .headers()
-                    .get("Retry-After")
-                    .and_then(|value| value.to_str().ok())
-                    .and_then(|value| value.parse::<u64>().ok())
-                    .unwrap_or(60);
-                tracing::warn!(
-                    context,
-                    attempts,
-                    wait_time,
-                    "GitHub rate limit hit, waiting before retry"
+                let limit = parse_rate_limit(response.headers(), now).and_then(|b| b.limit);
+                record_rate_limit(
+                    &charged_key,
+                    RateLimitBudget {
+                        limit,
+                        remaining: 0,
+                        reset_at,
+                    },
                 );
-                tokio::time::sleep(Duration::from_secs(wait_time)).await;
+                tracing::warn!(context, %reset_at, "Rate limit hit, deferring until reset");
+                return Err(RateLimited {
+                    reset_at,
+                    context: context.to_string(),
+                }
+                .into());
             }
             StatusCode::UNPROCESSABLE_ENTITY => {
                 return Err(anyhow::anyhow!(
@@ -79,6 +324,145 @@ pub async fn send_request_with_retry(req: RequestBuilder, context: &str) -> Resu
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResponse {
+    pub url: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub body: String,
+}
+
+/// Persistent store for conditional GitHub requests. Entries are keyed by
+/// [`cache_key`], so different tokens never share cached bodies.
+#[async_trait]
+pub trait HttpCache: Send + Sync {
+    async fn get(&self, key: &str) -> Result<Option<CachedResponse>>;
+    async fn put(&self, key: &str, response: CachedResponse) -> Result<()>;
+}
+
+pub fn cache_key(req: &reqwest::Request) -> String {
+    let auth_digest = credential_digest(request_credential(req));
+    let raw = format!("{} {} {}", req.method(), req.url(), auth_digest);
+    format!("{:x}", md5::compute(raw))
+}
+
+async fn lookup_cached(
+    req: RequestBuilder,
+    cache: &dyn HttpCache,
+) -> Result<(RequestBuilder, String, Option<CachedResponse>)> {
+    let built = req
+        .try_clone()
+        .ok_or_else(|| anyhow::anyhow!("failed to clone request"))?
+        .build()?;
+    let key = cache_key(&built);
+
+    let cached = match cache.get(&key).await {
+        Ok(cached) => cached,
+        Err(e) => {
+            tracing::warn!(url = %built.url(), "HTTP cache lookup failed: {}", e);
+            None
+        }
+    };
+
+    let mut req = req;
+    if let Some(entry) = cached.as_ref() {
+        if let Some(etag) = entry.etag.as_deref() {
+            req = req.header(header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = entry.last_modified.as_deref() {
+            req = req.header(header::IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    Ok((req, key, cached))
+}
+
+async fn resolve_cached(
+    response: Response,
+    cache: &dyn HttpCache,
+    key: &str,
+    cached: Option<CachedResponse>,
+) -> Result<Vec<u8>> {
+    if response.status() == StatusCode::NOT_MODIFIED {
+        let entry =
+            cached.ok_or_else(|| anyhow::anyhow!("received 304 without a cached response"))?;
+        // Re-storing the entry refreshes its age so revalidated bodies stay cached.
+        if let Err(e) = cache.put(key, entry.clone()).await {
+            tracing::warn!("HTTP cache refresh failed: {}", e);
+        }
+        return Ok(entry.body.into_bytes());
+    }
+
+    let header_value = |name: header::HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let etag = header_value(header::ETAG);
+    let last_modified = header_value(header::LAST_MODIFIED);
+    let url = response.url().to_string();
+    let body = response.bytes().await?.to_vec();
+
+    if etag.is_some() || last_modified.is_some() {
+        if let Ok(text) = std::str::from_utf8(&body) {
+            let entry = CachedResponse {
+                url,
+                etag,
+                last_modified,
+                body: text.to_string(),
+            };
+            if let Err(e) = cache.put(key, entry).await {
+                tracing::warn!("HTTP cache store failed: {}", e);
+            }
+        }
+    }
+
+    Ok(body)
+}
+
+/// Sends `req` with retries, revalidating against `cache` when given. A 304
+/// answer is served from the cache; GitHub does not charge it against the quota.
+pub async fn send_cached_request(
+    req: RequestBuilder,
+    cache: Option<&dyn HttpCache>,
+    context: &str,
+) -> Result<Vec<u8>> {
+    let Some(cache) = cache else {
+        let response = send_request_with_retry(req, context).await?;
+        return Ok(response.bytes().await?.to_vec());
+    };
+
+    let (req, key, cached) = lookup_cached(req, cache).await?;
+    let response = send_request_with_retry(req, context).await?;
+    resolve_cached(response, cache, &key, cached).await
+}
+
+/// Single-attempt variant for callers that report the status themselves, such
+/// as health checks. Returns 200 for responses revalidated from the cache.
+pub async fn send_cached_once(
+    req: RequestBuilder,
+    cache: &dyn HttpCache,
+) -> Result<(StatusCode, Vec<u8>)> {
+    let (req, key, cached) = lookup_cached(req, cache).await?;
+    let built = req
+        .try_clone()
+        .ok_or_else(|| anyhow::anyhow!("failed to clone request"))?
+        .build()?;
+    let response = req.send().await?;
+    if let Some(budget) = parse_rate_limit(response.headers(), Utc::now()) {
+        record_rate_limit(&response_rate_limit_key(&built, response.headers()), budget);
+    }
+    let status = response.status();
+    if status != StatusCode::OK && status != StatusCode::NOT_MODIFIED {
+        return Ok((status, Vec::new()));
+    }
+
+    let body = resolve_cached(response, cache, &key, cached).await?;
+    Ok((StatusCode::OK, body))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,4 +483,215 @@ mod tests {
         let err = send_request_with_retry(req, "test").await.unwrap_err();
         assert!(err.to_string().contains("error"));
     }
+
+    #[derive(Default)]
+    struct MemoryCache {
+        entries: std::sync::Mutex<std::collections::HashMap<String, CachedResponse>>,
+    }
+
+    #[async_trait]
+    impl HttpCache for MemoryCache {
+        async fn get(&self, key: &str) -> Result<Option<CachedResponse>> {
+            Ok(self.entries.lock().unwrap().get(key).cloned())
+        }
+
+        async fn put(&self, key: &str, response: CachedResponse) -> Result<()> {
+            self.entries
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), response);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn cache_key_separates_urls_and_tokens() {
+        let client = build_github_client(None).unwrap();
+        let authed = |url: &str| {
+            with_github_token(client.get(url), Some("token-123"))
+                .build()
+                .unwrap()
+        };
+
+        let a = cache_key(&client.get("https://api.github.com/a").build().unwrap());
+        let b = cache_key(&client.get("https://api.github.com/b").build().unwrap());
+        let a_authed = cache_key(&authed("https://api.github.com/a"));
+
+        assert_ne!(a, b);
+        assert_ne!(a, a_authed);
+        assert_eq!(
+            a,
+            cache_key(&client.get("https://api.github.com/a").build().unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn lookup_cached_adds_conditional_headers() {
+        let cache = MemoryCache::default();
+        let client = reqwest::Client::new();
+        let req = client.get("https://api.github.com/rate_limit");
+        let key = cache_key(&req.try_clone().unwrap().build().unwrap());
+        cache
+            .put(
+                &key,
+                CachedResponse {
+                    url: "https://api.github.com/rate_limit".to_string(),
+                    etag: Some("\"abc\"".to_string()),
+                    last_modified: Some("Wed, 01 Jan 2025 00:00:00 GMT".to_string()),
+                    body: "{}".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+
+        let (req, looked_up_key, cached) = lookup_cached(req, &cache).await.unwrap();
+        let built = req.build().unwrap();
+        assert_eq!(looked_up_key, key);
+        assert!(cached.is_some());
+        assert_eq!(built.headers()[header::IF_NONE_MATCH], "\"abc\"");
+        assert_eq!(
+            built.headers()[header::IF_MODIFIED_SINCE],
+            "Wed, 01 Jan 2025 00:00:00 GMT"
+        );
+    }
+
+    #[tokio::test]
+    async fn send_cached_request_surfaces_connection_errors() {
+        let cache = MemoryCache::default();
+        let client = reqwest::Client::new();
+        let req = client.get("http://127.0.0.1:1/unreachable");
+        assert!(send_cached_request(req, Some(&cache), "test")
+            .await
+            .is_err());
+    }
+
+    fn rate_limit_headers(remaining: &str, reset: &str) -> header::HeaderMap {
+        let mut headers = header::HeaderMap::new();
+        headers.insert("x-ratelimit-limit", "5000".parse().unwrap());
+        headers.insert("x-ratelimit-remaining", remaining.parse().unwrap());
+        headers.insert("x-ratelimit-reset", reset.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn parse_rate_limit_reads_epoch_and_relative_resets() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+
+        let github = parse_rate_limit(&rate_limit_headers("42", "1700000600"), now).unwrap();
+        assert_eq!(github.limit, Some(5000));
+        assert_eq!(github.remaining, 42);
+        assert_eq!(github.reset_at, now + chrono::Duration::seconds(600));
+
+        let mut gitlab = header::HeaderMap::new();
+        gitlab.insert("ratelimit-remaining", "0".parse().unwrap());
+        gitlab.insert("ratelimit-reset", "30".parse().unwrap());
+        let gitlab = parse_rate_limit(&gitlab, now).unwrap();
+        assert!(gitlab.is_exhausted(now));
+        assert_eq!(gitlab.reset_at, now + chrono::Duration::seconds(30));
+
+        assert!(parse_rate_limit(&header::HeaderMap::new(), now).is_none());
+    }
+
+    #[test]
+    fn rate_limited_until_distinguishes_limits_from_permission_errors() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+
+        let mut secondary = header::HeaderMap::new();
+        secondary.insert("retry-after", "120".parse().unwrap());
+        assert_eq!(
+            rate_limited_until(StatusCode::FORBIDDEN, &secondary, now),
+            Some(now + chrono::Duration::seconds(120))
+        );
+
+        let primary = rate_limit_headers("0", "1700000900");
+        assert_eq!(
+            rate_limited_until(StatusCode::FORBIDDEN, &primary, now),
+            Some(now + chrono::Duration::seconds(900))
+        );
+
+        let forbidden = rate_limit_headers("10", "1700000900");
+        assert_eq!(
+            rate_limited_until(StatusCode::FORBIDDEN, &forbidden, now),
+            None
+        );
+        assert_eq!(
+            rate_limited_until(
+                StatusCode::TOO_MANY_REQUESTS,
+                &header::HeaderMap::new(),
+                now
+            ),
+            Some(now + chrono::Duration::seconds(DEFAULT_RATE_LIMIT_WAIT_SECS))
+        );
+    }
+
+    #[tokio::test]
+    async fn exhausted_budget_defers_requests_for_that_token_only() {
+        let api_url = "https://ratelimit.test.invalid";
+        let client = build_github_client(None).unwrap();
+        let req = with_github_token(
+            client.get(format!("{}/search/repositories", api_url)),
+            Some("exhausted-token"),
+        );
+        let built = req.try_clone().unwrap().build().unwrap();
+        let key = request_rate_limit_key(&built, request_resource(&built));
+        let reset_at = Utc::now() + chrono::Duration::minutes(10);
+        record_rate_limit(
+            &key,
+            RateLimitBudget {
+                limit: Some(30),
+                remaining: 0,
+                reset_at,
+            },
+        );
+
+        let budget = rate_limit_budget(
+            api_url,
+            Some(" exhausted-token "),
+            RateLimitResource::Search,
+        )
+        .unwrap();
+        assert_eq!(budget.remaining, 0);
+        assert!(
+            rate_limit_budget(api_url, Some("other-token"), RateLimitResource::Search).is_none()
+        );
+        assert!(ensure_rate_limit_budget(
+            api_url,
+            Some("other-token"),
+            RateLimitResource::Search,
+            "test"
+        )
+        .is_ok());
+        assert!(ensure_rate_limit_budget(
+            api_url,
+            Some("exhausted-token"),
+            RateLimitResource::Core,
+            "test"
+        )
+        .is_ok());
+
+        let err = send_request_with_retry(req, "test").await.unwrap_err();
+        let limited = err.downcast_ref::<RateLimited>().unwrap();
+        assert_eq!(limited.reset_at, reset_at);
+        assert!(limited.retry_after(Utc::now()) > Duration::from_secs(500));
+    }
+
+    #[test]
+    fn requests_are_keyed_by_rate_limit_resource() {
+        let client = build_github_client(None).unwrap();
+        let resource = |path: &str| {
+            let req = client
+                .get(format!("https://api.github.com{}", path))
+                .build()
+                .unwrap();
+            request_resource(&req)
+        };
+        assert_eq!(resource("/search/repositories"), "search");
+        assert_eq!(resource("/search/code"), "code_search");
+        assert_eq!(resource("/repos/acme/skills"), "core");
+
+        let mut headers = header::HeaderMap::new();
+        assert_eq!(response_resource(&headers), None);
+        headers.insert("x-ratelimit-resource", "Search".parse().unwrap());
+        assert_eq!(response_resource(&headers), Some("search".to_string()));
+    }
 }
@@ -0,0 +1,345 @@
+use super::s3::{ListedObject, ObjectInfo};
+use anyhow::{Context, Result};
+use aws_sdk_s3::primitives::ByteStream;
+use aws_smithy_types::byte_stream::Length;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::path::{Component, Path, PathBuf};
+
+/// Path under which the API serves locally stored objects.
+pub const LOCAL_OBJECT_ROUTE: &str = "/api/storage";
+
+/// Uploads are staged here and renamed into place, so readers never see a
+/// partially written object. Never listed, since no worker prefix matches it.
+const STAGING_DIR: &str = ".staging";
+
+/// Stores objects as files under a root directory, keyed by their relative
+/// path. Download URLs are signed with an HMAC instead of S3 presigning.
+pub struct LocalStorage {
+    root: PathBuf,
+    public_url: String,
+    url_signing_key: Option<String>,
+}
+
+impl LocalStorage {
+    pub fn new(
+        root: impl Into<PathBuf>,
+        public_url: Option<String>,
+        url_signing_key: Option<String>,
+    ) -> Self {
+        Self {
+            root: root.into(),
+            public_url: public_url
+                .map(|url| url.trim().trim_end_matches('/').to_string())
+                .unwrap_or_default(),
+            url_signing_key: url_signing_key.filter(|key| !key.trim().is_empty()),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn can_sign_urls(&self) -> bool {
+        self.url_signing_key.is_some()
+    }
+
+    pub async fn create_root(&self) -> Result<()> {
+        tokio::fs::create_dir_all(&self.root)
+            .await
+            .with_context(|| format!("Failed to create {}", self.root.display()))
+    }
+
+    pub async fn upload_file(&self, key: &str, body: Vec<u8>) -> Result<String> {
+        let path = self.object_path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let staging = self.root.join(STAGING_DIR);
+        tokio::fs::create_dir_all(&staging).await?;
+        let staged = staging.join(uuid::Uuid::new_v4().to_string());
+        tokio::fs::write(&staged, body).await?;
+        if let Err(e) = tokio::fs::rename(&staged, &path).await {
+            let _ = tokio::fs::remove_file(&staged).await;
+            return Err(e).with_context(|| format!("Failed to store {}", key));
+        }
+        Ok(self.object_url(key))
+    }
+
+    pub async fn download_file(&self, key: &str) -> Result<Vec<u8>> {
+        let path = self.object_path(key)?;
+        tokio::fs::read(&path)
+            .await
+            .with_context(|| format!("Failed to read {}", key))
+    }
+
+    pub async fn head_file(&self, key: &str) -> Result<ObjectInfo> {
+        let path = self.object_path(key)?;
+        let metadata = tokio::fs::metadata(&path)
+            .await
+            .with_context(|| format!("Failed to read {}", key))?;
+        Ok(ObjectInfo {
+            size: metadata.len(),
+            e_tag: None,
+        })
+    }
+
+    pub async fn open_file(&self, key: &str, range: Option<(u64, u64)>) -> Result<ByteStream> {
+        let mut builder = ByteStream::read_from().path(self.object_path(key)?);
+        if let Some((start, end)) = range {
+            builder = builder.offset(start).length(Length::Exact(end - start + 1));
+        }
+        builder
+            .build()
+            .await
+            .with_context(|| format!("Failed to read {}", key))
+    }
+
+    pub async fn copy_file(&self, from_key: &str, to_key: &str) -> Result<String> {
+        let from = self.object_path(from_key)?;
+        let to = self.object_path(to_key)?;
+        if let Some(parent) = to.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::copy(&from, &to)
+            .await
+            .with_context(|| format!("Failed to copy {} to {}", from_key, to_key))?;
+        Ok(self.object_url(to_key))
+    }
+
+    pub async fn list_files(&self, prefix: &str) -> Result<Vec<ListedObject>> {
+        // Only the directory part of the prefix can be walked; the rest is
+        // matched against each key.
+        let dir = match prefix.rfind('/') {
+            Some(end) => self.object_path(&prefix[..end])?,
+            None => self.root.clone(),
+        };
+        let root = self.root.clone();
+        let prefix = prefix.to_string();
+        tokio::task::spawn_blocking(move || -> Result<Vec<ListedObject>> {
+            let mut objects = Vec::new();
+            if !dir.exists() {
+                return Ok(objects);
+            }
+            for entry in walkdir::WalkDir::new(&dir) {
+                let entry = entry?;
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                let key = entry
+                    .path()
+                    .strip_prefix(&root)?
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                if !key.starts_with(&prefix) || key.starts_with(STAGING_DIR) {
+                    continue;
+                }
+                let metadata = entry.metadata()?;
+                objects.push(ListedObject {
+                    key,
+                    size: metadata.len(),
+                    last_modified: metadata.modified().ok().map(DateTime::<Utc>::from),
+                });
+            }
+            objects.sort_by(|a, b| a.key.cmp(&b.key));
+            Ok(objects)
+        })
+        .await?
+    }
+
+    /// Deleting a missing object succeeds, as it does on S3.
+    pub async fn delete_file(&self, key: &str) -> Result<()> {
+        match tokio::fs::remove_file(self.object_path(key)?).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e).with_context(|| format!("Failed to delete {}", key)),
+        }
+    }
+
+    pub fn presigned_url(&self, key: &str, expires_in: std::time::Duration) -> Result<String> {
+        self.presigned_url_at(key, Utc::now(), expires_in)
+    }
+
+    fn presigned_url_at(
+        &self,
+        key: &str,
+        now: DateTime<Utc>,
+        expires_in: std::time::Duration,
+    ) -> Result<String> {
+        self.object_path(key)?;
+        let expires = now.timestamp() + expires_in.as_secs() as i64;
+        let signature = hex::encode(self.url_mac(key, expires)?.finalize().into_bytes());
+        Ok(format!(
+            "{}?expires={}&signature={}",
+            self.object_url(key),
+            expires,
+            signature
+        ))
+    }
+
+    /// Whether a download URL for `key` was signed by this storage and has
+    /// not expired at `now`.
+    pub fn verify_url(&self, key: &str, expires: i64, signature: &str, now: DateTime<Utc>) -> bool {
+        if expires < now.timestamp() {
+            return false;
+        }
+        let Ok(signature) = hex::decode(signature) else {
+            return false;
+        };
+        self.url_mac(key, expires)
+            .map(|mac| mac.verify_slice(&signature).is_ok())
+            .unwrap_or(false)
+    }
+
+    fn url_mac(&self, key: &str, expires: i64) -> Result<Hmac<Sha256>> {
+        let secret = self.url_signing_key.as_deref().ok_or_else(|| {
+            anyhow::anyhow!("storage.url_signing_key is required to sign local download URLs")
+        })?;
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .map_err(|e| anyhow::anyhow!("Invalid storage.url_signing_key: {}", e))?;
+        mac.update(key.as_bytes());
+        mac.update(b"\n");
+        mac.update(expires.to_string().as_bytes());
+        Ok(mac)
+    }
+
+    fn object_url(&self, key: &str) -> String {
+        format!("{}{}/{}", self.public_url, LOCAL_OBJECT_ROUTE, key)
+    }
+
+    /// Rejects keys that could escape the root directory.
+    fn object_path(&self, key: &str) -> Result<PathBuf> {
+        let relative = Path::new(key);
+        let is_safe = !key.is_empty()
+            && relative
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+        if !is_safe {
+            anyhow::bail!("Invalid object key '{}'", key);
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_storage() -> LocalStorage {
+        let root = std::env::temp_dir().join(format!("skillregistry-{}", uuid::Uuid::new_v4()));
+        LocalStorage::new(
+            root,
+            Some("https://registry.example.com/".to_string()),
+            Some("secret".to_string()),
+        )
+    }
+
+    #[tokio::test]
+    async fn objects_round_trip_through_the_filesystem() -> Result<()> {
+        let storage = temp_storage();
+        storage.create_root().await?;
+
+        let url = storage
+            .upload_file("skills/github.com/acme/repo/demo/a.zip", b"zip".to_vec())
+            .await?;
+        assert_eq!(
+            url,
+            "https://registry.example.com/api/storage/skills/github.com/acme/repo/demo/a.zip"
+        );
+        storage
+            .upload_file("repo-snapshots/1/s.zip", b"snapshot".to_vec())
+            .await?;
+        storage
+            .copy_file(
+                "skills/github.com/acme/repo/demo/a.zip",
+                "skills/github.com/acme/repo/demo/b.zip",
+            )
+            .await?;
+
+        assert_eq!(
+            storage
+                .download_file("skills/github.com/acme/repo/demo/b.zip")
+                .await?,
+            b"zip"
+        );
+        assert_eq!(storage.head_file("repo-snapshots/1/s.zip").await?.size, 8);
+        let partial = storage
+            .open_file("repo-snapshots/1/s.zip", Some((1, 3)))
+            .await?
+            .collect()
+            .await?
+            .into_bytes();
+        assert_eq!(partial.as_ref(), b"nap");
+
+        let keys: Vec<String> = storage
+            .list_files("skills/")
+            .await?
+            .into_iter()
+            .map(|object| object.key)
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                "skills/github.com/acme/repo/demo/a.zip",
+                "skills/github.com/acme/repo/demo/b.zip",
+            ]
+        );
+        assert!(storage.list_files("plugins/").await?.is_empty());
+
+        storage
+            .delete_file("skills/github.com/acme/repo/demo/a.zip")
+            .await?;
+        storage
+            .delete_file("skills/github.com/acme/repo/demo/a.zip")
+            .await?;
+        assert!(storage
+            .download_file("skills/github.com/acme/repo/demo/a.zip")
+            .await
+            .is_err());
+
+        tokio::fs::remove_dir_all(storage.root()).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn keys_outside_the_root_are_rejected() {
+        let storage = temp_storage();
+        for key in ["", "../etc/passwd", "/etc/passwd", "skills/../../x"] {
+            assert!(storage.upload_file(key, Vec::new()).await.is_err(), "{key}");
+        }
+    }
+
+    #[test]
+    fn presigned_urls_expire_and_bind_the_key() {
+        let storage = temp_storage();
+        let now = Utc::now();
+        let url = storage
+            .presigned_url_at("skills/a.zip", now, std::time::Duration::from_secs(60))
+            .unwrap();
+        let query = url.split_once('?').unwrap().1;
+        let params: std::collections::HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes())
+            .into_owned()
+            .collect();
+        let expires: i64 = params["expires"].parse().unwrap();
+        let signature = &params["signature"];
+
+        assert!(storage.verify_url("skills/a.zip", expires, signature, now));
+        assert!(!storage.verify_url("skills/b.zip", expires, signature, now));
+        assert!(!storage.verify_url("skills/a.zip", expires + 1, signature, now));
+        assert!(!storage.verify_url(
+            "skills/a.zip",
+            expires,
+            signature,
+            now + chrono::Duration::seconds(61)
+        ));
+
+        let unsigned = LocalStorage::new(storage.root(), None, None);
+        assert!(!unsigned.can_sign_urls());
+        assert!(unsigned
+            .presigned_url("skills/a.zip", std::time::Duration::from_secs(60))
+            .is_err());
+    }
+}
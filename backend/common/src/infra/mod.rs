@@ -1,4 +1,6 @@
 pub mod github_http;
+pub mod local_storage;
 pub mod s3;
+pub mod storage;
 
 pub use s3::*;
@@ -9,6 +9,20 @@ use aws_sdk_s3::Client;
 use base64::{engine::general_purpose, Engine as _};
 use md5;
 
+/// Metadata of a stored object.
+pub struct ObjectInfo {
+    pub size: u64,
+    pub e_tag: Option<String>,
+}
+
+/// An object returned by a prefix listing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListedObject {
+    pub key: String,
+    pub size: u64,
+    pub last_modified: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 pub struct S3Service {
     client: Client,
     bucket: String,
@@ -162,6 +176,91 @@ impl S3Service {
         Ok(bytes)
     }
 
+    pub async fn head_file(&self, key: &str) -> Result<ObjectInfo> {
+        let head = self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await?;
+        Ok(ObjectInfo {
+            size: head.content_length.unwrap_or_default().max(0) as u64,
+            e_tag: head.e_tag,
+        })
+    }
+
+    /// Opens an object as a byte stream, optionally limited to an inclusive
+    /// byte range, so callers can forward it without buffering.
+    pub async fn open_file(&self, key: &str, range: Option<(u64, u64)>) -> Result<ByteStream> {
+        let obj = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .set_range(range.map(|(start, end)| format!("bytes={}-{}", start, end)))
+            .send()
+            .await?;
+        Ok(obj.body)
+    }
+
+    /// Server-side copy within the bucket; returns the public URL of `to_key`.
+    pub async fn copy_file(&self, from_key: &str, to_key: &str) -> Result<String> {
+        self.client
+            .copy_object()
+            .bucket(&self.bucket)
+            .copy_source(format!("{}/{}", self.bucket, from_key))
+            .key(to_key)
+            .send()
+            .await?;
+        Ok(build_object_url(&self.base_url, &self.bucket, to_key))
+    }
+
+    /// Every object under `prefix`, following continuation tokens.
+    pub async fn list_files(&self, prefix: &str) -> Result<Vec<ListedObject>> {
+        let mut objects = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let page = self
+                .client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .prefix(prefix)
+                .set_continuation_token(continuation_token.take())
+                .send()
+                .await?;
+            for object in page.contents() {
+                let Some(key) = object.key() else {
+                    continue;
+                };
+                objects.push(ListedObject {
+                    key: key.to_string(),
+                    size: object.size().unwrap_or_default().max(0) as u64,
+                    last_modified: object.last_modified().and_then(|modified| {
+                        chrono::DateTime::from_timestamp(modified.secs(), modified.subsec_nanos())
+                    }),
+                });
+            }
+            match page.next_continuation_token {
+                Some(token) if page.is_truncated.unwrap_or(false) => {
+                    continuation_token = Some(token)
+                }
+                _ => break,
+            }
+        }
+        Ok(objects)
+    }
+
+    pub async fn delete_file(&self, key: &str) -> Result<()> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await?;
+        Ok(())
+    }
+
     async fn upload_file_internal(&self, key: &str, body: &[u8], base64_md5: &str) -> Result<()> {
         const MULTIPART_THRESHOLD: usize = 5 * 1024 * 1024;
 
@@ -0,0 +1,82 @@
+use super::local_storage::LocalStorage;
+use super::s3::{ListedObject, ObjectInfo, S3Service};
+use anyhow::Result;
+use aws_sdk_s3::primitives::ByteStream;
+
+/// The object store selected by `storage.backend`, holding packages and repo
+/// snapshots.
+pub enum ObjectStore {
+    S3(S3Service),
+    Local(LocalStorage),
+}
+
+impl ObjectStore {
+    pub fn as_local(&self) -> Option<&LocalStorage> {
+        match self {
+            ObjectStore::Local(local) => Some(local),
+            ObjectStore::S3(_) => None,
+        }
+    }
+
+    pub async fn upload_file(&self, key: &str, body: Vec<u8>) -> Result<String> {
+        match self {
+            ObjectStore::S3(s3) => s3.upload_file(key, body).await,
+            ObjectStore::Local(local) => local.upload_file(key, body).await,
+        }
+    }
+
+    /// A short-lived download URL: presigned by S3, or signed by the API for
+    /// local storage.
+    pub async fn get_presigned_url(
+        &self,
+        key: &str,
+        expires_in: std::time::Duration,
+    ) -> Result<String> {
+        match self {
+            ObjectStore::S3(s3) => s3.get_presigned_url(key, expires_in).await,
+            ObjectStore::Local(local) => local.presigned_url(key, expires_in),
+        }
+    }
+
+    pub async fn download_file(&self, key: &str) -> Result<Vec<u8>> {
+        match self {
+            ObjectStore::S3(s3) => s3.download_file(key).await,
+            ObjectStore::Local(local) => local.download_file(key).await,
+        }
+    }
+
+    pub async fn head_file(&self, key: &str) -> Result<ObjectInfo> {
+        match self {
+            ObjectStore::S3(s3) => s3.head_file(key).await,
+            ObjectStore::Local(local) => local.head_file(key).await,
+        }
+    }
+
+    pub async fn open_file(&self, key: &str, range: Option<(u64, u64)>) -> Result<ByteStream> {
+        match self {
+            ObjectStore::S3(s3) => s3.open_file(key, range).await,
+            ObjectStore::Local(local) => local.open_file(key, range).await,
+        }
+    }
+
+    pub async fn copy_file(&self, from_key: &str, to_key: &str) -> Result<String> {
+        match self {
+            ObjectStore::S3(s3) => s3.copy_file(from_key, to_key).await,
+            ObjectStore::Local(local) => local.copy_file(from_key, to_key).await,
+        }
+    }
+
+    pub async fn list_files(&self, prefix: &str) -> Result<Vec<ListedObject>> {
+        match self {
+            ObjectStore::S3(s3) => s3.list_files(prefix).await,
+            ObjectStore::Local(local) => local.list_files(prefix).await,
+        }
+    }
+
+    pub async fn delete_file(&self, key: &str) -> Result<()> {
+        match self {
+            ObjectStore::S3(s3) => s3.delete_file(key).await,
+            ObjectStore::Local(local) => local.delete_file(key).await,
+        }
+    }
+}
@@ -18,7 +18,8 @@ pub struct AppState {
 }
 
 pub use builders::{
-    build_all, build_repositories, build_s3_service, build_services, Repositories, Services,
+    build_all, build_object_store, build_repositories, build_s3_service, build_services,
+    Repositories, Services,
 };
 pub use github::GithubClient;
 pub use services::{plugins, registry, skills, ServiceError};
@@ -1,4 +1,5 @@
 use crate::entities::{discovery_registries, prelude::DiscoveryRegistries};
+use crate::infra::github_http::RateLimitResource;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
     Set,
@@ -61,6 +62,21 @@ pub trait DiscoveryRegistryRepository: Send + Sync {
         checked_at: chrono::NaiveDateTime,
         updated_at: chrono::NaiveDateTime,
     ) -> Result<(), DbErr>;
+
+    async fn update_webhook_secret(
+        &self,
+        id: i32,
+        webhook_secret: Option<String>,
+        updated_at: chrono::NaiveDateTime,
+    ) -> Result<bool, DbErr>;
+
+    async fn update_rate_limit(
+        &self,
+        id: i32,
+        resource: RateLimitResource,
+        remaining: Option<i32>,
+        reset_at: Option<chrono::NaiveDateTime>,
+    ) -> Result<(), DbErr>;
 }
 
 pub struct DiscoveryRegistryRepositoryImpl {
@@ -115,6 +131,11 @@ impl DiscoveryRegistryRepository for DiscoveryRegistryRepositoryImpl {
             last_run_status: Set(None),
             last_run_message: Set(None),
             next_run_at: Set(Some(next_run_at)),
+            webhook_secret: Set(None),
+            rate_limit_remaining: Set(None),
+            rate_limit_reset_at: Set(None),
+            search_rate_limit_remaining: Set(None),
+            search_rate_limit_reset_at: Set(None),
             created_at: Set(now),
             updated_at: Set(now),
             ..Default::default()
@@ -204,4 +225,46 @@ impl DiscoveryRegistryRepository for DiscoveryRegistryRepositoryImpl {
 
         Ok(())
     }
+
+    async fn update_webhook_secret(
+        &self,
+        id: i32,
+        webhook_secret: Option<String>,
+        updated_at: chrono::NaiveDateTime,
+    ) -> Result<bool, DbErr> {
+        let Some(existing) = self.find_by_id(id).await? else {
+            return Ok(false);
+        };
+
+        let mut active: discovery_registries::ActiveModel = existing.into();
+        active.webhook_secret = Set(webhook_secret);
+        active.updated_at = Set(updated_at);
+        active.update(self.db.as_ref()).await?;
+        Ok(true)
+    }
+
+    async fn update_rate_limit(
+        &self,
+        id: i32,
+        resource: RateLimitResource,
+        remaining: Option<i32>,
+        reset_at: Option<chrono::NaiveDateTime>,
+    ) -> Result<(), DbErr> {
+        if let Some(existing) = self.find_by_id(id).await? {
+            let mut active: discovery_registries::ActiveModel = existing.into();
+            match resource {
+                RateLimitResource::Core => {
+                    active.rate_limit_remaining = Set(remaining);
+                    active.rate_limit_reset_at = Set(reset_at);
+                }
+                RateLimitResource::Search => {
+                    active.search_rate_limit_remaining = Set(remaining);
+                    active.search_rate_limit_reset_at = Set(reset_at);
+                }
+            }
+            active.update(self.db.as_ref()).await?;
+        }
+
+        Ok(())
+    }
 }
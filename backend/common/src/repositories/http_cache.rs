@@ -0,0 +1,70 @@
+use crate::entities::http_cache;
+use crate::infra::github_http::{CachedResponse, HttpCache};
+use anyhow::Result;
+use sea_orm::*;
+use std::sync::Arc;
+
+/// Entries not stored or revalidated within this window are ignored and
+/// evicted, so one-off search pages do not pile up forever.
+const CACHE_TTL_DAYS: i64 = 7;
+
+pub struct HttpCacheRepositoryImpl {
+    db: Arc<DatabaseConnection>,
+}
+
+impl HttpCacheRepositoryImpl {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    fn cutoff() -> chrono::NaiveDateTime {
+        (chrono::Utc::now() - chrono::Duration::days(CACHE_TTL_DAYS)).naive_utc()
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpCache for HttpCacheRepositoryImpl {
+    async fn get(&self, key: &str) -> Result<Option<CachedResponse>> {
+        let entry = http_cache::Entity::find()
+            .filter(http_cache::Column::CacheKey.eq(key))
+            .filter(http_cache::Column::UpdatedAt.gte(Self::cutoff()))
+            .one(self.db.as_ref())
+            .await?;
+
+        Ok(entry.map(|model| CachedResponse {
+            url: model.url,
+            etag: model.etag,
+            last_modified: model.last_modified,
+            body: model.body,
+        }))
+    }
+
+    async fn put(&self, key: &str, response: CachedResponse) -> Result<()> {
+        let now = chrono::Utc::now().naive_utc();
+        http_cache::Entity::delete_many()
+            .filter(http_cache::Column::UpdatedAt.lt(Self::cutoff()))
+            .filter(http_cache::Column::CacheKey.ne(key))
+            .exec(self.db.as_ref())
+            .await?;
+
+        let existing = http_cache::Entity::find()
+            .filter(http_cache::Column::CacheKey.eq(key))
+            .one(self.db.as_ref())
+            .await?;
+
+        let mut active: http_cache::ActiveModel = match existing {
+            Some(model) => model.into(),
+            None => http_cache::ActiveModel {
+                cache_key: Set(key.to_string()),
+                ..Default::default()
+            },
+        };
+        active.url = Set(response.url);
+        active.etag = Set(response.etag);
+        active.last_modified = Set(response.last_modified);
+        active.body = Set(response.body);
+        active.updated_at = Set(now);
+        active.save(self.db.as_ref()).await?;
+        Ok(())
+    }
+}
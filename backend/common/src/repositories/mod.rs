@@ -1,9 +1,11 @@
 pub mod discovery_registries;
+pub mod http_cache;
 pub mod plugins;
 pub mod registry;
 pub mod skills;
 
 pub use discovery_registries::{DiscoveryRegistryRepository, DiscoveryRegistryRepositoryImpl};
+pub use http_cache::HttpCacheRepositoryImpl;
 pub use plugins::{PluginRepository, PluginRepositoryImpl};
-pub use registry::{RegistryRepository, RegistryRepositoryImpl};
+pub use registry::{eq_ignore_case, RegistryRepository, RegistryRepositoryImpl};
 pub use skills::{SkillRepository, SkillRepositoryImpl};
@@ -1,5 +1,9 @@
+use crate::domain::version::sort_versions_desc;
 use crate::entities::{plugin_components, plugin_versions, plugins, prelude::*, skills};
-use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, ExprTrait, QueryFilter, QueryOrder,
+};
 use std::sync::Arc;
 
 #[async_trait::async_trait]
@@ -18,6 +22,9 @@ pub trait PluginRepository: Send + Sync {
         version: &str,
     ) -> Result<Option<plugin_versions::Model>, DbErr>;
 
+    /// All versions of a plugin, newest first by semver precedence.
+    async fn find_versions(&self, plugin_id: i32) -> Result<Vec<plugin_versions::Model>, DbErr>;
+
     async fn find_components(
         &self,
         plugin_version_id: i32,
@@ -38,6 +45,9 @@ pub trait PluginRepository: Send + Sync {
         &self,
         plugin_version_id: i32,
     ) -> Result<Vec<plugin_components::Model>, DbErr>;
+
+    /// Counts one install against both the plugin total and the downloaded version.
+    async fn increment_install_count(&self, plugin_id: i32, version_id: i32) -> Result<(), DbErr>;
 }
 
 pub struct PluginRepositoryImpl {
@@ -86,6 +96,15 @@ impl PluginRepository for PluginRepositoryImpl {
             .await
     }
 
+    async fn find_versions(&self, plugin_id: i32) -> Result<Vec<plugin_versions::Model>, DbErr> {
+        let mut versions = PluginVersions::find()
+            .filter(plugin_versions::Column::PluginId.eq(plugin_id))
+            .all(self.db.as_ref())
+            .await?;
+        sort_versions_desc(&mut versions, |v| v.version.as_str());
+        Ok(versions)
+    }
+
     async fn find_components(
         &self,
         plugin_version_id: i32,
@@ -140,4 +159,34 @@ impl PluginRepository for PluginRepositoryImpl {
             .all(self.db.as_ref())
             .await
     }
+
+    async fn increment_install_count(&self, plugin_id: i32, version_id: i32) -> Result<(), DbErr> {
+        let result = PluginVersions::update_many()
+            .col_expr(
+                plugin_versions::Column::InstallCount,
+                Expr::col(plugin_versions::Column::InstallCount).add(1),
+            )
+            .filter(plugin_versions::Column::Id.eq(version_id))
+            .filter(plugin_versions::Column::PluginId.eq(plugin_id))
+            .exec(self.db.as_ref())
+            .await?;
+
+        if result.rows_affected == 0 {
+            return Err(DbErr::RecordNotFound(format!(
+                "plugin version id {} not found for plugin id {}",
+                version_id, plugin_id
+            )));
+        }
+
+        Plugins::update_many()
+            .col_expr(
+                plugins::Column::InstallCount,
+                Expr::col(plugins::Column::InstallCount).add(1),
+            )
+            .filter(plugins::Column::Id.eq(plugin_id))
+            .exec(self.db.as_ref())
+            .await?;
+
+        Ok(())
+    }
 }
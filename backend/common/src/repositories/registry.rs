@@ -1,4 +1,5 @@
 use crate::entities::{blacklist, prelude::*, skill_registry};
+use sea_orm::sea_query::{Expr, Func, SimpleExpr};
 use sea_orm::*;
 use std::sync::Arc;
 
@@ -19,6 +20,15 @@ pub trait RegistryRepository: Send + Sync {
 
     async fn find_by_id(&self, id: i32) -> Result<Option<skill_registry::Model>, DbErr>;
 
+    async fn create_repo(
+        &self,
+        platform: skill_registry::Platform,
+        host: &str,
+        owner: &str,
+        name: &str,
+        url: &str,
+    ) -> Result<skill_registry::Model, DbErr>;
+
     async fn find_all_pending(&self, expiry_date: chrono::NaiveDateTime)
         -> Result<Vec<i32>, DbErr>;
 
@@ -56,6 +66,12 @@ impl RegistryRepositoryImpl {
     }
 }
 
+/// GitHub, GitLab and Gitea resolve owner and repository names regardless of
+/// case, so stored names are matched the same way.
+pub fn eq_ignore_case(column: impl ColumnTrait, value: &str) -> SimpleExpr {
+    Func::lower(Expr::col(column)).eq(value.to_lowercase())
+}
+
 fn host_match_condition(host: &str) -> Condition {
     let normalized_host = host.trim().to_ascii_lowercase();
     let url_https = format!("https://{}/%", normalized_host);
@@ -89,8 +105,8 @@ impl RegistryRepository for RegistryRepositoryImpl {
         repo: &str,
     ) -> Result<Option<skill_registry::Model>, DbErr> {
         SkillRegistry::find()
-            .filter(skill_registry::Column::Owner.eq(org))
-            .filter(skill_registry::Column::Name.eq(repo))
+            .filter(eq_ignore_case(skill_registry::Column::Owner, org))
+            .filter(eq_ignore_case(skill_registry::Column::Name, repo))
             .filter(skill_registry::Column::Status.ne("blacklisted"))
             .filter(host_match_condition(host))
             .one(self.db.as_ref())
@@ -101,6 +117,31 @@ impl RegistryRepository for RegistryRepositoryImpl {
         SkillRegistry::find_by_id(id).one(self.db.as_ref()).await
     }
 
+    async fn create_repo(
+        &self,
+        platform: skill_registry::Platform,
+        host: &str,
+        owner: &str,
+        name: &str,
+        url: &str,
+    ) -> Result<skill_registry::Model, DbErr> {
+        let now = chrono::Utc::now().naive_utc();
+        skill_registry::ActiveModel {
+            platform: Set(platform),
+            owner: Set(owner.to_string()),
+            name: Set(name.to_string()),
+            url: Set(url.to_string()),
+            host: Set(Some(host.to_string())),
+            status: Set("active".to_string()),
+            stars: Set(0),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        }
+        .insert(self.db.as_ref())
+        .await
+    }
+
     async fn find_all_pending(
         &self,
         expiry_date: chrono::NaiveDateTime,
@@ -162,14 +203,14 @@ impl RegistryRepository for RegistryRepositoryImpl {
 
     async fn find_blacklist_by_url(&self, url: &str) -> Result<Option<blacklist::Model>, DbErr> {
         Blacklist::find()
-            .filter(blacklist::Column::RepositoryUrl.eq(url))
+            .filter(eq_ignore_case(blacklist::Column::RepositoryUrl, url))
             .one(self.db.as_ref())
             .await
     }
 
     async fn upsert_blacklist(&self, url: &str, reason: &str) -> Result<(), DbErr> {
         if let Some(_existing) = Blacklist::find()
-            .filter(blacklist::Column::RepositoryUrl.eq(url))
+            .filter(eq_ignore_case(blacklist::Column::RepositoryUrl, url))
             .one(self.db.as_ref())
             .await?
         {
@@ -1,3 +1,4 @@
+use crate::domain::version::sort_versions_desc;
 use crate::entities::{prelude::*, skill_registry, skill_versions, skills};
 use sea_orm::sea_query::Expr;
 use sea_orm::{
@@ -66,6 +67,7 @@ pub trait SkillRepository: Send + Sync {
         name: &str,
     ) -> Result<Option<skills::Model>, DbErr>;
 
+    /// All versions of a skill, newest first by semver precedence.
     async fn find_versions(&self, skill_id: i32) -> Result<Vec<skill_versions::Model>, DbErr>;
 
     async fn find_version_by_name(
@@ -104,7 +106,8 @@ pub trait SkillRepository: Send + Sync {
 
     async fn update_skill_active(&self, skill: skills::Model, is_active: i32) -> Result<(), DbErr>;
 
-    async fn increment_install_count(&self, skill_id: i32) -> Result<(), DbErr>;
+    /// Counts one install against both the skill total and the downloaded version.
+    async fn increment_install_count(&self, skill_id: i32, version_id: i32) -> Result<(), DbErr>;
 }
 
 pub struct SkillRepositoryImpl {
@@ -249,10 +252,12 @@ impl SkillRepository for SkillRepositoryImpl {
     }
 
     async fn find_versions(&self, skill_id: i32) -> Result<Vec<skill_versions::Model>, DbErr> {
-        SkillVersions::find()
+        let mut versions = SkillVersions::find()
             .filter(skill_versions::Column::SkillId.eq(skill_id))
             .all(self.db.as_ref())
-            .await
+            .await?;
+        sort_versions_desc(&mut versions, |v| v.version.as_str());
+        Ok(versions)
     }
 
     async fn find_version_by_name(
@@ -386,7 +391,24 @@ impl SkillRepository for SkillRepositoryImpl {
         Ok(())
     }
 
-    async fn increment_install_count(&self, skill_id: i32) -> Result<(), DbErr> {
+    async fn increment_install_count(&self, skill_id: i32, version_id: i32) -> Result<(), DbErr> {
+        let result = SkillVersions::update_many()
+            .col_expr(
+                skill_versions::Column::InstallCount,
+                Expr::col(skill_versions::Column::InstallCount).add(1),
+            )
+            .filter(skill_versions::Column::Id.eq(version_id))
+            .filter(skill_versions::Column::SkillId.eq(skill_id))
+            .exec(self.db.as_ref())
+            .await?;
+
+        if result.rows_affected == 0 {
+            return Err(DbErr::RecordNotFound(format!(
+                "skill version id {} not found for skill id {}",
+                version_id, skill_id
+            )));
+        }
+
         let result = Skills::update_many()
             .col_expr(
                 skills::Column::InstallCount,
@@ -1 +1,3 @@
+pub use crate::infra::local_storage::LocalStorage;
 pub use crate::infra::s3::*;
+pub use crate::infra::storage::ObjectStore;
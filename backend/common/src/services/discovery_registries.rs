@@ -1,4 +1,5 @@
 use crate::entities::discovery_registries;
+use crate::infra::github_http::RateLimitResource;
 use crate::repositories::discovery_registries::{
     CreateDiscoveryRegistryParams, DiscoveryRegistryRepository,
 };
@@ -21,6 +22,11 @@ pub struct DiscoveryRegistryConfig {
     pub last_run_status: Option<String>,
     pub last_run_message: Option<String>,
     pub next_run_at: Option<chrono::NaiveDateTime>,
+    pub webhook_secret: Option<String>,
+    pub rate_limit_remaining: Option<i32>,
+    pub rate_limit_reset_at: Option<chrono::NaiveDateTime>,
+    pub search_rate_limit_remaining: Option<i32>,
+    pub search_rate_limit_reset_at: Option<chrono::NaiveDateTime>,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
 }
@@ -39,6 +45,23 @@ pub trait DiscoveryRegistryService: Send + Sync {
         api_url: String,
     ) -> Result<DiscoveryRegistryConfig, DbErr>;
 
+    async fn create_gitlab_registry(
+        &self,
+        token: String,
+        queries: Vec<String>,
+        schedule_interval_seconds: i64,
+        api_url: String,
+    ) -> Result<DiscoveryRegistryConfig, DbErr>;
+
+    async fn create_registry(
+        &self,
+        platform: discovery_registries::Platform,
+        token: String,
+        queries: Vec<String>,
+        schedule_interval_seconds: i64,
+        api_url: String,
+    ) -> Result<DiscoveryRegistryConfig, DbErr>;
+
     async fn update_config(
         &self,
         id: i32,
@@ -70,6 +93,20 @@ pub trait DiscoveryRegistryService: Send + Sync {
         message: Option<String>,
         checked_at: chrono::NaiveDateTime,
     ) -> Result<(), DbErr>;
+
+    async fn update_webhook_secret(
+        &self,
+        id: i32,
+        webhook_secret: Option<String>,
+    ) -> Result<bool, DbErr>;
+
+    async fn update_rate_limit(
+        &self,
+        id: i32,
+        resource: RateLimitResource,
+        remaining: Option<i32>,
+        reset_at: Option<chrono::NaiveDateTime>,
+    ) -> Result<(), DbErr>;
 }
 
 pub struct DiscoveryRegistryServiceImpl {
@@ -105,6 +142,11 @@ impl DiscoveryRegistryServiceImpl {
             last_run_status: model.last_run_status,
             last_run_message: model.last_run_message,
             next_run_at: model.next_run_at,
+            webhook_secret: model.webhook_secret,
+            rate_limit_remaining: model.rate_limit_remaining,
+            rate_limit_reset_at: model.rate_limit_reset_at,
+            search_rate_limit_remaining: model.search_rate_limit_remaining,
+            search_rate_limit_reset_at: model.search_rate_limit_reset_at,
             created_at: model.created_at,
             updated_at: model.updated_at,
         })
@@ -140,6 +182,41 @@ impl DiscoveryRegistryService for DiscoveryRegistryServiceImpl {
         queries: Vec<String>,
         schedule_interval_seconds: i64,
         api_url: String,
+    ) -> Result<DiscoveryRegistryConfig, DbErr> {
+        self.create_registry(
+            discovery_registries::Platform::Github,
+            token,
+            queries,
+            schedule_interval_seconds,
+            api_url,
+        )
+        .await
+    }
+
+    async fn create_gitlab_registry(
+        &self,
+        token: String,
+        queries: Vec<String>,
+        schedule_interval_seconds: i64,
+        api_url: String,
+    ) -> Result<DiscoveryRegistryConfig, DbErr> {
+        self.create_registry(
+            discovery_registries::Platform::Gitlab,
+            token,
+            queries,
+            schedule_interval_seconds,
+            api_url,
+        )
+        .await
+    }
+
+    async fn create_registry(
+        &self,
+        platform: discovery_registries::Platform,
+        token: String,
+        queries: Vec<String>,
+        schedule_interval_seconds: i64,
+        api_url: String,
     ) -> Result<DiscoveryRegistryConfig, DbErr> {
         let queries_json = Self::serialize_queries(&queries)?;
         let now = chrono::Utc::now().naive_utc();
@@ -148,7 +225,7 @@ impl DiscoveryRegistryService for DiscoveryRegistryServiceImpl {
         let model = self
             .repo
             .create(CreateDiscoveryRegistryParams {
-                platform: discovery_registries::Platform::Github,
+                platform,
                 token,
                 api_url,
                 queries_json,
@@ -232,4 +309,26 @@ impl DiscoveryRegistryService for DiscoveryRegistryServiceImpl {
             )
             .await
     }
+
+    async fn update_webhook_secret(
+        &self,
+        id: i32,
+        webhook_secret: Option<String>,
+    ) -> Result<bool, DbErr> {
+        self.repo
+            .update_webhook_secret(id, webhook_secret, chrono::Utc::now().naive_utc())
+            .await
+    }
+
+    async fn update_rate_limit(
+        &self,
+        id: i32,
+        resource: RateLimitResource,
+        remaining: Option<i32>,
+        reset_at: Option<chrono::NaiveDateTime>,
+    ) -> Result<(), DbErr> {
+        self.repo
+            .update_rate_limit(id, resource, remaining, reset_at)
+            .await
+    }
 }
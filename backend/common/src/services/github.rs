@@ -4,6 +4,7 @@ use crate::github::{GithubClient, GithubRepo};
 pub trait GithubService: Send + Sync {
     async fn search_repositories(&self, query: &str) -> anyhow::Result<Vec<GithubRepo>>;
     async fn search_code(&self, query: &str) -> anyhow::Result<Vec<GithubRepo>>;
+    async fn get_repository(&self, owner: &str, repo: &str) -> anyhow::Result<GithubRepo>;
     async fn download_zipball(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<u8>>;
 }
 
@@ -17,6 +18,10 @@ impl GithubService for GithubClient {
         self.search_code(query).await
     }
 
+    async fn get_repository(&self, owner: &str, repo: &str) -> anyhow::Result<GithubRepo> {
+        self.get_repository(owner, repo).await
+    }
+
     async fn download_zipball(&self, owner: &str, repo: &str) -> anyhow::Result<Vec<u8>> {
         self.download_zipball(owner, repo).await
     }
@@ -36,6 +41,7 @@ mod tests {
             .await
             .is_err());
         assert!(service.search_code("path:SKILL.md").await.is_err());
+        assert!(service.get_repository("acme", "repo").await.is_err());
         assert!(service.download_zipball("acme", "repo").await.is_err());
     }
 }
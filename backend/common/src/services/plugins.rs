@@ -1,7 +1,14 @@
+use super::skills::{
+    diff_versions, package_file_content, package_files, resolve_requested_version, DiffSide,
+    DownloadSkillResult, PackageArtifact, PackageFileContent, SourceProvenance, VersionDiff,
+};
 use super::ServiceError;
+use crate::domain::manifest::FileEntry;
+use crate::entities::{plugin_versions, plugins};
 use crate::repositories::plugins::PluginRepository;
 use crate::repositories::registry::RegistryRepository;
 use crate::repositories::skills::SkillRepository;
+use crate::s3::ObjectStore;
 use async_trait::async_trait;
 use serde::Serialize;
 use std::collections::BTreeMap;
@@ -12,6 +19,7 @@ pub struct PluginListItemDto {
     pub name: String,
     pub description: Option<String>,
     pub latest_version: Option<String>,
+    pub install_count: i32,
     pub strict: bool,
     pub source: Option<serde_json::Value>,
 }
@@ -41,8 +49,10 @@ pub enum SkillOriginDto {
 pub struct PluginDetail {
     pub plugin: serde_json::Value,
     pub version: serde_json::Value,
+    pub versions: Vec<serde_json::Value>,
     pub components: BTreeMap<String, Vec<serde_json::Value>>,
     pub registry: serde_json::Value,
+    pub source: Option<SourceProvenance>,
 }
 
 #[derive(Serialize)]
@@ -86,12 +96,64 @@ pub trait PluginService: Send + Sync {
         kind: &str,
         component_name: &str,
     ) -> Result<PluginComponentDetail, ServiceError>;
+
+    /// Presigns the packaged plugin zip. `version` accepts the same selectors
+    /// as skill downloads; `None` downloads the latest version.
+    async fn download_plugin(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        plugin_name: &str,
+        version: Option<&str>,
+    ) -> Result<DownloadSkillResult, ServiceError>;
+
+    async fn resolve_plugin_package(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        plugin_name: &str,
+        version: Option<&str>,
+    ) -> Result<PackageArtifact, ServiceError>;
+
+    async fn record_plugin_install(&self, artifact: &PackageArtifact) -> Result<(), ServiceError>;
+
+    async fn list_plugin_files(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        plugin_name: &str,
+        version: &str,
+    ) -> Result<Vec<FileEntry>, ServiceError>;
+
+    async fn get_plugin_file(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        plugin_name: &str,
+        version: &str,
+        path: &str,
+    ) -> Result<PackageFileContent, ServiceError>;
+
+    async fn diff_plugin_versions(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        plugin_name: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<VersionDiff, ServiceError>;
 }
 
 pub struct PluginServiceImpl {
     plugin_repo: Arc<dyn PluginRepository>,
     registry_repo: Arc<dyn RegistryRepository>,
     skill_repo: Arc<dyn SkillRepository>,
+    s3_service: Arc<ObjectStore>,
 }
 
 impl PluginServiceImpl {
@@ -99,13 +161,82 @@ impl PluginServiceImpl {
         plugin_repo: Arc<dyn PluginRepository>,
         registry_repo: Arc<dyn RegistryRepository>,
         skill_repo: Arc<dyn SkillRepository>,
+        s3_service: Arc<ObjectStore>,
     ) -> Self {
         Self {
             plugin_repo,
             registry_repo,
             skill_repo,
+            s3_service,
         }
     }
+
+    async fn find_plugin_version(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        plugin_name: &str,
+        version: Option<&str>,
+    ) -> Result<(plugins::Model, plugin_versions::Model), ServiceError> {
+        let registry = self
+            .registry_repo
+            .find_by_host(host, org, repo)
+            .await?
+            .ok_or_else(|| ServiceError::new(404, "Repository not found"))?;
+
+        let plugin = self
+            .plugin_repo
+            .find_by_registry_name(registry.id, plugin_name)
+            .await?
+            .ok_or_else(|| ServiceError::new(404, "Plugin not found"))?;
+
+        let plugin_version = self.resolve_plugin_version(&plugin, version).await?;
+        Ok((plugin, plugin_version))
+    }
+
+    // Plugins have no frontmatter; their resolved plugin.json fields play that role.
+    fn diff_side(version: &plugin_versions::Model) -> DiffSide<'_> {
+        let mut metadata = version
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get("resolved_manifest"))
+            .and_then(|m| m.as_object().cloned())
+            .unwrap_or_default();
+        if let Some(description) = version.description.as_ref() {
+            metadata.insert(
+                "description".to_string(),
+                serde_json::Value::String(description.clone()),
+            );
+        }
+        DiffSide {
+            version: &version.version,
+            s3_key: version.s3_key.as_deref(),
+            file_hash: version.file_hash.as_deref(),
+            metadata: serde_json::Value::Object(metadata),
+        }
+    }
+
+    /// Resolves a requested version to a concrete `plugin_versions` row.
+    async fn resolve_plugin_version(
+        &self,
+        plugin: &plugins::Model,
+        requested: Option<&str>,
+    ) -> Result<plugin_versions::Model, ServiceError> {
+        resolve_requested_version(
+            requested,
+            plugin.latest_version.as_deref(),
+            plugin.head_version.as_deref(),
+            "Plugin version not found",
+            |version| {
+                self.plugin_repo
+                    .find_version_by_plugin_and_version(plugin.id, version)
+            },
+            || self.plugin_repo.find_versions(plugin.id),
+            |v| v.version.as_str(),
+        )
+        .await
+    }
 }
 
 #[async_trait::async_trait]
@@ -130,6 +261,7 @@ impl PluginService for PluginServiceImpl {
                 name: p.name,
                 description: p.description,
                 latest_version: p.latest_version,
+                install_count: p.install_count,
                 strict: p.strict != 0,
                 source: p.source,
             })
@@ -169,6 +301,7 @@ impl PluginService for PluginServiceImpl {
             .ok_or_else(|| ServiceError::new(404, "Plugin version not found"))?;
 
         let components = self.plugin_repo.find_components(plugin_version.id).await?;
+        let versions = self.plugin_repo.find_versions(plugin.id).await?;
 
         let mut grouped: BTreeMap<String, Vec<serde_json::Value>> = BTreeMap::new();
         for c in components {
@@ -182,14 +315,21 @@ impl PluginService for PluginServiceImpl {
                 }));
         }
 
+        let source = SourceProvenance::from_plugin_version(&plugin_version);
         Ok(PluginDetail {
             plugin: serde_json::to_value(&plugin)
                 .map_err(|e| ServiceError::new(500, format!("Serialization error: {}", e)))?,
             version: serde_json::to_value(&plugin_version)
                 .map_err(|e| ServiceError::new(500, format!("Serialization error: {}", e)))?,
+            versions: versions
+                .into_iter()
+                .map(|v| serde_json::to_value(&v))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| ServiceError::new(500, format!("Serialization error: {}", e)))?,
             components: grouped,
             registry: serde_json::to_value(&registry)
                 .map_err(|e| ServiceError::new(500, format!("Serialization error: {}", e)))?,
+            source,
         })
     }
 
@@ -328,4 +468,139 @@ impl PluginService for PluginServiceImpl {
                 .map_err(|e| ServiceError::new(500, format!("Serialization error: {}", e)))?,
         })
     }
+
+    async fn download_plugin(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        plugin_name: &str,
+        version: Option<&str>,
+    ) -> Result<DownloadSkillResult, ServiceError> {
+        let artifact = self
+            .resolve_plugin_package(host, org, repo, plugin_name, version)
+            .await?;
+
+        let expires_in = std::time::Duration::from_secs(15 * 60);
+        let download_url = self
+            .s3_service
+            .get_presigned_url(&artifact.s3_key, expires_in)
+            .await
+            .map_err(|e| {
+                ServiceError::new(500, format!("Failed to generate download URL: {}", e))
+            })?;
+
+        self.record_plugin_install(&artifact).await?;
+
+        Ok(DownloadSkillResult {
+            download_url,
+            expires_at: chrono::Utc::now() + chrono::Duration::minutes(15),
+            md5: artifact.md5,
+            sha256: artifact.sha256,
+            version: artifact.version,
+            file_size: artifact.file_size,
+            signature: artifact.signature,
+            signature_key_id: artifact.signature_key_id,
+        })
+    }
+
+    async fn resolve_plugin_package(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        plugin_name: &str,
+        version: Option<&str>,
+    ) -> Result<PackageArtifact, ServiceError> {
+        let (plugin, plugin_version) = self
+            .find_plugin_version(host, org, repo, plugin_name, version)
+            .await?;
+
+        let s3_key = plugin_version
+            .s3_key
+            .ok_or_else(|| ServiceError::new(404, "No download artifact available"))?;
+
+        Ok(PackageArtifact {
+            owner_id: plugin.id,
+            version_id: plugin_version.id,
+            s3_key,
+            file_name: format!("{}-{}.zip", plugin.name, plugin_version.version),
+            version: plugin_version.version,
+            md5: plugin_version.file_hash,
+            sha256: plugin_version.sha256,
+            file_size: plugin_version.file_size,
+            signature: plugin_version.signature,
+            signature_key_id: plugin_version.signature_key_id,
+        })
+    }
+
+    async fn record_plugin_install(&self, artifact: &PackageArtifact) -> Result<(), ServiceError> {
+        self.plugin_repo
+            .increment_install_count(artifact.owner_id, artifact.version_id)
+            .await
+            .map_err(|e| {
+                ServiceError::new(500, format!("Failed to increment install count: {}", e))
+            })
+    }
+
+    async fn list_plugin_files(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        plugin_name: &str,
+        version: &str,
+    ) -> Result<Vec<FileEntry>, ServiceError> {
+        let (_, plugin_version) = self
+            .find_plugin_version(host, org, repo, plugin_name, Some(version))
+            .await?;
+        package_files(
+            &self.s3_service,
+            plugin_version.file_manifest.as_ref(),
+            plugin_version.s3_key.as_deref(),
+        )
+        .await
+    }
+
+    async fn get_plugin_file(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        plugin_name: &str,
+        version: &str,
+        path: &str,
+    ) -> Result<PackageFileContent, ServiceError> {
+        let (_, plugin_version) = self
+            .find_plugin_version(host, org, repo, plugin_name, Some(version))
+            .await?;
+        package_file_content(
+            &self.s3_service,
+            plugin_version.file_manifest.as_ref(),
+            plugin_version.s3_key.as_deref(),
+            path,
+        )
+        .await
+    }
+
+    async fn diff_plugin_versions(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        plugin_name: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<VersionDiff, ServiceError> {
+        let (plugin, from_version) = self
+            .find_plugin_version(host, org, repo, plugin_name, Some(from))
+            .await?;
+        let to_version = self.resolve_plugin_version(&plugin, Some(to)).await?;
+        diff_versions(
+            &self.s3_service,
+            Self::diff_side(&from_version),
+            Self::diff_side(&to_version),
+        )
+        .await
+    }
 }
@@ -1,9 +1,17 @@
+use crate::domain::repo_url::parse_repo_url;
 use crate::entities::{blacklist, skill_registry};
 use crate::repositories::registry::RegistryRepository;
+use crate::services::ServiceError;
 use async_trait::async_trait;
 use sea_orm::DbErr;
 use std::sync::Arc;
 
+#[derive(Debug, Clone)]
+pub struct SubmittedRepository {
+    pub repo: skill_registry::Model,
+    pub created: bool,
+}
+
 #[async_trait]
 pub trait RegistryService: Send + Sync {
     async fn find_by_owner_repo(
@@ -21,6 +29,12 @@ pub trait RegistryService: Send + Sync {
 
     async fn find_by_id(&self, id: i32) -> Result<Option<skill_registry::Model>, DbErr>;
 
+    async fn submit_repository(
+        &self,
+        url: &str,
+        platform: Option<skill_registry::Platform>,
+    ) -> Result<SubmittedRepository, ServiceError>;
+
     async fn find_all_pending(&self, expiry_date: chrono::NaiveDateTime)
         -> Result<Vec<i32>, DbErr>;
 
@@ -56,6 +70,18 @@ impl RegistryServiceImpl {
     pub fn new(repo: Arc<dyn RegistryRepository>) -> Self {
         Self { repo }
     }
+
+    fn infer_platform(host: &str) -> Option<skill_registry::Platform> {
+        if host == "github.com" || host.contains("github") {
+            Some(skill_registry::Platform::Github)
+        } else if host.contains("gitlab") {
+            Some(skill_registry::Platform::Gitlab)
+        } else if host == "codeberg.org" || host.contains("gitea") || host.contains("forgejo") {
+            Some(skill_registry::Platform::Gitea)
+        } else {
+            None
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -81,6 +107,57 @@ impl RegistryService for RegistryServiceImpl {
         self.repo.find_by_id(id).await
     }
 
+    async fn submit_repository(
+        &self,
+        url: &str,
+        platform: Option<skill_registry::Platform>,
+    ) -> Result<SubmittedRepository, ServiceError> {
+        let locator = parse_repo_url(url)
+            .ok_or_else(|| ServiceError::new(400, "url is not a recognizable repository url"))?;
+        let platform = platform
+            .or_else(|| Self::infer_platform(&locator.host))
+            .ok_or_else(|| {
+                ServiceError::new(
+                    400,
+                    format!("cannot infer provider for host '{}'", locator.host),
+                )
+            })?;
+
+        let canonical_url = locator.canonical_url();
+        if let Some(entry) = self.repo.find_blacklist_by_url(&canonical_url).await? {
+            return Err(ServiceError::new(
+                403,
+                format!("repository is blacklisted: {}", entry.reason),
+            ));
+        }
+
+        if let Some(repo) = self
+            .repo
+            .find_by_host(&locator.host, &locator.owner, &locator.name)
+            .await?
+        {
+            return Ok(SubmittedRepository {
+                repo,
+                created: false,
+            });
+        }
+
+        let repo = self
+            .repo
+            .create_repo(
+                platform,
+                &locator.host,
+                &locator.owner,
+                &locator.name,
+                &canonical_url,
+            )
+            .await?;
+        Ok(SubmittedRepository {
+            repo,
+            created: true,
+        })
+    }
+
     async fn find_all_pending(
         &self,
         expiry_date: chrono::NaiveDateTime,
@@ -153,6 +230,8 @@ mod tests {
             blacklisted_at: None,
             stars: 10,
             last_scanned_at: None,
+            last_commit_sha: None,
+            indexed_tags: None,
             created_at: now,
             updated_at: now,
         }
@@ -181,6 +260,17 @@ mod tests {
             Ok(Some(sample_repo()))
         }
 
+        async fn create_repo(
+            &self,
+            _platform: skill_registry::Platform,
+            _host: &str,
+            _owner: &str,
+            _name: &str,
+            _url: &str,
+        ) -> Result<skill_registry::Model, DbErr> {
+            Ok(sample_repo())
+        }
+
         async fn find_all_pending(
             &self,
             _expiry_date: chrono::NaiveDateTime,
@@ -203,7 +293,12 @@ mod tests {
             &self,
             url: &str,
         ) -> Result<Option<blacklist::Model>, DbErr> {
-            Ok(Some(blacklist::Model {
+            let blocked = [
+                "https://github.com/acme/skills",
+                "https://github.com/acme/blocked",
+            ];
+            let blocked = blocked.iter().any(|b| b.eq_ignore_ascii_case(url));
+            Ok(blocked.then(|| blacklist::Model {
                 id: 3,
                 repository_url: url.to_string(),
                 reason: "blocked".to_string(),
@@ -275,4 +370,56 @@ mod tests {
             .await
             .is_ok());
     }
+
+    #[tokio::test]
+    async fn submit_repository_rejects_invalid_and_blacklisted_urls() {
+        let service = RegistryServiceImpl::new(Arc::new(StubRegistryRepo));
+
+        let err = service
+            .submit_repository("acme/skills", None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, 400);
+
+        let err = service
+            .submit_repository("https://git.example.com/acme/skills", None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, 400);
+
+        let err = service
+            .submit_repository("https://github.com/acme/skills", None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, 403);
+        assert!(err.message.contains("blocked"));
+    }
+
+    #[tokio::test]
+    async fn submit_repository_matches_blacklist_regardless_of_case() {
+        let service = RegistryServiceImpl::new(Arc::new(StubRegistryRepo));
+
+        let err = service
+            .submit_repository("https://GitHub.com/Acme/Skills", None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, 403);
+    }
+
+    #[test]
+    fn infer_platform_matches_known_hosts() {
+        assert_eq!(
+            RegistryServiceImpl::infer_platform("github.com"),
+            Some(skill_registry::Platform::Github)
+        );
+        assert_eq!(
+            RegistryServiceImpl::infer_platform("gitlab.example.com"),
+            Some(skill_registry::Platform::Gitlab)
+        );
+        assert_eq!(
+            RegistryServiceImpl::infer_platform("codeberg.org"),
+            Some(skill_registry::Platform::Gitea)
+        );
+        assert_eq!(RegistryServiceImpl::infer_platform("git.example.com"), None);
+    }
 }
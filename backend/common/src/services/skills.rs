@@ -1,10 +1,17 @@
 use super::ServiceError;
+use crate::domain::diff::{diff_files, diff_metadata, FileChanges, MetadataChange};
+use crate::domain::manifest::{
+    is_text_mime_type, read_zip_file, unzip_files, zip_manifest, FileEntry,
+};
+use crate::domain::version::resolve_version;
 use crate::repositories::registry::RegistryRepository;
 use crate::repositories::skills::{ListSkillsParams, SkillRepository, SkillWithRegistry};
-use crate::s3::S3Service;
+use crate::s3::ObjectStore;
 use async_trait::async_trait;
+use sea_orm::DbErr;
 use serde::Serialize;
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
 
 #[derive(Serialize)]
@@ -42,6 +49,37 @@ pub struct SkillDetail {
     pub allowed_tools: Option<Vec<String>>,
     pub homepage: Option<String>,
     pub documentation_url: Option<String>,
+    pub source: Option<SourceProvenance>,
+}
+
+/// Where a published version was built from in its source repository.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct SourceProvenance {
+    pub commit_sha: String,
+    pub git_ref: Option<String>,
+    pub committed_at: Option<chrono::NaiveDateTime>,
+    pub source_path: Option<String>,
+}
+
+// Versions indexed before provenance was recorded have no commit and yield `None`.
+impl SourceProvenance {
+    pub fn from_skill_version(version: &crate::entities::skill_versions::Model) -> Option<Self> {
+        Some(Self {
+            commit_sha: version.commit_sha.clone()?,
+            git_ref: version.git_ref.clone(),
+            committed_at: version.committed_at,
+            source_path: version.source_path.clone(),
+        })
+    }
+
+    pub fn from_plugin_version(version: &crate::entities::plugin_versions::Model) -> Option<Self> {
+        Some(Self {
+            commit_sha: version.commit_sha.clone()?,
+            git_ref: version.git_ref.clone(),
+            committed_at: version.committed_at,
+            source_path: version.source_path.clone(),
+        })
+    }
 }
 
 #[derive(Serialize)]
@@ -54,8 +92,176 @@ pub struct DownloadSkillResult {
     pub download_url: String,
     pub expires_at: chrono::DateTime<chrono::Utc>,
     pub md5: Option<String>,
+    pub sha256: Option<String>,
     pub version: String,
     pub file_size: Option<i64>,
+    /// Base64 Ed25519 signature over the zip, verifiable with the key
+    /// published under `signature_key_id`.
+    pub signature: Option<String>,
+    pub signature_key_id: Option<String>,
+}
+
+/// A resolved package object, for callers that serve the bytes themselves
+/// instead of handing out a presigned URL.
+#[derive(Debug, Clone)]
+pub struct PackageArtifact {
+    pub owner_id: i32,
+    pub version_id: i32,
+    pub s3_key: String,
+    pub file_name: String,
+    pub version: String,
+    pub md5: Option<String>,
+    pub sha256: Option<String>,
+    pub file_size: Option<i64>,
+    pub signature: Option<String>,
+    pub signature_key_id: Option<String>,
+}
+
+/// Largest file the package file endpoints return inline.
+pub const MAX_FILE_CONTENT_BYTES: u64 = 512 * 1024;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct PackageFileContent {
+    #[serde(flatten)]
+    pub file: FileEntry,
+    pub content: String,
+}
+
+/// Files of a stored package. Versions synced before manifests were recorded
+/// are listed from the zip itself.
+pub(crate) async fn package_files(
+    s3: &ObjectStore,
+    manifest: Option<&serde_json::Value>,
+    s3_key: Option<&str>,
+) -> Result<Vec<FileEntry>, ServiceError> {
+    if let Some(manifest) = manifest {
+        return serde_json::from_value(manifest.clone())
+            .map_err(|e| ServiceError::new(500, format!("Invalid file manifest: {}", e)));
+    }
+    let zip = fetch_package(s3, s3_key).await?;
+    zip_manifest(&zip).map_err(|e| ServiceError::new(500, format!("Failed to read package: {}", e)))
+}
+
+pub(crate) async fn package_file_content(
+    s3: &ObjectStore,
+    manifest: Option<&serde_json::Value>,
+    s3_key: Option<&str>,
+    path: &str,
+) -> Result<PackageFileContent, ServiceError> {
+    let file = package_files(s3, manifest, s3_key)
+        .await?
+        .into_iter()
+        .find(|file| file.path == path)
+        .ok_or_else(|| ServiceError::new(404, "File not found"))?;
+    if !is_text_mime_type(&file.mime_type) {
+        return Err(ServiceError::new(415, "Binary files cannot be displayed"));
+    }
+    if file.size > MAX_FILE_CONTENT_BYTES {
+        return Err(ServiceError::new(
+            413,
+            format!(
+                "File is larger than the {} byte display limit",
+                MAX_FILE_CONTENT_BYTES
+            ),
+        ));
+    }
+
+    let zip = fetch_package(s3, s3_key).await?;
+    let content = read_zip_file(&zip, path)
+        .map_err(|e| ServiceError::new(500, format!("Failed to read package: {}", e)))?
+        .ok_or_else(|| ServiceError::new(404, "File not found"))?;
+    let content = String::from_utf8(content)
+        .map_err(|_| ServiceError::new(415, "Binary files cannot be displayed"))?;
+    Ok(PackageFileContent { file, content })
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct VersionDiff {
+    pub from: String,
+    pub to: String,
+    #[serde(flatten)]
+    pub files: FileChanges,
+    /// Frontmatter or manifest fields that changed, e.g. `allowed-tools`.
+    pub metadata: Vec<MetadataChange>,
+}
+
+/// A version being compared: its package and the metadata fields to diff.
+pub(crate) struct DiffSide<'a> {
+    pub version: &'a str,
+    pub s3_key: Option<&'a str>,
+    pub file_hash: Option<&'a str>,
+    pub metadata: serde_json::Value,
+}
+
+pub(crate) async fn diff_versions(
+    s3: &ObjectStore,
+    from: DiffSide<'_>,
+    to: DiffSide<'_>,
+) -> Result<VersionDiff, ServiceError> {
+    let same_content = from.file_hash.is_some() && from.file_hash == to.file_hash;
+    let files = if same_content {
+        FileChanges::default()
+    } else {
+        let (old, new) =
+            tokio::try_join!(fetch_package(s3, from.s3_key), fetch_package(s3, to.s3_key))?;
+        let unzip = |zip: &[u8]| {
+            unzip_files(zip)
+                .map_err(|e| ServiceError::new(500, format!("Failed to read package: {}", e)))
+        };
+        diff_files(&unzip(&old)?, &unzip(&new)?)
+    };
+    Ok(VersionDiff {
+        from: from.version.to_string(),
+        to: to.version.to_string(),
+        files,
+        metadata: diff_metadata(&from.metadata, &to.metadata),
+    })
+}
+
+/// Resolves a requested version or channel to a concrete version row.
+///
+/// `latest`, the default, is the highest release and `main` follows the
+/// default branch. Anything else is looked up exactly before it is resolved
+/// as a semver requirement against every version.
+pub(crate) async fn resolve_requested_version<'a, V, Exact, All>(
+    requested: Option<&'a str>,
+    latest: Option<&'a str>,
+    head: Option<&'a str>,
+    not_found: &str,
+    find_exact: impl FnOnce(&'a str) -> Exact,
+    find_all: impl FnOnce() -> All,
+    version_of: impl Fn(&V) -> &str,
+) -> Result<V, ServiceError>
+where
+    Exact: Future<Output = Result<Option<V>, DbErr>>,
+    All: Future<Output = Result<Vec<V>, DbErr>>,
+{
+    let requested = match requested.map(str::trim) {
+        None | Some("latest") => latest,
+        Some("main") => head.or(latest),
+        other => other,
+    }
+    .ok_or_else(|| ServiceError::new(404, not_found))?;
+
+    if let Some(version) = find_exact(requested).await? {
+        return Ok(version);
+    }
+
+    let versions = find_all().await?;
+    let resolved = resolve_version(requested, versions.iter().map(&version_of))
+        .map(ToString::to_string)
+        .ok_or_else(|| ServiceError::new(404, not_found))?;
+    versions
+        .into_iter()
+        .find(|v| version_of(v) == resolved)
+        .ok_or_else(|| ServiceError::new(404, not_found))
+}
+
+async fn fetch_package(s3: &ObjectStore, s3_key: Option<&str>) -> Result<Vec<u8>, ServiceError> {
+    let s3_key = s3_key.ok_or_else(|| ServiceError::new(404, "No download artifact available"))?;
+    s3.download_file(s3_key)
+        .await
+        .map_err(|e| ServiceError::new(500, format!("Failed to fetch package: {}", e)))
 }
 
 #[async_trait]
@@ -82,26 +288,75 @@ pub trait SkillService: Send + Sync {
         version: &str,
     ) -> Result<SkillVersionDetail, ServiceError>;
 
+    /// `version` may be an exact version, a semver requirement such as `^1.2`,
+    /// or `latest`; `None` downloads the latest version.
     async fn download_skill(
         &self,
         host: &str,
         org: &str,
         repo: &str,
         name: &str,
+        version: Option<&str>,
     ) -> Result<DownloadSkillResult, ServiceError>;
+
+    /// Resolves the package object `download_skill` would serve, without
+    /// counting an install.
+    async fn resolve_skill_package(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        name: &str,
+        version: Option<&str>,
+    ) -> Result<PackageArtifact, ServiceError>;
+
+    async fn record_skill_install(&self, artifact: &PackageArtifact) -> Result<(), ServiceError>;
+
+    async fn list_skill_files(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<Vec<FileEntry>, ServiceError>;
+
+    /// Content of one text file in the package, refused for binary files and
+    /// files over [`MAX_FILE_CONTENT_BYTES`].
+    async fn get_skill_file(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        name: &str,
+        version: &str,
+        path: &str,
+    ) -> Result<PackageFileContent, ServiceError>;
+
+    /// Files and frontmatter fields changed between two versions; both accept
+    /// the same selectors as downloads.
+    async fn diff_skill_versions(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        name: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<VersionDiff, ServiceError>;
 }
 
 pub struct SkillServiceImpl {
     skill_repo: Arc<dyn SkillRepository>,
     registry_repo: Arc<dyn RegistryRepository>,
-    s3_service: Arc<S3Service>,
+    s3_service: Arc<ObjectStore>,
 }
 
 impl SkillServiceImpl {
     pub fn new(
         skill_repo: Arc<dyn SkillRepository>,
         registry_repo: Arc<dyn RegistryRepository>,
-        s3_service: Arc<S3Service>,
+        s3_service: Arc<ObjectStore>,
     ) -> Self {
         Self {
             skill_repo,
@@ -146,6 +401,75 @@ impl SkillServiceImpl {
         Ok(latest_by_skill)
     }
 
+    async fn find_skill_version(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        name: &str,
+        version: Option<&str>,
+    ) -> Result<
+        (
+            crate::entities::skills::Model,
+            crate::entities::skill_versions::Model,
+        ),
+        ServiceError,
+    > {
+        let registry = self
+            .registry_repo
+            .find_by_host(host, org, repo)
+            .await?
+            .ok_or_else(|| ServiceError::new(404, "Repository not found"))?;
+
+        let skill = self
+            .skill_repo
+            .find_by_registry_name(registry.id, name)
+            .await?
+            .ok_or_else(|| ServiceError::new(404, "Skill not found"))?;
+
+        let skill_version = self.resolve_skill_version(&skill, version).await?;
+        Ok((skill, skill_version))
+    }
+
+    // The description lives in its own column but is a frontmatter field too.
+    fn diff_side(version: &crate::entities::skill_versions::Model) -> DiffSide<'_> {
+        let mut metadata = version
+            .metadata
+            .as_ref()
+            .and_then(|m| m.as_object().cloned())
+            .unwrap_or_default();
+        if let Some(description) = version.description.as_ref() {
+            metadata.insert(
+                "description".to_string(),
+                serde_json::Value::String(description.clone()),
+            );
+        }
+        DiffSide {
+            version: &version.version,
+            s3_key: version.s3_key.as_deref(),
+            file_hash: version.file_hash.as_deref(),
+            metadata: serde_json::Value::Object(metadata),
+        }
+    }
+
+    /// Resolves a requested version to a concrete `skill_versions` row.
+    async fn resolve_skill_version(
+        &self,
+        skill: &crate::entities::skills::Model,
+        requested: Option<&str>,
+    ) -> Result<crate::entities::skill_versions::Model, ServiceError> {
+        resolve_requested_version(
+            requested,
+            skill.latest_version.as_deref(),
+            skill.head_version.as_deref(),
+            "Version not found",
+            |version| self.skill_repo.find_version_by_name(skill.id, version),
+            || self.skill_repo.find_versions(skill.id),
+            |v| v.version.as_str(),
+        )
+        .await
+    }
+
     fn extract_host(url: &str) -> String {
         let without_scheme = url
             .strip_prefix("https://")
@@ -395,6 +719,7 @@ impl SkillService for SkillServiceImpl {
             .or_else(|| versions.first());
         let metadata = latest_version.and_then(|v| v.metadata.clone());
         let metadata_ref = metadata.as_ref();
+        let source = latest_version.and_then(SourceProvenance::from_skill_version);
 
         Ok(SkillDetail {
             skill: serde_json::to_value(&skill)
@@ -421,6 +746,7 @@ impl SkillService for SkillServiceImpl {
                 "documentation_url",
                 Some("docs"),
             ),
+            source,
         })
     }
 
@@ -444,11 +770,7 @@ impl SkillService for SkillServiceImpl {
             .await?
             .ok_or_else(|| ServiceError::new(404, "Skill not found"))?;
 
-        let skill_version = self
-            .skill_repo
-            .find_version_by_name(skill.id, version)
-            .await?
-            .ok_or_else(|| ServiceError::new(404, "Version not found"))?;
+        let skill_version = self.resolve_skill_version(&skill, Some(version)).await?;
 
         Ok(SkillVersionDetail {
             skill_version: serde_json::to_value(&skill_version)
@@ -462,59 +784,134 @@ impl SkillService for SkillServiceImpl {
         org: &str,
         repo: &str,
         name: &str,
+        version: Option<&str>,
     ) -> Result<DownloadSkillResult, ServiceError> {
-        let registry = self
-            .registry_repo
-            .find_by_host(host, org, repo)
-            .await?
-            .ok_or_else(|| ServiceError::new(404, "Repository not found"))?;
-
-        let skill = self
-            .skill_repo
-            .find_by_registry_name(registry.id, name)
-            .await?
-            .ok_or_else(|| ServiceError::new(404, "Skill not found"))?;
-
-        let version = skill
-            .latest_version
-            .clone()
-            .ok_or_else(|| ServiceError::new(404, "No version available"))?;
-
-        let skill_version = self
-            .skill_repo
-            .find_version_by_name(skill.id, &version)
-            .await?
-            .ok_or_else(|| ServiceError::new(404, "Version not found"))?;
-
-        let s3_key = skill_version
-            .s3_key
-            .as_deref()
-            .ok_or_else(|| ServiceError::new(404, "No download artifact available"))?;
+        let artifact = self
+            .resolve_skill_package(host, org, repo, name, version)
+            .await?;
 
         let expires_in = std::time::Duration::from_secs(15 * 60);
         let download_url = self
             .s3_service
-            .get_presigned_url(s3_key, expires_in)
+            .get_presigned_url(&artifact.s3_key, expires_in)
             .await
             .map_err(|e| {
                 ServiceError::new(500, format!("Failed to generate download URL: {}", e))
             })?;
 
-        self.skill_repo
-            .increment_install_count(skill.id)
-            .await
-            .map_err(|e| {
-                ServiceError::new(500, format!("Failed to increment install count: {}", e))
-            })?;
+        self.record_skill_install(&artifact).await?;
 
         Ok(DownloadSkillResult {
             download_url,
             expires_at: chrono::Utc::now() + chrono::Duration::minutes(15),
-            md5: skill_version.file_hash,
+            md5: artifact.md5,
+            sha256: artifact.sha256,
+            version: artifact.version,
+            file_size: artifact.file_size,
+            signature: artifact.signature,
+            signature_key_id: artifact.signature_key_id,
+        })
+    }
+
+    async fn resolve_skill_package(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        name: &str,
+        version: Option<&str>,
+    ) -> Result<PackageArtifact, ServiceError> {
+        let (skill, skill_version) = self
+            .find_skill_version(host, org, repo, name, version)
+            .await?;
+
+        let s3_key = skill_version
+            .s3_key
+            .ok_or_else(|| ServiceError::new(404, "No download artifact available"))?;
+
+        Ok(PackageArtifact {
+            owner_id: skill.id,
+            version_id: skill_version.id,
+            s3_key,
+            file_name: format!("{}-{}.zip", skill.name, skill_version.version),
             version: skill_version.version,
-            file_size: None,
+            md5: skill_version.file_hash,
+            sha256: skill_version.sha256,
+            file_size: skill_version.file_size,
+            signature: skill_version.signature,
+            signature_key_id: skill_version.signature_key_id,
         })
     }
+
+    async fn record_skill_install(&self, artifact: &PackageArtifact) -> Result<(), ServiceError> {
+        self.skill_repo
+            .increment_install_count(artifact.owner_id, artifact.version_id)
+            .await
+            .map_err(|e| {
+                ServiceError::new(500, format!("Failed to increment install count: {}", e))
+            })
+    }
+
+    async fn list_skill_files(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<Vec<FileEntry>, ServiceError> {
+        let (_, skill_version) = self
+            .find_skill_version(host, org, repo, name, Some(version))
+            .await?;
+        package_files(
+            &self.s3_service,
+            skill_version.file_manifest.as_ref(),
+            skill_version.s3_key.as_deref(),
+        )
+        .await
+    }
+
+    async fn get_skill_file(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        name: &str,
+        version: &str,
+        path: &str,
+    ) -> Result<PackageFileContent, ServiceError> {
+        let (_, skill_version) = self
+            .find_skill_version(host, org, repo, name, Some(version))
+            .await?;
+        package_file_content(
+            &self.s3_service,
+            skill_version.file_manifest.as_ref(),
+            skill_version.s3_key.as_deref(),
+            path,
+        )
+        .await
+    }
+
+    async fn diff_skill_versions(
+        &self,
+        host: &str,
+        org: &str,
+        repo: &str,
+        name: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<VersionDiff, ServiceError> {
+        let (skill, from_version) = self
+            .find_skill_version(host, org, repo, name, Some(from))
+            .await?;
+        let to_version = self.resolve_skill_version(&skill, Some(to)).await?;
+        diff_versions(
+            &self.s3_service,
+            Self::diff_side(&from_version),
+            Self::diff_side(&to_version),
+        )
+        .await
+    }
 }
 
 #[cfg(test)]
@@ -534,8 +931,10 @@ mod tests {
                 name: "assistant-skill".to_string(),
                 skill_registry_id: 7,
                 latest_version: latest_version.map(ToString::to_string),
+                head_version: None,
                 install_count: 3,
                 is_active: 1,
+                reused_version: None,
                 created_at: now,
                 updated_at: now,
             },
@@ -554,6 +953,8 @@ mod tests {
                 blacklisted_at: None,
                 stars: 42,
                 last_scanned_at: None,
+                last_commit_sha: None,
+                indexed_tags: None,
                 created_at: now,
                 updated_at: now,
             },
@@ -570,11 +971,68 @@ mod tests {
             s3_key: Some("skills/assistant-skill.zip".to_string()),
             oss_url: None,
             file_hash: Some("abc123".to_string()),
+            sha256: None,
+            file_size: None,
+            signature: None,
+            signature_key_id: None,
             metadata,
+            file_manifest: None,
+            commit_sha: None,
+            git_ref: None,
+            committed_at: None,
+            source_path: None,
+            install_count: 0,
             created_at: chrono::Utc::now().naive_utc(),
         }
     }
 
+    #[test]
+    fn source_provenance_requires_commit_sha() {
+        let mut version = sample_version(None);
+        assert_eq!(SourceProvenance::from_skill_version(&version), None);
+
+        version.commit_sha = Some("0123456789abcdef0123456789abcdef01234567".to_string());
+        version.git_ref = Some("refs/heads/main".to_string());
+        version.source_path = Some("skills/assistant".to_string());
+        let source = SourceProvenance::from_skill_version(&version).unwrap();
+        assert_eq!(
+            source.commit_sha,
+            "0123456789abcdef0123456789abcdef01234567"
+        );
+        assert_eq!(source.git_ref.as_deref(), Some("refs/heads/main"));
+        assert_eq!(source.committed_at, None);
+        assert_eq!(source.source_path.as_deref(), Some("skills/assistant"));
+    }
+
+    #[tokio::test]
+    async fn resolve_requested_version_handles_channels_and_ranges() {
+        let versions = ["1.0.0", "1.1.0", "2.0.0-rc.1"];
+        let resolve = |requested: Option<&'static str>, head: Option<&'static str>| {
+            resolve_requested_version(
+                requested,
+                Some("1.1.0"),
+                head,
+                "Version not found",
+                |exact| async move { Ok(versions.into_iter().find(|v| *v == exact)) },
+                || async move { Ok(versions.to_vec()) },
+                |v: &&str| *v,
+            )
+        };
+
+        assert_eq!(resolve(None, None).await.unwrap(), "1.1.0");
+        assert_eq!(resolve(Some("main"), None).await.unwrap(), "1.1.0");
+        assert_eq!(
+            resolve(Some(" main "), Some("2.0.0-rc.1")).await.unwrap(),
+            "2.0.0-rc.1"
+        );
+        assert_eq!(
+            resolve(Some("latest"), Some("2.0.0-rc.1")).await.unwrap(),
+            "1.1.0"
+        );
+        assert_eq!(resolve(Some("^1"), None).await.unwrap(), "1.1.0");
+        assert_eq!(resolve(Some("3.0.0"), None).await.unwrap_err().code, 404);
+    }
+
     #[test]
     fn extract_host_handles_various_urls() {
         assert_eq!(
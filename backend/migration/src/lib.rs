@@ -1,12 +1,46 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20260205_000001_create_all_tables;
+mod m20261016_000001_add_discovery_registry_webhook_secret;
+mod m20261016_000002_create_http_cache;
+mod m20261016_000003_add_discovery_registry_rate_limit;
+mod m20261016_000004_add_skill_registry_last_commit_sha;
+mod m20261016_000005_add_version_provenance;
+mod m20261016_000006_add_skill_registry_indexed_tags;
+mod m20261016_000007_create_skill_version_revisions;
+mod m20261016_000008_add_skill_version_install_count;
+mod m20261016_000009_add_plugin_install_counts;
+mod m20261016_000010_add_package_digests;
+mod m20261016_000011_add_package_signatures;
+mod m20261016_000012_add_version_file_manifests;
+mod m20261016_000013_add_http_cache_updated_at_index;
+mod m20261016_000014_add_discovery_registry_search_rate_limit;
+mod m20261016_000015_create_plugin_version_revisions;
+mod m20261016_000016_add_head_versions;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20260205_000001_create_all_tables::Migration)]
+        vec![
+            Box::new(m20260205_000001_create_all_tables::Migration),
+            Box::new(m20261016_000001_add_discovery_registry_webhook_secret::Migration),
+            Box::new(m20261016_000002_create_http_cache::Migration),
+            Box::new(m20261016_000003_add_discovery_registry_rate_limit::Migration),
+            Box::new(m20261016_000004_add_skill_registry_last_commit_sha::Migration),
+            Box::new(m20261016_000005_add_version_provenance::Migration),
+            Box::new(m20261016_000006_add_skill_registry_indexed_tags::Migration),
+            Box::new(m20261016_000007_create_skill_version_revisions::Migration),
+            Box::new(m20261016_000008_add_skill_version_install_count::Migration),
+            Box::new(m20261016_000009_add_plugin_install_counts::Migration),
+            Box::new(m20261016_000010_add_package_digests::Migration),
+            Box::new(m20261016_000011_add_package_signatures::Migration),
+            Box::new(m20261016_000012_add_version_file_manifests::Migration),
+            Box::new(m20261016_000013_add_http_cache_updated_at_index::Migration),
+            Box::new(m20261016_000014_add_discovery_registry_search_rate_limit::Migration),
+            Box::new(m20261016_000015_create_plugin_version_revisions::Migration),
+            Box::new(m20261016_000016_add_head_versions::Migration),
+        ]
     }
 }
@@ -0,0 +1,42 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(HttpCache::Table)
+                    .col(pk_auto(HttpCache::Id))
+                    .col(string_uniq(HttpCache::CacheKey))
+                    .col(text(HttpCache::Url))
+                    .col(string_null(HttpCache::Etag))
+                    .col(string_null(HttpCache::LastModified))
+                    .col(text(HttpCache::Body))
+                    .col(date_time(HttpCache::UpdatedAt))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(HttpCache::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum HttpCache {
+    Table,
+    Id,
+    CacheKey,
+    Url,
+    Etag,
+    LastModified,
+    Body,
+    UpdatedAt,
+}
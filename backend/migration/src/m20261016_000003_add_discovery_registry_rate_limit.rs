@@ -0,0 +1,53 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only accepts one column per ALTER TABLE statement.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DiscoveryRegistries::Table)
+                    .add_column(integer_null(DiscoveryRegistries::RateLimitRemaining))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DiscoveryRegistries::Table)
+                    .add_column(date_time_null(DiscoveryRegistries::RateLimitResetAt))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DiscoveryRegistries::Table)
+                    .drop_column(DiscoveryRegistries::RateLimitResetAt)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DiscoveryRegistries::Table)
+                    .drop_column(DiscoveryRegistries::RateLimitRemaining)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DiscoveryRegistries {
+    Table,
+    RateLimitRemaining,
+    RateLimitResetAt,
+}
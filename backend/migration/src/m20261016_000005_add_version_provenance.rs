@@ -0,0 +1,114 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+fn add_columns() -> Vec<TableAlterStatement> {
+    vec![
+        Table::alter()
+            .table(SkillVersions::Table)
+            .add_column(string_len_null(SkillVersions::CommitSha, 64))
+            .to_owned(),
+        Table::alter()
+            .table(SkillVersions::Table)
+            .add_column(string_null(SkillVersions::GitRef))
+            .to_owned(),
+        Table::alter()
+            .table(SkillVersions::Table)
+            .add_column(date_time_null(SkillVersions::CommittedAt))
+            .to_owned(),
+        Table::alter()
+            .table(SkillVersions::Table)
+            .add_column(text_null(SkillVersions::SourcePath))
+            .to_owned(),
+        Table::alter()
+            .table(PluginVersions::Table)
+            .add_column(string_len_null(PluginVersions::CommitSha, 64))
+            .to_owned(),
+        Table::alter()
+            .table(PluginVersions::Table)
+            .add_column(string_null(PluginVersions::GitRef))
+            .to_owned(),
+        Table::alter()
+            .table(PluginVersions::Table)
+            .add_column(date_time_null(PluginVersions::CommittedAt))
+            .to_owned(),
+        Table::alter()
+            .table(PluginVersions::Table)
+            .add_column(text_null(PluginVersions::SourcePath))
+            .to_owned(),
+    ]
+}
+
+fn drop_columns() -> Vec<TableAlterStatement> {
+    vec![
+        Table::alter()
+            .table(PluginVersions::Table)
+            .drop_column(PluginVersions::SourcePath)
+            .to_owned(),
+        Table::alter()
+            .table(PluginVersions::Table)
+            .drop_column(PluginVersions::CommittedAt)
+            .to_owned(),
+        Table::alter()
+            .table(PluginVersions::Table)
+            .drop_column(PluginVersions::GitRef)
+            .to_owned(),
+        Table::alter()
+            .table(PluginVersions::Table)
+            .drop_column(PluginVersions::CommitSha)
+            .to_owned(),
+        Table::alter()
+            .table(SkillVersions::Table)
+            .drop_column(SkillVersions::SourcePath)
+            .to_owned(),
+        Table::alter()
+            .table(SkillVersions::Table)
+            .drop_column(SkillVersions::CommittedAt)
+            .to_owned(),
+        Table::alter()
+            .table(SkillVersions::Table)
+            .drop_column(SkillVersions::GitRef)
+            .to_owned(),
+        Table::alter()
+            .table(SkillVersions::Table)
+            .drop_column(SkillVersions::CommitSha)
+            .to_owned(),
+    ]
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only accepts one column per ALTER TABLE statement.
+        for stmt in add_columns() {
+            manager.alter_table(stmt).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for stmt in drop_columns() {
+            manager.alter_table(stmt).await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum SkillVersions {
+    Table,
+    CommitSha,
+    GitRef,
+    CommittedAt,
+    SourcePath,
+}
+
+#[derive(DeriveIden)]
+enum PluginVersions {
+    Table,
+    CommitSha,
+    GitRef,
+    CommittedAt,
+    SourcePath,
+}
@@ -0,0 +1,100 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SkillVersionRevisions::Table)
+                    .col(pk_auto(SkillVersionRevisions::Id))
+                    .col(integer(SkillVersionRevisions::SkillVersionId))
+                    .col(integer(SkillVersionRevisions::Revision))
+                    .col(string(SkillVersionRevisions::FileHash))
+                    .col(string_null(SkillVersionRevisions::S3Key))
+                    .col(string_null(SkillVersionRevisions::OssUrl))
+                    .col(string_len_null(SkillVersionRevisions::CommitSha, 64))
+                    .col(string_null(SkillVersionRevisions::GitRef))
+                    .col(date_time_null(SkillVersionRevisions::CommittedAt))
+                    .col(date_time(SkillVersionRevisions::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_skill_version_revisions_skill_version_id")
+                            .from(
+                                SkillVersionRevisions::Table,
+                                SkillVersionRevisions::SkillVersionId,
+                            )
+                            .to(SkillVersions::Table, SkillVersions::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_skill_version_revisions_version_hash")
+                    .table(SkillVersionRevisions::Table)
+                    .col(SkillVersionRevisions::SkillVersionId)
+                    .col(SkillVersionRevisions::FileHash)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Skills::Table)
+                    .add_column(string_null(Skills::ReusedVersion))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Skills::Table)
+                    .drop_column(Skills::ReusedVersion)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(SkillVersionRevisions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SkillVersionRevisions {
+    Table,
+    Id,
+    SkillVersionId,
+    Revision,
+    FileHash,
+    S3Key,
+    OssUrl,
+    CommitSha,
+    GitRef,
+    CommittedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum SkillVersions {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Skills {
+    Table,
+    ReusedVersion,
+}
@@ -0,0 +1,97 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SkillVersions::Table)
+                    .add_column(string_len_null(SkillVersions::Sha256, 64))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SkillVersions::Table)
+                    .add_column(big_integer_null(SkillVersions::FileSize))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PluginVersions::Table)
+                    .add_column(string_len_null(PluginVersions::Sha256, 64))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PluginVersions::Table)
+                    .add_column(big_integer_null(PluginVersions::FileSize))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PluginVersions::Table)
+                    .drop_column(PluginVersions::FileSize)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PluginVersions::Table)
+                    .drop_column(PluginVersions::Sha256)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SkillVersions::Table)
+                    .drop_column(SkillVersions::FileSize)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SkillVersions::Table)
+                    .drop_column(SkillVersions::Sha256)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SkillVersions {
+    Table,
+    Sha256,
+    FileSize,
+}
+
+#[derive(DeriveIden)]
+enum PluginVersions {
+    Table,
+    Sha256,
+    FileSize,
+}
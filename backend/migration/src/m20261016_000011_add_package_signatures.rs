@@ -0,0 +1,97 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SkillVersions::Table)
+                    .add_column(string_null(SkillVersions::Signature))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SkillVersions::Table)
+                    .add_column(string_null(SkillVersions::SignatureKeyId))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PluginVersions::Table)
+                    .add_column(string_null(PluginVersions::Signature))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PluginVersions::Table)
+                    .add_column(string_null(PluginVersions::SignatureKeyId))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PluginVersions::Table)
+                    .drop_column(PluginVersions::SignatureKeyId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PluginVersions::Table)
+                    .drop_column(PluginVersions::Signature)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SkillVersions::Table)
+                    .drop_column(SkillVersions::SignatureKeyId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SkillVersions::Table)
+                    .drop_column(SkillVersions::Signature)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SkillVersions {
+    Table,
+    Signature,
+    SignatureKeyId,
+}
+
+#[derive(DeriveIden)]
+enum PluginVersions {
+    Table,
+    Signature,
+    SignatureKeyId,
+}
@@ -0,0 +1,59 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SkillVersions::Table)
+                    .add_column(json_null(SkillVersions::FileManifest))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PluginVersions::Table)
+                    .add_column(json_null(PluginVersions::FileManifest))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PluginVersions::Table)
+                    .drop_column(PluginVersions::FileManifest)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SkillVersions::Table)
+                    .drop_column(SkillVersions::FileManifest)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum SkillVersions {
+    Table,
+    FileManifest,
+}
+
+#[derive(DeriveIden)]
+enum PluginVersions {
+    Table,
+    FileManifest,
+}
@@ -0,0 +1,54 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // The existing rate limit columns hold the core budget; GitHub tracks
+        // search requests against a separate one.
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DiscoveryRegistries::Table)
+                    .add_column(integer_null(DiscoveryRegistries::SearchRateLimitRemaining))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DiscoveryRegistries::Table)
+                    .add_column(date_time_null(DiscoveryRegistries::SearchRateLimitResetAt))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DiscoveryRegistries::Table)
+                    .drop_column(DiscoveryRegistries::SearchRateLimitResetAt)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DiscoveryRegistries::Table)
+                    .drop_column(DiscoveryRegistries::SearchRateLimitRemaining)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DiscoveryRegistries {
+    Table,
+    SearchRateLimitRemaining,
+    SearchRateLimitResetAt,
+}
@@ -0,0 +1,173 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PluginVersionRevisions::Table)
+                    .col(pk_auto(PluginVersionRevisions::Id))
+                    .col(integer(PluginVersionRevisions::PluginVersionId))
+                    .col(integer(PluginVersionRevisions::Revision))
+                    .col(string(PluginVersionRevisions::FileHash))
+                    .col(string_null(PluginVersionRevisions::S3Key))
+                    .col(string_null(PluginVersionRevisions::OssUrl))
+                    .col(string_len_null(PluginVersionRevisions::Sha256, 64))
+                    .col(big_integer_null(PluginVersionRevisions::FileSize))
+                    .col(string_null(PluginVersionRevisions::Signature))
+                    .col(string_null(PluginVersionRevisions::SignatureKeyId))
+                    .col(string_len_null(PluginVersionRevisions::CommitSha, 64))
+                    .col(string_null(PluginVersionRevisions::GitRef))
+                    .col(date_time_null(PluginVersionRevisions::CommittedAt))
+                    .col(date_time(PluginVersionRevisions::CreatedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_plugin_version_revisions_plugin_version_id")
+                            .from(
+                                PluginVersionRevisions::Table,
+                                PluginVersionRevisions::PluginVersionId,
+                            )
+                            .to(PluginVersions::Table, PluginVersions::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_plugin_version_revisions_version_hash")
+                    .table(PluginVersionRevisions::Table)
+                    .col(PluginVersionRevisions::PluginVersionId)
+                    .col(PluginVersionRevisions::FileHash)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Plugins::Table)
+                    .add_column(string_null(Plugins::ReusedVersion))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SkillVersionRevisions::Table)
+                    .add_column(string_len_null(SkillVersionRevisions::Sha256, 64))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SkillVersionRevisions::Table)
+                    .add_column(big_integer_null(SkillVersionRevisions::FileSize))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SkillVersionRevisions::Table)
+                    .add_column(string_null(SkillVersionRevisions::Signature))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(SkillVersionRevisions::Table)
+                    .add_column(string_null(SkillVersionRevisions::SignatureKeyId))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [
+            SkillVersionRevisions::SignatureKeyId,
+            SkillVersionRevisions::Signature,
+            SkillVersionRevisions::FileSize,
+            SkillVersionRevisions::Sha256,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(SkillVersionRevisions::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Plugins::Table)
+                    .drop_column(Plugins::ReusedVersion)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(PluginVersionRevisions::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PluginVersionRevisions {
+    Table,
+    Id,
+    PluginVersionId,
+    Revision,
+    FileHash,
+    S3Key,
+    OssUrl,
+    Sha256,
+    FileSize,
+    Signature,
+    SignatureKeyId,
+    CommitSha,
+    GitRef,
+    CommittedAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PluginVersions {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Plugins {
+    Table,
+    ReusedVersion,
+}
+
+#[derive(DeriveIden)]
+enum SkillVersionRevisions {
+    Table,
+    Sha256,
+    FileSize,
+    Signature,
+    SignatureKeyId,
+}
@@ -30,6 +30,7 @@ async fn test_all_tables_exist() {
         "skill_registry",
         "skills",
         "skill_versions",
+        "skill_version_revisions",
         "blacklist",
         "users",
         "local_credentials",
@@ -41,8 +42,10 @@ async fn test_all_tables_exist() {
         "auth_identities",
         "plugins",
         "plugin_versions",
+        "plugin_version_revisions",
         "plugin_components",
         "task_logs",
+        "http_cache",
     ];
 
     for table in expected_tables {
@@ -81,6 +84,8 @@ async fn test_skill_registry_entity_matches_schema() {
         blacklisted_at: Set(None),
         stars: Set(0),
         last_scanned_at: Set(None),
+        last_commit_sha: Set(Some("0123456789abcdef0123456789abcdef01234567".to_string())),
+        indexed_tags: Set(Some(sea_orm::JsonValue::from(vec!["v1.0.0"]))),
         created_at: Set(chrono::Utc::now().naive_utc()),
         updated_at: Set(chrono::Utc::now().naive_utc()),
         ..Default::default()
@@ -118,6 +123,11 @@ async fn test_discovery_registries_entity_matches_schema() {
         last_health_checked_at: Set(None),
         last_run_at: Set(None),
         next_run_at: Set(Some(chrono::Utc::now().naive_utc())),
+        webhook_secret: Set(Some("whsec_test".to_string())),
+        rate_limit_remaining: Set(Some(4999)),
+        rate_limit_reset_at: Set(Some(chrono::Utc::now().naive_utc())),
+        search_rate_limit_remaining: Set(Some(29)),
+        search_rate_limit_reset_at: Set(Some(chrono::Utc::now().naive_utc())),
         created_at: Set(chrono::Utc::now().naive_utc()),
         updated_at: Set(chrono::Utc::now().naive_utc()),
         ..Default::default()
@@ -140,6 +150,8 @@ async fn test_skills_entity_matches_schema() {
     let db = setup_test_db().await;
 
     use common::entities::skill_registry;
+    use common::entities::skill_version_revisions;
+    use common::entities::skill_versions;
     use common::entities::skills;
     use sea_orm::{ActiveModelTrait, Set};
 
@@ -168,7 +180,9 @@ async fn test_skills_entity_matches_schema() {
         name: Set("test-skill".to_string()),
         skill_registry_id: Set(registry.id),
         latest_version: Set(None),
+        head_version: Set(Some("1.1.0-rc.1".to_string())),
         is_active: Set(1),
+        reused_version: Set(Some("1.0.0".to_string())),
         created_at: Set(chrono::Utc::now().naive_utc()),
         updated_at: Set(chrono::Utc::now().naive_utc()),
         ..Default::default()
@@ -184,6 +198,48 @@ async fn test_skills_entity_matches_schema() {
     let count = skills::Entity::find().count(&db).await;
     assert!(count.is_ok());
     assert_eq!(count.unwrap(), 1);
+
+    let skill = result.unwrap();
+    let version = skill_versions::ActiveModel {
+        skill_id: Set(skill.id),
+        version: Set("1.0.0".to_string()),
+        commit_sha: Set(Some("0123456789abcdef0123456789abcdef01234567".to_string())),
+        git_ref: Set(Some("refs/heads/main".to_string())),
+        committed_at: Set(Some(chrono::Utc::now().naive_utc())),
+        source_path: Set(Some("skills/test-skill".to_string())),
+        sha256: Set(Some("a".repeat(64))),
+        file_size: Set(Some(5 * 1024 * 1024 * 1024)),
+        signature: Set(Some("c".repeat(88))),
+        signature_key_id: Set(Some("0123456789abcdef".to_string())),
+        created_at: Set(chrono::Utc::now().naive_utc()),
+        ..Default::default()
+    };
+    let result = version.insert(&db).await;
+    assert!(
+        result.is_ok(),
+        "Failed to insert into skill_versions: {:?}",
+        result.err()
+    );
+    let version = result.unwrap();
+    assert_eq!(version.install_count, 0);
+
+    let revision = skill_version_revisions::ActiveModel {
+        skill_version_id: Set(version.id),
+        revision: Set(2),
+        file_hash: Set("d41d8cd98f00b204e9800998ecf8427e".to_string()),
+        sha256: Set(Some("e".repeat(64))),
+        file_size: Set(Some(1024)),
+        signature: Set(Some("f".repeat(88))),
+        signature_key_id: Set(Some("0123456789abcdef".to_string())),
+        created_at: Set(chrono::Utc::now().naive_utc()),
+        ..Default::default()
+    };
+    let result = revision.insert(&db).await;
+    assert!(
+        result.is_ok(),
+        "Failed to insert into skill_version_revisions: {:?}",
+        result.err()
+    );
 }
 
 #[tokio::test]
@@ -243,6 +299,104 @@ async fn test_blacklist_entity_matches_schema() {
     assert_eq!(count.unwrap(), 1);
 }
 
+#[tokio::test]
+async fn test_registry_lookups_ignore_case() {
+    let db = setup_test_db().await;
+
+    use common::entities::skill_registry;
+    use common::repositories::{RegistryRepository, RegistryRepositoryImpl};
+
+    let repo = RegistryRepositoryImpl::new(std::sync::Arc::new(db));
+    repo.create_repo(
+        skill_registry::Platform::Github,
+        "github.com",
+        "Acme",
+        "Skills",
+        "https://github.com/Acme/Skills",
+    )
+    .await
+    .unwrap();
+    repo.upsert_blacklist("https://github.com/Acme/Blocked", "spam")
+        .await
+        .unwrap();
+
+    assert!(repo
+        .find_by_host("github.com", "acme", "skills")
+        .await
+        .unwrap()
+        .is_some());
+    assert!(repo
+        .find_blacklist_by_url("https://github.com/acme/blocked")
+        .await
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+async fn test_http_cache_entity_matches_schema() {
+    let db = setup_test_db().await;
+
+    use common::entities::http_cache;
+    use sea_orm::{ActiveModelTrait, Set};
+
+    let entry = http_cache::ActiveModel {
+        cache_key: Set("d41d8cd98f00b204e9800998ecf8427e".to_string()),
+        url: Set("https://api.github.com/search/repositories?q=topic%3Aagent-skill".to_string()),
+        etag: Set(Some("W/\"abc\"".to_string())),
+        last_modified: Set(None),
+        body: Set("{\"total_count\":0,\"items\":[]}".to_string()),
+        updated_at: Set(chrono::Utc::now().naive_utc()),
+        ..Default::default()
+    };
+
+    let result = entry.insert(&db).await;
+    assert!(
+        result.is_ok(),
+        "Failed to insert into http_cache: {:?}",
+        result.err()
+    );
+
+    let count = http_cache::Entity::find().count(&db).await;
+    assert!(count.is_ok());
+    assert_eq!(count.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_http_cache_repository_expires_stale_entries() {
+    let db = setup_test_db().await;
+
+    use common::entities::http_cache;
+    use common::infra::github_http::{CachedResponse, HttpCache};
+    use common::repositories::HttpCacheRepositoryImpl;
+    use sea_orm::{ActiveModelTrait, Set};
+
+    let stale = http_cache::ActiveModel {
+        cache_key: Set("stale".to_string()),
+        url: Set("https://api.github.com/search/code?q=SKILL.md".to_string()),
+        etag: Set(Some("\"old\"".to_string())),
+        last_modified: Set(None),
+        body: Set("{}".to_string()),
+        updated_at: Set((chrono::Utc::now() - chrono::Duration::days(30)).naive_utc()),
+        ..Default::default()
+    };
+    stale.insert(&db).await.unwrap();
+
+    let cache = HttpCacheRepositoryImpl::new(std::sync::Arc::new(db.clone()));
+    assert_eq!(cache.get("stale").await.unwrap(), None);
+
+    let fresh = CachedResponse {
+        url: "https://api.github.com/search/repositories?q=topic%3Aagent-skill".to_string(),
+        etag: Some("\"new\"".to_string()),
+        last_modified: None,
+        body: "{\"items\":[]}".to_string(),
+    };
+    cache.put("fresh", fresh.clone()).await.unwrap();
+    assert_eq!(cache.get("fresh").await.unwrap(), Some(fresh));
+
+    let count = http_cache::Entity::find().count(&db).await.unwrap();
+    assert_eq!(count, 1, "stale entries are evicted on write");
+}
+
 #[tokio::test]
 async fn test_organizations_entity_matches_schema() {
     let db = setup_test_db().await;
@@ -322,6 +476,8 @@ async fn test_refresh_tokens_entity_matches_schema() {
 async fn test_plugins_entity_matches_schema() {
     let db = setup_test_db().await;
 
+    use common::entities::plugin_version_revisions;
+    use common::entities::plugin_versions;
     use common::entities::plugins;
     use common::entities::skill_registry;
     use sea_orm::{ActiveModelTrait, Set};
@@ -354,7 +510,9 @@ async fn test_plugins_entity_matches_schema() {
         source: Set(None),
         strict: Set(0),
         latest_version: Set(None),
+        head_version: Set(Some("1.1.0-rc.1".to_string())),
         is_active: Set(1),
+        reused_version: Set(Some("1.0.0".to_string())),
         created_at: Set(chrono::Utc::now().naive_utc()),
         updated_at: Set(chrono::Utc::now().naive_utc()),
         ..Default::default()
@@ -370,6 +528,49 @@ async fn test_plugins_entity_matches_schema() {
     let count = plugins::Entity::find().count(&db).await;
     assert!(count.is_ok());
     assert_eq!(count.unwrap(), 1);
+
+    let plugin = result.unwrap();
+    assert_eq!(plugin.install_count, 0);
+    let version = plugin_versions::ActiveModel {
+        plugin_id: Set(plugin.id),
+        version: Set("1.0.0".to_string()),
+        commit_sha: Set(Some("0123456789abcdef0123456789abcdef01234567".to_string())),
+        git_ref: Set(Some("refs/heads/main".to_string())),
+        committed_at: Set(Some(chrono::Utc::now().naive_utc())),
+        source_path: Set(Some(".".to_string())),
+        sha256: Set(Some("b".repeat(64))),
+        file_size: Set(Some(2048)),
+        signature: Set(Some("d".repeat(88))),
+        signature_key_id: Set(Some("0123456789abcdef".to_string())),
+        created_at: Set(chrono::Utc::now().naive_utc()),
+        ..Default::default()
+    };
+    let result = version.insert(&db).await;
+    assert!(
+        result.is_ok(),
+        "Failed to insert into plugin_versions: {:?}",
+        result.err()
+    );
+    let version = result.unwrap();
+    assert_eq!(version.install_count, 0);
+
+    let revision = plugin_version_revisions::ActiveModel {
+        plugin_version_id: Set(version.id),
+        revision: Set(2),
+        file_hash: Set("d41d8cd98f00b204e9800998ecf8427e".to_string()),
+        sha256: Set(Some("e".repeat(64))),
+        file_size: Set(Some(2048)),
+        signature: Set(Some("f".repeat(88))),
+        signature_key_id: Set(Some("0123456789abcdef".to_string())),
+        created_at: Set(chrono::Utc::now().naive_utc()),
+        ..Default::default()
+    };
+    let result = revision.insert(&db).await;
+    assert!(
+        result.is_ok(),
+        "Failed to insert into plugin_version_revisions: {:?}",
+        result.err()
+    );
 }
 
 #[tokio::test]
@@ -5,8 +5,18 @@ use argon2::{
 };
 use aws_sdk_s3::config::{Credentials, SharedCredentialsProvider};
 use chrono::Utc;
-use common::entities::prelude::{DiscoveryRegistries, Users};
-use common::entities::{auth_identities, discovery_registries, local_credentials, users};
+use common::build_object_store;
+use common::domain::archive::PackageDigest;
+use common::domain::artifact::{artifact_key, registry_locator, ArtifactKind};
+use common::domain::repo_url::RepoLocator;
+use common::entities::prelude::{
+    DiscoveryRegistries, PluginVersions, Plugins, SkillRegistry, SkillVersions, Skills, Users,
+};
+use common::entities::{
+    auth_identities, discovery_registries, local_credentials, plugin_versions, skill_registry,
+    skill_versions, users,
+};
+use common::s3::{LocalStorage, ObjectStore};
 use common::settings::Settings;
 use migration::{Migrator, MigratorTrait};
 use rand::rngs::OsRng;
@@ -14,6 +24,7 @@ use sea_orm::{
     ActiveModelTrait, ColumnTrait, Database, DatabaseConnection, EntityTrait, PaginatorTrait,
     QueryFilter, Set,
 };
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::time::Duration;
 use temporalio_client::{ClientOptions, WorkflowClientTrait, WorkflowOptions};
@@ -42,8 +53,14 @@ async fn main() -> anyhow::Result<()> {
     seed_admin(&db, &settings).await?;
     seed_default_discovery_registry(&db, &settings).await?;
 
-    // 3. S3 Setup
-    setup_s3(&settings).await?;
+    // 3. Storage Setup
+    let s3 = build_object_store(&settings).await;
+    match s3.as_local() {
+        Some(local) => setup_local_storage(local).await?,
+        None => setup_s3(&settings).await?,
+    }
+    rekey_artifacts(&db, &s3).await?;
+    backfill_package_digests(&db, &s3).await?;
 
     // 4. Temporal Setup
     if should_skip_temporal_setup() {
@@ -231,6 +248,282 @@ async fn seed_default_discovery_registry(
     Ok(())
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ArtifactRekey {
+    kind: ArtifactKind,
+    version_id: i32,
+    registry_id: i32,
+    git_ref: Option<String>,
+    from_key: String,
+    to_key: String,
+}
+
+// Packages used to be stored under `{skills|plugins}/{name}/{version}.zip`, so
+// same-named packages from different repos shared one object. The stored
+// content hash is enough to derive the new key without downloading anything.
+async fn plan_artifact_rekeys(db: &DatabaseConnection) -> anyhow::Result<Vec<ArtifactRekey>> {
+    let locators: HashMap<i32, RepoLocator> = SkillRegistry::find()
+        .all(db)
+        .await?
+        .iter()
+        .map(|repo| (repo.id, registry_locator(repo)))
+        .collect();
+    let skill_owners: HashMap<i32, (i32, String)> = Skills::find()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|skill| (skill.id, (skill.skill_registry_id, skill.name)))
+        .collect();
+    let plugin_owners: HashMap<i32, (i32, String)> = Plugins::find()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|plugin| (plugin.id, (plugin.skill_registry_id, plugin.name)))
+        .collect();
+
+    let rekey = |kind: ArtifactKind,
+                 version_id: i32,
+                 owner: Option<&(i32, String)>,
+                 git_ref: Option<String>,
+                 s3_key: Option<String>,
+                 file_hash: Option<String>| {
+        let (registry_id, name) = owner?;
+        let from_key = s3_key?;
+        let file_hash = file_hash?;
+        let to_key = artifact_key(kind, locators.get(registry_id)?, name, &file_hash);
+        (from_key != to_key).then(|| ArtifactRekey {
+            kind,
+            version_id,
+            registry_id: *registry_id,
+            git_ref,
+            from_key,
+            to_key,
+        })
+    };
+
+    let mut plan = Vec::new();
+    for version in SkillVersions::find()
+        .filter(skill_versions::Column::S3Key.is_not_null())
+        .all(db)
+        .await?
+    {
+        plan.extend(rekey(
+            ArtifactKind::Skill,
+            version.id,
+            skill_owners.get(&version.skill_id),
+            version.git_ref,
+            version.s3_key,
+            version.file_hash,
+        ));
+    }
+    for version in PluginVersions::find()
+        .filter(plugin_versions::Column::S3Key.is_not_null())
+        .all(db)
+        .await?
+    {
+        plan.extend(rekey(
+            ArtifactKind::Plugin,
+            version.id,
+            plugin_owners.get(&version.plugin_id),
+            version.git_ref,
+            version.s3_key,
+            version.file_hash,
+        ));
+    }
+    Ok(plan)
+}
+
+async fn rekey_artifacts(db: &DatabaseConnection, s3: &ObjectStore) -> anyhow::Result<()> {
+    let plan = plan_artifact_rekeys(db).await?;
+    if plan.is_empty() {
+        return Ok(());
+    }
+    tracing::info!("Re-keying {} package artifacts...", plan.len());
+
+    let mut sharers: HashMap<&str, usize> = HashMap::new();
+    for rekey in &plan {
+        *sharers.entry(rekey.from_key.as_str()).or_default() += 1;
+    }
+
+    for rekey in &plan {
+        // Only the last repo to upload a shared legacy key still has its bytes
+        // there, and we cannot tell which one it was.
+        if sharers[rekey.from_key.as_str()] > 1 {
+            tracing::warn!(
+                s3_key = %rekey.from_key,
+                "Legacy artifact key is shared by several repositories; scheduling a re-upload"
+            );
+            reset_artifact(db, rekey).await?;
+            continue;
+        }
+
+        match s3.copy_file(&rekey.from_key, &rekey.to_key).await {
+            Ok(oss_url) => update_artifact_key(db, rekey, oss_url).await?,
+            Err(e) => tracing::warn!(
+                s3_key = %rekey.from_key,
+                error = %e,
+                "Failed to copy artifact to its namespaced key; keeping the legacy key"
+            ),
+        }
+    }
+
+    tracing::info!("Artifact re-keying complete.");
+    Ok(())
+}
+
+async fn update_artifact_key(
+    db: &DatabaseConnection,
+    rekey: &ArtifactRekey,
+    oss_url: String,
+) -> anyhow::Result<()> {
+    match rekey.kind {
+        ArtifactKind::Skill => {
+            skill_versions::ActiveModel {
+                id: Set(rekey.version_id),
+                s3_key: Set(Some(rekey.to_key.clone())),
+                oss_url: Set(Some(oss_url)),
+                ..Default::default()
+            }
+            .update(db)
+            .await?;
+        }
+        ArtifactKind::Plugin => {
+            plugin_versions::ActiveModel {
+                id: Set(rekey.version_id),
+                s3_key: Set(Some(rekey.to_key.clone())),
+                oss_url: Set(Some(oss_url)),
+                ..Default::default()
+            }
+            .update(db)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+// Clearing the hash defeats the unchanged-content check and clearing the
+// registry's commit defeats the unchanged-HEAD check, so the next sync
+// re-uploads the package under its namespaced key. Versions from a release
+// tag are only rebuilt by the tag sync, which skips tags already indexed.
+async fn reset_artifact(db: &DatabaseConnection, rekey: &ArtifactRekey) -> anyhow::Result<()> {
+    match rekey.kind {
+        ArtifactKind::Skill => {
+            skill_versions::ActiveModel {
+                id: Set(rekey.version_id),
+                s3_key: Set(None),
+                oss_url: Set(None),
+                file_hash: Set(None),
+                sha256: Set(None),
+                file_size: Set(None),
+                signature: Set(None),
+                signature_key_id: Set(None),
+                ..Default::default()
+            }
+            .update(db)
+            .await?;
+        }
+        ArtifactKind::Plugin => {
+            plugin_versions::ActiveModel {
+                id: Set(rekey.version_id),
+                s3_key: Set(None),
+                oss_url: Set(None),
+                file_hash: Set(None),
+                sha256: Set(None),
+                file_size: Set(None),
+                signature: Set(None),
+                signature_key_id: Set(None),
+                ..Default::default()
+            }
+            .update(db)
+            .await?;
+        }
+    }
+    let mut registry = skill_registry::ActiveModel {
+        id: Set(rekey.registry_id),
+        last_commit_sha: Set(None),
+        ..Default::default()
+    };
+    let tag = rekey
+        .git_ref
+        .as_deref()
+        .and_then(|git_ref| git_ref.strip_prefix("refs/tags/"));
+    if let Some(tag) = tag {
+        if let Some(existing) = SkillRegistry::find_by_id(rekey.registry_id).one(db).await? {
+            let mut tags: Vec<String> = existing
+                .indexed_tags
+                .and_then(|value| serde_json::from_value(value).ok())
+                .unwrap_or_default();
+            tags.retain(|indexed| indexed != tag);
+            registry.indexed_tags = Set(Some(serde_json::json!(tags)));
+        }
+    }
+    registry.update(db).await?;
+    Ok(())
+}
+
+// Packages uploaded before digests were recorded have no SHA-256 or size, and
+// unchanged content is never re-uploaded, so compute them from the stored zip.
+async fn backfill_package_digests(db: &DatabaseConnection, s3: &ObjectStore) -> anyhow::Result<()> {
+    let skill_versions = SkillVersions::find()
+        .filter(skill_versions::Column::S3Key.is_not_null())
+        .filter(skill_versions::Column::Sha256.is_null())
+        .all(db)
+        .await?;
+    let plugin_versions = PluginVersions::find()
+        .filter(plugin_versions::Column::S3Key.is_not_null())
+        .filter(plugin_versions::Column::Sha256.is_null())
+        .all(db)
+        .await?;
+    if skill_versions.is_empty() && plugin_versions.is_empty() {
+        return Ok(());
+    }
+    tracing::info!(
+        "Backfilling digests for {} package artifacts...",
+        skill_versions.len() + plugin_versions.len()
+    );
+
+    for version in skill_versions {
+        let Some(digest) = fetch_package_digest(s3, version.s3_key.as_deref()).await else {
+            continue;
+        };
+        skill_versions::ActiveModel {
+            id: Set(version.id),
+            sha256: Set(Some(digest.sha256)),
+            file_size: Set(Some(digest.size)),
+            ..Default::default()
+        }
+        .update(db)
+        .await?;
+    }
+    for version in plugin_versions {
+        let Some(digest) = fetch_package_digest(s3, version.s3_key.as_deref()).await else {
+            continue;
+        };
+        plugin_versions::ActiveModel {
+            id: Set(version.id),
+            sha256: Set(Some(digest.sha256)),
+            file_size: Set(Some(digest.size)),
+            ..Default::default()
+        }
+        .update(db)
+        .await?;
+    }
+
+    tracing::info!("Package digest backfill complete.");
+    Ok(())
+}
+
+async fn fetch_package_digest(s3: &ObjectStore, s3_key: Option<&str>) -> Option<PackageDigest> {
+    let s3_key = s3_key?;
+    match s3.download_file(s3_key).await {
+        Ok(bytes) => Some(PackageDigest::of(&bytes)),
+        Err(e) => {
+            tracing::warn!(s3_key, error = %e, "Failed to download artifact for digest backfill");
+            None
+        }
+    }
+}
+
 async fn wait_for_db(url: &str) -> anyhow::Result<DatabaseConnection> {
     tracing::info!("Connecting to database at {}...", url);
     let mut attempt = 1;
@@ -259,6 +552,16 @@ async fn wait_for_db(url: &str) -> anyhow::Result<DatabaseConnection> {
     }
 }
 
+async fn setup_local_storage(storage: &LocalStorage) -> anyhow::Result<()> {
+    tracing::info!(
+        "Setting up local storage (Directory: {})...",
+        storage.root().display()
+    );
+    storage.create_root().await?;
+    tracing::info!("Storage directory {} ready.", storage.root().display());
+    Ok(())
+}
+
 async fn setup_s3(settings: &Settings) -> anyhow::Result<()> {
     let bucket_name = settings.s3.bucket.clone();
     let endpoint = settings.s3.endpoint.clone();
@@ -432,7 +735,9 @@ mod tests {
                 access_key_id: None,
                 secret_access_key: None,
                 force_path_style: false,
+                download_mode: common::settings::DownloadMode::Presigned,
             },
+            storage: common::settings::StorageSettings::default(),
             github: common::settings::GithubSettings {
                 search_keywords: "topic:agent-skill".to_string(),
                 token: Some("ghp_token".to_string()),
@@ -440,12 +745,16 @@ mod tests {
             },
             worker: common::settings::WorkerSettings {
                 scan_interval_seconds: 3600,
+                tag_patterns: common::settings::default_tag_patterns(),
+                gc_grace_period_hours: common::settings::default_gc_grace_period_hours(),
+                ingest_limits: common::settings::IngestLimits::default(),
             },
             temporal: common::settings::TemporalSettings {
                 server_url: "http://localhost:7233".to_string(),
                 task_queue: "queue".to_string(),
             },
             auth: common::settings::AuthSettings::default(),
+            signing: common::settings::SigningSettings::default(),
             debug: true,
         }
     }
@@ -456,6 +765,145 @@ mod tests {
         db
     }
 
+    async fn insert_legacy_skill(db: &DatabaseConnection, repo: &str) -> skill_versions::Model {
+        let now = Utc::now().naive_utc();
+        let registry = skill_registry::ActiveModel {
+            platform: Set(skill_registry::Platform::Github),
+            owner: Set("acme".to_string()),
+            name: Set(repo.to_string()),
+            url: Set(format!("https://github.com/acme/{repo}")),
+            status: Set("active".to_string()),
+            stars: Set(0),
+            last_commit_sha: Set(Some("abc".to_string())),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap();
+        let skill = common::entities::skills::ActiveModel {
+            name: Set("pdf".to_string()),
+            skill_registry_id: Set(registry.id),
+            latest_version: Set(Some("0.1.0".to_string())),
+            install_count: Set(0),
+            is_active: Set(1),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap();
+        skill_versions::ActiveModel {
+            skill_id: Set(skill.id),
+            version: Set("0.1.0".to_string()),
+            s3_key: Set(Some("skills/pdf/0.1.0.zip".to_string())),
+            oss_url: Set(Some(
+                "https://s3.local/test/skills/pdf/0.1.0.zip".to_string(),
+            )),
+            file_hash: Set(Some(format!("{repo}-hash"))),
+            created_at: Set(now),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn plan_artifact_rekeys_namespaces_legacy_keys_once() {
+        let db = setup_db().await;
+        let version = insert_legacy_skill(&db, "docs").await;
+
+        let plan = plan_artifact_rekeys(&db).await.unwrap();
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].version_id, version.id);
+        assert_eq!(plan[0].from_key, "skills/pdf/0.1.0.zip");
+        assert_eq!(
+            plan[0].to_key,
+            "skills/github.com/acme/docs/pdf/docs-hash.zip"
+        );
+
+        update_artifact_key(&db, &plan[0], "https://s3.local/new".to_string())
+            .await
+            .unwrap();
+        assert!(plan_artifact_rekeys(&db).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rekey_artifacts_resets_keys_shared_across_repositories() {
+        let db = setup_db().await;
+        let first = insert_legacy_skill(&db, "docs").await;
+        let second = insert_legacy_skill(&db, "tools").await;
+        let s3 = build_object_store(&test_settings()).await;
+
+        rekey_artifacts(&db, &s3).await.unwrap();
+
+        for id in [first.id, second.id] {
+            let version = SkillVersions::find_by_id(id)
+                .one(&db)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(version.s3_key, None);
+            assert_eq!(version.file_hash, None);
+        }
+        for repo in SkillRegistry::find().all(&db).await.unwrap() {
+            assert_eq!(repo.last_commit_sha, None);
+        }
+        assert!(plan_artifact_rekeys(&db).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reset_artifact_reindexes_the_release_tag() {
+        let db = setup_db().await;
+        let version = insert_legacy_skill(&db, "docs").await;
+        let registry = SkillRegistry::find().one(&db).await.unwrap().unwrap();
+        skill_registry::ActiveModel {
+            id: Set(registry.id),
+            indexed_tags: Set(Some(serde_json::json!(["v0.1.0", "v0.2.0"]))),
+            ..Default::default()
+        }
+        .update(&db)
+        .await
+        .unwrap();
+        skill_versions::ActiveModel {
+            id: Set(version.id),
+            git_ref: Set(Some("refs/tags/v0.1.0".to_string())),
+            ..Default::default()
+        }
+        .update(&db)
+        .await
+        .unwrap();
+
+        let plan = plan_artifact_rekeys(&db).await.unwrap();
+        reset_artifact(&db, &plan[0]).await.unwrap();
+
+        let registry = SkillRegistry::find_by_id(registry.id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(registry.indexed_tags, Some(serde_json::json!(["v0.2.0"])));
+        assert_eq!(registry.last_commit_sha, None);
+    }
+
+    #[tokio::test]
+    async fn setup_local_storage_creates_the_storage_directory() {
+        let root = std::env::temp_dir()
+            .join(format!("skillregistry-setup-{}", Uuid::new_v4()))
+            .join("storage");
+        let storage = LocalStorage::new(&root, None, None);
+
+        setup_local_storage(&storage).await.unwrap();
+        assert!(root.is_dir());
+        // Re-running setup keeps the existing directory.
+        setup_local_storage(&storage).await.unwrap();
+
+        std::fs::remove_dir_all(root.parent().unwrap()).unwrap();
+    }
+
     #[test]
     fn is_truthy_accepts_expected_variants() {
         for value in ["1", "true", "TRUE", "yes", "on", "  on  "] {
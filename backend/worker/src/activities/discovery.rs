@@ -1,6 +1,10 @@
-use crate::ports::GithubApi;
+use super::activity_error;
+use crate::ports::{build_source_provider, SourceProvider};
 use anyhow::Result;
+use common::domain::repo_url::normalize_host;
 use common::entities::{prelude::*, *};
+use common::infra::github_http::{rate_limit_budget, RateLimitResource, RateLimited};
+use common::repositories::{eq_ignore_case, HttpCacheRepositoryImpl};
 use sea_orm::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -12,30 +16,30 @@ pub struct DiscoveryResult {
     pub new_count: u32,
     pub updated_count: u32,
     pub touched_repo_ids: Vec<i32>,
+    #[serde(default)]
+    pub search_slices: u32,
 }
 
-fn extract_repo_host(url: &str) -> Option<String> {
-    let parsed = url::Url::parse(url).ok()?;
-    let host = parsed.host_str()?.trim();
-    if host.is_empty() {
-        None
-    } else {
-        Some(host.to_ascii_lowercase())
+fn repo_platform(platform: &discovery_registries::Platform) -> skill_registry::Platform {
+    match platform {
+        discovery_registries::Platform::Github => skill_registry::Platform::Github,
+        discovery_registries::Platform::Gitlab => skill_registry::Platform::Gitlab,
+        discovery_registries::Platform::Gitea => skill_registry::Platform::Gitea,
     }
 }
 
 pub struct DiscoveryActivities {
     db: Arc<DatabaseConnection>,
-    github: Arc<dyn GithubApi>,
+    source: Arc<dyn SourceProvider>,
     discovery_registry_service:
         Option<Arc<dyn common::services::discovery_registries::DiscoveryRegistryService>>,
 }
 
 impl DiscoveryActivities {
-    pub fn new(db: Arc<DatabaseConnection>, github: Arc<dyn GithubApi>) -> Self {
+    pub fn new(db: Arc<DatabaseConnection>, source: Arc<dyn SourceProvider>) -> Self {
         Self {
             db,
-            github,
+            source,
             discovery_registry_service: None,
         }
     }
@@ -54,9 +58,14 @@ impl DiscoveryActivities {
         &self,
         queries: Vec<String>,
     ) -> Result<DiscoveryResult, ActivityError> {
-        self.discover_repos_inner(None, queries, self.github.as_ref())
-            .await
-            .map_err(ActivityError::from)
+        self.discover_repos_inner(
+            None,
+            skill_registry::Platform::Github,
+            queries,
+            self.source.as_ref(),
+        )
+        .await
+        .map_err(activity_error)
     }
 
     pub async fn fetch_due_registry_ids(&self) -> Result<Vec<i32>, ActivityError> {
@@ -66,11 +75,31 @@ impl DiscoveryActivities {
             )));
         };
 
-        let due = service
-            .find_due(chrono::Utc::now().naive_utc())
-            .await
-            .map_err(ActivityError::from)?;
-        Ok(due.into_iter().map(|r| r.id).collect())
+        let now = chrono::Utc::now().naive_utc();
+        let due = service.find_due(now).await.map_err(ActivityError::from)?;
+        Ok(due
+            .into_iter()
+            .filter(|r| {
+                // GitHub charges discovery to its search budget; GitLab and
+                // Gitea only have one.
+                let (remaining, reset_at) = match r.platform {
+                    discovery_registries::Platform::Github => {
+                        (r.search_rate_limit_remaining, r.search_rate_limit_reset_at)
+                    }
+                    _ => (r.rate_limit_remaining, r.rate_limit_reset_at),
+                };
+                let exhausted =
+                    remaining == Some(0) && reset_at.is_some_and(|reset_at| reset_at > now);
+                if exhausted {
+                    tracing::info!(
+                        registry_id = r.id,
+                        "Deferring discovery until rate limit resets"
+                    );
+                }
+                !exhausted
+            })
+            .map(|r| r.id)
+            .collect())
     }
 
     pub async fn run_registry_discovery(
@@ -89,12 +118,43 @@ impl DiscoveryActivities {
             .map_err(ActivityError::from)?
             .ok_or_else(|| ActivityError::from(anyhow::anyhow!("registry not found")))?;
 
-        let github = crate::github::GithubClient::new(Some(config.token), config.api_url.clone())
-            .map_err(ActivityError::from)?;
+        let platform = repo_platform(&config.platform);
+        let cache = Arc::new(HttpCacheRepositoryImpl::new(self.db.clone()));
+        let source = build_source_provider(
+            &platform,
+            Some(config.token.clone()),
+            config.api_url.clone(),
+            Some(cache),
+        )
+        .map_err(ActivityError::from)?;
         let result = self
-            .discover_repos_inner(Some(registry_id), config.queries, &github)
-            .await
-            .map_err(ActivityError::from)?;
+            .discover_repos_inner(
+                Some(registry_id),
+                platform,
+                config.queries.clone(),
+                source.as_ref(),
+            )
+            .await;
+
+        for resource in [RateLimitResource::Core, RateLimitResource::Search] {
+            let Some(budget) = rate_limit_budget(&config.api_url, Some(&config.token), resource)
+            else {
+                continue;
+            };
+            let remaining = i32::try_from(budget.remaining).unwrap_or(i32::MAX);
+            if let Err(e) = service
+                .update_rate_limit(
+                    registry_id,
+                    resource,
+                    Some(remaining),
+                    Some(budget.reset_at.naive_utc()),
+                )
+                .await
+            {
+                tracing::warn!(registry_id, "Failed to record rate limit budget: {}", e);
+            }
+        }
+        let result = result.map_err(activity_error)?;
 
         let now = chrono::Utc::now().naive_utc();
         let interval = std::cmp::max(config.schedule_interval_seconds, 60);
@@ -116,14 +176,16 @@ impl DiscoveryActivities {
     async fn discover_repos_inner(
         &self,
         discovery_registry_id: Option<i32>,
+        platform: skill_registry::Platform,
         queries: Vec<String>,
-        github: &dyn GithubApi,
+        source: &dyn SourceProvider,
     ) -> Result<DiscoveryResult> {
         tracing::info!("Starting discovery task...");
 
         let mut new_count = 0;
         let mut updated_count = 0;
         let mut touched_repo_ids = Vec::new();
+        let mut search_slices = 0;
         let mut processed_repos = HashSet::new();
 
         for query in &queries {
@@ -133,22 +195,28 @@ impl DiscoveryActivities {
                 || query.contains("path:")
                 || query.contains("extension:")
             {
-                github.search_code(query).await
+                source.search_code(query).await
             } else {
                 let q = if !query.contains("sort:") {
                     format!("{} fork:false sort:updated", query)
                 } else {
                     query.to_string()
                 };
-                github.search_repositories(&q).await
+                source.search_repositories(&q).await
             };
 
             match repos_result {
-                Ok(repos) => {
-                    tracing::info!("Found {} repositories for query '{}'", repos.len(), query);
-
-                    for repo in repos {
-                        let repo_key = format!("{}/{}", repo.owner.login, repo.name);
+                Ok(results) => {
+                    tracing::info!(
+                        "Found {} repositories for query '{}' across {} search slice(s)",
+                        results.repos.len(),
+                        query,
+                        results.slices
+                    );
+                    search_slices += results.slices;
+
+                    for repo in results.repos {
+                        let repo_key = format!("{}/{}", repo.owner, repo.name);
                         if processed_repos.contains(&repo_key) {
                             continue;
                         }
@@ -156,7 +224,10 @@ impl DiscoveryActivities {
 
                         // Check blacklist
                         let blacklisted = Blacklist::find()
-                            .filter(blacklist::Column::RepositoryUrl.eq(&repo.html_url))
+                            .filter(eq_ignore_case(
+                                blacklist::Column::RepositoryUrl,
+                                &repo.html_url,
+                            ))
                             .one(&*self.db)
                             .await?;
 
@@ -169,12 +240,12 @@ impl DiscoveryActivities {
                             continue;
                         }
 
-                        let repo_host = extract_repo_host(&repo.html_url);
+                        let repo_host = normalize_host(&repo.html_url);
 
                         // Check if exists
                         let mut existing_query = SkillRegistry::find()
-                            .filter(skill_registry::Column::Name.eq(&repo.name))
-                            .filter(skill_registry::Column::Owner.eq(&repo.owner.login));
+                            .filter(eq_ignore_case(skill_registry::Column::Name, &repo.name))
+                            .filter(eq_ignore_case(skill_registry::Column::Owner, &repo.owner));
 
                         if let Some(host) = repo_host.as_deref() {
                             existing_query = existing_query.filter(
@@ -196,7 +267,7 @@ impl DiscoveryActivities {
                         if let Some(existing_model) = existing {
                             // Update existing
                             let mut active: skill_registry::ActiveModel = existing_model.into();
-                            active.stars = Set(repo.stargazers_count);
+                            active.stars = Set(repo.stars);
                             active.updated_at = Set(repo.updated_at.naive_utc());
                             active.last_scanned_at = Set(Some(chrono::Utc::now().naive_utc()));
                             active.host = Set(repo_host.clone());
@@ -210,14 +281,14 @@ impl DiscoveryActivities {
                             // Insert new
                             let new_repo = skill_registry::ActiveModel {
                                 discovery_registry_id: Set(discovery_registry_id),
-                                platform: Set(skill_registry::Platform::Github),
-                                owner: Set(repo.owner.login.clone()),
+                                platform: Set(platform.clone()),
+                                owner: Set(repo.owner.clone()),
                                 name: Set(repo.name.clone()),
                                 url: Set(repo.html_url.clone()),
                                 host: Set(repo_host.clone()),
                                 description: Set(repo.description.clone()),
                                 status: Set("active".to_string()),
-                                stars: Set(repo.stargazers_count),
+                                stars: Set(repo.stars),
                                 created_at: Set(repo.created_at.naive_utc()),
                                 updated_at: Set(repo.updated_at.naive_utc()),
                                 last_scanned_at: Set(Some(chrono::Utc::now().naive_utc())),
@@ -230,6 +301,10 @@ impl DiscoveryActivities {
                         }
                     }
                 }
+                Err(e) if e.is::<RateLimited>() => {
+                    tracing::warn!("Search deferred for query '{}': {}", query, e);
+                    return Err(e);
+                }
                 Err(e) => {
                     tracing::error!("Search failed for query '{}': {}", query, e);
                 }
@@ -237,14 +312,16 @@ impl DiscoveryActivities {
         }
 
         tracing::info!(
-            "Discovery task completed. New: {}, Updated: {}",
+            "Discovery task completed. New: {}, Updated: {}, Search slices: {}",
             new_count,
-            updated_count
+            updated_count,
+            search_slices
         );
         Ok(DiscoveryResult {
             new_count,
             updated_count,
             touched_repo_ids,
+            search_slices,
         })
     }
 }
@@ -252,8 +329,7 @@ impl DiscoveryActivities {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::github::{GithubOwner, GithubRepo};
-    use crate::ports::MockGithubApi;
+    use crate::ports::{MockSourceProvider, SourceRepo};
     use common::entities::{blacklist, skill_registry};
     use sea_orm::{
         ActiveModelTrait, ColumnTrait, Database, DatabaseBackend, EntityTrait, MockDatabase,
@@ -283,6 +359,8 @@ mod tests {
                     created_at: chrono::Utc::now().naive_utc(),
                     updated_at: chrono::Utc::now().naive_utc(),
                     last_scanned_at: Some(chrono::Utc::now().naive_utc()),
+                    last_commit_sha: None,
+                    indexed_tags: None,
                 }], // Result of the SELECT after INSERT (SeaORM usually returns the model)
             ])
             .append_exec_results(vec![MockExecResult {
@@ -291,20 +369,19 @@ mod tests {
             }])
             .into_connection();
 
-        let mut github = MockGithubApi::new();
+        let mut github = MockSourceProvider::new();
 
         github.expect_search_repositories().returning(|_| {
-            Ok(vec![GithubRepo {
+            Ok(vec![SourceRepo {
                 name: "test-repo".to_string(),
-                owner: GithubOwner {
-                    login: "test-owner".to_string(),
-                },
+                owner: "test-owner".to_string(),
                 html_url: "https://github.com/test-owner/test-repo".to_string(),
                 description: Some("test description".to_string()),
-                stargazers_count: 10,
+                stars: 10,
                 created_at: chrono::Utc::now(),
                 updated_at: chrono::Utc::now(),
-            }])
+            }]
+            .into())
         });
 
         let discovery = DiscoveryActivities::new(Arc::new(db), Arc::new(github));
@@ -319,16 +396,16 @@ mod tests {
     }
 
     #[test]
-    fn extract_repo_host_handles_invalid_and_mixed_case_urls() {
+    fn normalize_host_handles_invalid_and_mixed_case_urls() {
         assert_eq!(
-            extract_repo_host("https://GitHub.com/acme/repo"),
+            normalize_host("https://GitHub.com/acme/repo"),
             Some("github.com".to_string())
         );
         assert_eq!(
-            extract_repo_host("http://example.com/repo"),
+            normalize_host("http://example.com/repo"),
             Some("example.com".to_string())
         );
-        assert_eq!(extract_repo_host("not-a-url"), None);
+        assert_eq!(normalize_host("not-a-url"), None);
     }
 
     #[tokio::test]
@@ -363,41 +440,35 @@ mod tests {
         .insert(&db)
         .await?;
 
-        let updated_repo = GithubRepo {
+        let updated_repo = SourceRepo {
             name: "existing".to_string(),
-            owner: GithubOwner {
-                login: "acme".to_string(),
-            },
+            owner: "acme".to_string(),
             html_url: "https://github.com/acme/existing".to_string(),
             description: Some("updated".to_string()),
-            stargazers_count: 99,
+            stars: 99,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
-        let new_repo = GithubRepo {
+        let new_repo = SourceRepo {
             name: "new-skill".to_string(),
-            owner: GithubOwner {
-                login: "acme".to_string(),
-            },
+            owner: "acme".to_string(),
             html_url: "https://github.com/acme/new-skill".to_string(),
             description: Some("new".to_string()),
-            stargazers_count: 12,
+            stars: 12,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
-        let blacklisted_repo = GithubRepo {
+        let blacklisted_repo = SourceRepo {
             name: "blacklisted".to_string(),
-            owner: GithubOwner {
-                login: "acme".to_string(),
-            },
+            owner: "acme".to_string(),
             html_url: "https://github.com/acme/blacklisted".to_string(),
             description: Some("blocked".to_string()),
-            stargazers_count: 1,
+            stars: 1,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
 
-        let mut github = MockGithubApi::new();
+        let mut github = MockSourceProvider::new();
         let updated_repo_clone = updated_repo.clone();
         let new_repo_clone = new_repo.clone();
         github
@@ -405,7 +476,7 @@ mod tests {
             .times(1)
             .returning(move |query| {
                 assert_eq!(query, "topic:agent-skill fork:false sort:updated");
-                Ok(vec![updated_repo_clone.clone(), new_repo_clone.clone()])
+                Ok(vec![updated_repo_clone.clone(), new_repo_clone.clone()].into())
             });
 
         let new_repo_clone = new_repo.clone();
@@ -414,7 +485,7 @@ mod tests {
             .times(1)
             .returning(move |query| {
                 assert_eq!(query, "path:SKILL.md");
-                Ok(vec![new_repo_clone.clone(), blacklisted_repo.clone()])
+                Ok(vec![new_repo_clone.clone(), blacklisted_repo.clone()].into())
             });
 
         let discovery = DiscoveryActivities::new(Arc::new(db.clone()), Arc::new(github));
@@ -429,6 +500,7 @@ mod tests {
         assert_eq!(result.new_count, 1);
         assert_eq!(result.updated_count, 1);
         assert_eq!(result.touched_repo_ids.len(), 2);
+        assert_eq!(result.search_slices, 2);
         assert!(result.touched_repo_ids.contains(&existing.id));
 
         let repos = SkillRegistry::find()
@@ -454,7 +526,7 @@ mod tests {
         let db = Database::connect("sqlite::memory:").await?;
         migration::Migrator::up(&db, None).await?;
 
-        let mut github = MockGithubApi::new();
+        let mut github = MockSourceProvider::new();
         github
             .expect_search_repositories()
             .times(1)
@@ -471,4 +543,128 @@ mod tests {
         assert!(result.touched_repo_ids.is_empty());
         Ok(())
     }
+
+    #[tokio::test]
+    async fn discover_repos_stops_and_surfaces_rate_limits() -> Result<()> {
+        use migration::MigratorTrait;
+
+        let db = Database::connect("sqlite::memory:").await?;
+        migration::Migrator::up(&db, None).await?;
+
+        let mut github = MockSourceProvider::new();
+        github.expect_search_repositories().times(1).returning(|_| {
+            Err(RateLimited {
+                reset_at: chrono::Utc::now() + chrono::Duration::minutes(5),
+                context: "search repositories".to_string(),
+            }
+            .into())
+        });
+        github.expect_search_code().never();
+
+        let discovery = DiscoveryActivities::new(Arc::new(db), Arc::new(github));
+        let err = discovery
+            .discover_repos_inner(
+                None,
+                skill_registry::Platform::Github,
+                vec!["topic:agent-skill".to_string(), "path:SKILL.md".to_string()],
+                discovery.source.as_ref(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.is::<RateLimited>());
+
+        assert!(matches!(
+            activity_error(err),
+            ActivityError::Retryable {
+                explicit_delay: Some(_),
+                ..
+            }
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn fetch_due_registry_ids_skips_registries_with_exhausted_budget() -> Result<()> {
+        use common::services::discovery_registries::DiscoveryRegistryService;
+        use migration::MigratorTrait;
+
+        let db = Arc::new(Database::connect("sqlite::memory:").await?);
+        migration::Migrator::up(db.as_ref(), None).await?;
+        let (_, services) =
+            common::build_all(db.clone(), &common::settings::Settings::default()).await?;
+        let service = services.discovery_registry_service.clone();
+
+        let mut ids = Vec::new();
+        for _ in 0..2 {
+            let config = service
+                .create_registry(
+                    discovery_registries::Platform::Github,
+                    "token".to_string(),
+                    vec!["topic:agent-skill".to_string()],
+                    3600,
+                    "https://api.github.com".to_string(),
+                )
+                .await?;
+            ids.push(config.id);
+        }
+        let reset_at = chrono::Utc::now().naive_utc() + chrono::Duration::minutes(30);
+        service
+            .update_rate_limit(ids[0], RateLimitResource::Search, Some(0), Some(reset_at))
+            .await?;
+        service
+            .update_rate_limit(ids[1], RateLimitResource::Core, Some(0), Some(reset_at))
+            .await?;
+
+        let discovery = DiscoveryActivities::new(db, Arc::new(MockSourceProvider::new()))
+            .with_registry_service(service);
+        let due = discovery
+            .fetch_due_registry_ids()
+            .await
+            .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+        assert_eq!(due, vec![ids[1]]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn discover_repos_inner_stores_gitlab_platform() -> Result<()> {
+        use migration::MigratorTrait;
+
+        let db = Database::connect("sqlite::memory:").await?;
+        migration::Migrator::up(&db, None).await?;
+
+        let mut gitlab = MockSourceProvider::new();
+        gitlab.expect_search_repositories().times(1).returning(|_| {
+            Ok(vec![SourceRepo {
+                name: "skills".to_string(),
+                owner: "platform/ai".to_string(),
+                html_url: "https://gitlab.example.com/platform/ai/skills".to_string(),
+                description: None,
+                stars: 2,
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+            }]
+            .into())
+        });
+
+        let discovery =
+            DiscoveryActivities::new(Arc::new(db.clone()), Arc::new(MockSourceProvider::new()));
+        let result = discovery
+            .discover_repos_inner(
+                None,
+                repo_platform(&discovery_registries::Platform::Gitlab),
+                vec!["topic:agent-skill".to_string()],
+                &gitlab,
+            )
+            .await?;
+        assert_eq!(result.new_count, 1);
+
+        let stored = SkillRegistry::find_by_id(result.touched_repo_ids[0])
+            .one(&db)
+            .await?
+            .unwrap();
+        assert_eq!(stored.platform, skill_registry::Platform::Gitlab);
+        assert_eq!(stored.owner, "platform/ai");
+        assert_eq!(stored.host.as_deref(), Some("gitlab.example.com"));
+        Ok(())
+    }
 }
@@ -0,0 +1,21 @@
+use super::activity_error;
+use crate::gc::{GcInput, GcReport, GcService};
+use std::sync::Arc;
+use temporalio_sdk::ActivityError;
+
+pub struct GcActivities {
+    gc_service: Arc<GcService>,
+}
+
+impl GcActivities {
+    pub fn new(gc_service: Arc<GcService>) -> Self {
+        Self { gc_service }
+    }
+
+    pub async fn collect_garbage(&self, input: GcInput) -> Result<GcReport, ActivityError> {
+        self.gc_service
+            .collect(&input)
+            .await
+            .map_err(activity_error)
+    }
+}
@@ -1,2 +1,21 @@
 pub mod discovery;
+pub mod gc;
 pub mod sync;
+
+use common::infra::github_http::RateLimited;
+use temporalio_sdk::ActivityError;
+
+/// Maps an activity failure to a Temporal error. Rate-limited work is retried
+/// once the budget resets instead of burning attempts on the default backoff.
+pub(crate) fn activity_error(err: anyhow::Error) -> ActivityError {
+    let delay = err
+        .downcast_ref::<RateLimited>()
+        .map(|limited| limited.retry_after(chrono::Utc::now()));
+    match delay {
+        Some(delay) => ActivityError::Retryable {
+            source: err,
+            explicit_delay: Some(delay),
+        },
+        None => ActivityError::from(err),
+    }
+}
@@ -1,3 +1,4 @@
+use super::activity_error;
 pub use crate::sync::domain::{RepoSnapshotRef, SnapshotResult, SyncResult};
 use std::sync::Arc;
 use temporalio_sdk::ActivityError;
@@ -22,7 +23,7 @@ impl SyncActivities {
         self.sync_service
             .process_one(registry_id)
             .await
-            .map_err(ActivityError::from)
+            .map_err(activity_error)
     }
 
     pub async fn fetch_repo_snapshot(
@@ -32,7 +33,7 @@ impl SyncActivities {
         self.sync_service
             .fetch_repo_snapshot(registry_id)
             .await
-            .map_err(ActivityError::from)
+            .map_err(activity_error)
     }
 
     pub async fn apply_sync_from_snapshot(
@@ -44,12 +45,19 @@ impl SyncActivities {
             .await
             .map_err(ActivityError::from)
     }
+
+    pub async fn index_repo_tags(&self, registry_id: i32) -> Result<SyncResult, ActivityError> {
+        self.sync_service
+            .index_tags(registry_id)
+            .await
+            .map_err(activity_error)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ports::{MockGithubApi, MockStorage};
+    use crate::ports::{MockSourceProvider, MockStorage};
     use common::build_all;
     use common::entities::skill_registry;
     use common::settings::Settings;
@@ -71,7 +79,9 @@ mod tests {
                 access_key_id: None,
                 secret_access_key: None,
                 force_path_style: false,
+                download_mode: common::settings::DownloadMode::Presigned,
             },
+            storage: common::settings::StorageSettings::default(),
             github: common::settings::GithubSettings {
                 search_keywords: "topic:agent-skill".to_string(),
                 token: None,
@@ -79,12 +89,16 @@ mod tests {
             },
             worker: common::settings::WorkerSettings {
                 scan_interval_seconds: 3600,
+                tag_patterns: common::settings::default_tag_patterns(),
+                gc_grace_period_hours: common::settings::default_gc_grace_period_hours(),
+                ingest_limits: common::settings::IngestLimits::default(),
             },
             temporal: common::settings::TemporalSettings {
                 server_url: "http://localhost:7233".to_string(),
                 task_queue: "test".to_string(),
             },
             auth: common::settings::AuthSettings::default(),
+            signing: common::settings::SigningSettings::default(),
             debug: true,
         })
     }
@@ -119,11 +133,19 @@ mod tests {
         )]);
         let snapshot_zip = common::domain::archive::package_zip(&file_map).unwrap();
 
-        let mut github = MockGithubApi::new();
+        let mut github = MockSourceProvider::new();
+        github
+            .expect_head_commit_sha()
+            .times(2)
+            .returning(|_, _, _, _| Err(anyhow::anyhow!("ls-remote unavailable")));
         github
             .expect_clone_repository_files()
             .times(2)
-            .returning(move |_, _, _, _| Ok(file_map.clone()));
+            .returning(move |_, _, _, _, _| Ok(file_map.clone().into()));
+        github
+            .expect_list_tags()
+            .times(1)
+            .returning(|_, _, _, _| Ok(Vec::new()));
 
         let mut storage = MockStorage::new();
         storage
@@ -1,8 +1,10 @@
-use crate::activities::{discovery::DiscoveryActivities, sync::SyncActivities};
+use crate::activities::{discovery::DiscoveryActivities, gc::GcActivities, sync::SyncActivities};
+use crate::gc::GcService;
 use crate::github;
 use crate::sync::SyncService;
 use anyhow::Result;
 use common::build_all;
+use common::domain::signing::PackageSigner;
 use common::settings::Settings;
 use common::{Repositories, Services};
 use sea_orm::DatabaseConnection;
@@ -13,6 +15,7 @@ pub struct WorkerContext {
     pub repos: Repositories,
     pub services: Services,
     pub github: Arc<github::GithubClient>,
+    pub signer: Option<Arc<PackageSigner>>,
     pub settings: Arc<Settings>,
 }
 
@@ -23,6 +26,7 @@ impl std::fmt::Debug for WorkerContext {
             .field("settings", &self.settings)
             .field("s3", &"S3Service")
             .field("github", &"GithubClient")
+            .field("signer", &self.signer.as_ref().map(|s| s.key_id()))
             .finish()
     }
 }
@@ -30,6 +34,7 @@ impl std::fmt::Debug for WorkerContext {
 pub struct WorkerServices {
     pub discovery: Arc<DiscoveryActivities>,
     pub sync: Arc<SyncActivities>,
+    pub gc: Arc<GcActivities>,
 }
 
 pub async fn build_worker_context(settings: Settings) -> Result<Arc<WorkerContext>> {
@@ -37,28 +42,42 @@ pub async fn build_worker_context(settings: Settings) -> Result<Arc<WorkerContex
     let db = Arc::new(db);
     let (repos, services) = build_all(db.clone(), &settings).await?;
 
-    let github = Arc::new(github::GithubClient::new(
-        settings.github.token.clone(),
-        settings.github.api_url.clone(),
-    )?);
+    let github = Arc::new(
+        github::GithubClient::new(
+            settings.github.token.clone(),
+            settings.github.api_url.clone(),
+        )?
+        .with_cache(Some(repos.http_cache.clone())),
+    );
+
+    let signer = PackageSigner::from_settings(&settings.signing)?.map(Arc::new);
+    if let Some(signer) = signer.as_ref() {
+        tracing::info!(key_id = signer.key_id(), "Signing uploaded packages");
+    }
 
     Ok(Arc::new(WorkerContext {
         db,
         repos,
         services,
         github,
+        signer,
         settings: Arc::new(settings),
     }))
 }
 
 pub fn build_worker_services(ctx: &Arc<WorkerContext>) -> WorkerServices {
-    let sync_service = Arc::new(SyncService::new(
-        (*ctx.db).clone(),
-        ctx.services.s3.clone(),
-        ctx.github.clone(),
-        ctx.services.registry_service.clone(),
-        ctx.services.discovery_registry_service.clone(),
-    ));
+    let sync_service = Arc::new(
+        SyncService::new(
+            (*ctx.db).clone(),
+            ctx.services.s3.clone(),
+            ctx.github.clone(),
+            ctx.services.registry_service.clone(),
+            ctx.services.discovery_registry_service.clone(),
+        )
+        .with_tag_patterns(ctx.settings.worker.tag_patterns.clone())
+        .with_signer(ctx.signer.clone())
+        .with_ingest_limits(ctx.settings.worker.ingest_limits.clone()),
+    );
 
     let discovery = Arc::new(
         DiscoveryActivities::new(ctx.db.clone(), ctx.github.clone())
@@ -67,7 +86,17 @@ pub fn build_worker_services(ctx: &Arc<WorkerContext>) -> WorkerServices {
 
     let sync = Arc::new(SyncActivities::new(sync_service));
 
-    WorkerServices { discovery, sync }
+    let gc_service = Arc::new(
+        GcService::new((*ctx.db).clone(), ctx.services.s3.clone())
+            .with_grace_period_hours(ctx.settings.worker.gc_grace_period_hours),
+    );
+    let gc = Arc::new(GcActivities::new(gc_service));
+
+    WorkerServices {
+        discovery,
+        sync,
+        gc,
+    }
 }
 
 #[cfg(test)]
@@ -89,7 +118,9 @@ mod tests {
                 access_key_id: None,
                 secret_access_key: None,
                 force_path_style: false,
+                download_mode: common::settings::DownloadMode::Presigned,
             },
+            storage: common::settings::StorageSettings::default(),
             github: common::settings::GithubSettings {
                 search_keywords: "topic:agent-skill".to_string(),
                 token: None,
@@ -97,12 +128,16 @@ mod tests {
             },
             worker: common::settings::WorkerSettings {
                 scan_interval_seconds: 3600,
+                tag_patterns: common::settings::default_tag_patterns(),
+                gc_grace_period_hours: common::settings::default_gc_grace_period_hours(),
+                ingest_limits: common::settings::IngestLimits::default(),
             },
             temporal: common::settings::TemporalSettings {
                 server_url: "http://localhost:7233".to_string(),
                 task_queue: "test-q".to_string(),
             },
             auth: common::settings::AuthSettings::default(),
+            signing: common::settings::SigningSettings::default(),
             debug: true,
         }
     }
@@ -123,6 +158,7 @@ mod tests {
             repos,
             services,
             github,
+            signer: None,
             settings: Arc::new(settings),
         });
 
@@ -133,5 +169,6 @@ mod tests {
         let worker_services = build_worker_services(&ctx);
         assert!(Arc::strong_count(&worker_services.discovery) >= 1);
         assert!(Arc::strong_count(&worker_services.sync) >= 1);
+        assert!(Arc::strong_count(&worker_services.gc) >= 1);
     }
 }
@@ -1,4 +1,4 @@
-use crate::activities::{discovery::DiscoveryActivities, sync::SyncActivities};
+use crate::activities::{discovery::DiscoveryActivities, gc::GcActivities, sync::SyncActivities};
 use crate::contracts;
 use crate::workflows;
 use std::sync::Arc;
@@ -8,6 +8,7 @@ pub fn register_activities(
     worker: &mut Worker,
     discovery: Arc<DiscoveryActivities>,
     sync: Arc<SyncActivities>,
+    gc: Arc<GcActivities>,
 ) {
     let discovery_clone = Arc::clone(&discovery);
     worker.register_activity(contracts::activities::DISCOVERY, move |_ctx, queries| {
@@ -68,6 +69,23 @@ pub fn register_activities(
             async move { sync.apply_sync_from_snapshot(snapshot).await }
         },
     );
+
+    let sync_clone = Arc::clone(&sync);
+    worker.register_activity(
+        contracts::activities::INDEX_REPO_TAGS,
+        move |_ctx, registry_id| {
+            let sync = Arc::clone(&sync_clone);
+            async move { sync.index_repo_tags(registry_id).await }
+        },
+    );
+
+    worker.register_activity(
+        contracts::activities::COLLECT_GARBAGE,
+        move |_ctx, input| {
+            let gc = Arc::clone(&gc);
+            async move { gc.collect_garbage(input).await }
+        },
+    );
 }
 
 pub fn register_workflows(worker: &mut Worker) {
@@ -87,4 +105,8 @@ pub fn register_workflows(worker: &mut Worker) {
         contracts::workflows::TRIGGER_REGISTRY,
         workflows::trigger_registry_workflow::trigger_registry_workflow,
     );
+    worker.register_wf(
+        contracts::workflows::GARBAGE_COLLECTION,
+        workflows::gc_workflow::gc_workflow,
+    );
 }
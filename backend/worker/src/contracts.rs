@@ -6,6 +6,8 @@ pub mod activities {
     pub const SYNC_SINGLE_SKILL: &str = "sync_single_skill_activity";
     pub const FETCH_REPO_SNAPSHOT: &str = "fetch_repo_snapshot_activity";
     pub const APPLY_SYNC_FROM_SNAPSHOT: &str = "apply_sync_from_snapshot_activity";
+    pub const INDEX_REPO_TAGS: &str = "index_repo_tags_activity";
+    pub const COLLECT_GARBAGE: &str = "collect_garbage_activity";
 }
 
 pub mod workflows {
@@ -13,6 +15,7 @@ pub mod workflows {
     pub const SYNC_SCHEDULER: &str = "sync_scheduler_workflow";
     pub const SYNC_REPO: &str = "sync_repo_workflow";
     pub const TRIGGER_REGISTRY: &str = "trigger_registry_workflow";
+    pub const GARBAGE_COLLECTION: &str = "gc_workflow";
 }
 
 pub const WORKFLOW_BATCH_CHUNK_SIZE: usize = 5;
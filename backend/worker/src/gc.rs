@@ -0,0 +1,424 @@
+use crate::ports::Storage;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use common::domain::signing::signature_key;
+use common::entities::prelude::{
+    PluginVersionRevisions, PluginVersions, SkillVersionRevisions, SkillVersions,
+};
+use common::entities::{
+    plugin_version_revisions, plugin_versions, skill_version_revisions, skill_versions,
+};
+use common::s3::ListedObject;
+use sea_orm::{DatabaseConnection, EntityTrait, QuerySelect};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Bucket prefixes written by the worker. Objects outside them are never
+/// considered for collection.
+pub const GC_PREFIXES: &[&str] = &["skills/", "plugins/", "repo-snapshots/"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GcInput {
+    /// Report what would be deleted without deleting anything. Defaults to
+    /// `true` so an empty input never removes objects.
+    pub dry_run: bool,
+    /// Overrides `worker.gc_grace_period_hours` for this run.
+    pub grace_period_hours: Option<u64>,
+}
+
+impl Default for GcInput {
+    fn default() -> Self {
+        Self {
+            dry_run: true,
+            grace_period_hours: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GcReport {
+    pub dry_run: bool,
+    pub scanned_objects: u64,
+    pub referenced_objects: u64,
+    /// Unreferenced objects still inside the grace period.
+    pub recent_objects: u64,
+    pub reclaimable_objects: u64,
+    pub reclaimable_bytes: u64,
+    pub deleted_objects: u64,
+    pub deleted_bytes: u64,
+    pub failed_deletes: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Disposition {
+    Referenced,
+    Recent,
+    Orphaned,
+}
+
+pub struct GcService {
+    db: DatabaseConnection,
+    s3: Arc<dyn Storage>,
+    grace_period_hours: u64,
+}
+
+impl GcService {
+    pub fn new(db: DatabaseConnection, s3: Arc<dyn Storage>) -> Self {
+        Self {
+            db,
+            s3,
+            grace_period_hours: common::settings::default_gc_grace_period_hours(),
+        }
+    }
+
+    pub fn with_grace_period_hours(mut self, grace_period_hours: u64) -> Self {
+        self.grace_period_hours = grace_period_hours;
+        self
+    }
+
+    /// Deletes package objects no version row points at, and repo snapshots.
+    /// Snapshots are only read by the sync run that uploaded them, so the
+    /// grace period is what keeps the recent ones alive.
+    pub async fn collect(&self, input: &GcInput) -> Result<GcReport> {
+        let grace_period_hours = input.grace_period_hours.unwrap_or(self.grace_period_hours);
+        let cutoff = grace_cutoff(Utc::now(), grace_period_hours);
+        let referenced = self.referenced_keys().await?;
+
+        let mut report = GcReport {
+            dry_run: input.dry_run,
+            ..Default::default()
+        };
+        for prefix in GC_PREFIXES {
+            for object in self.s3.list(prefix).await? {
+                report.scanned_objects += 1;
+                match disposition(&object, &referenced, cutoff) {
+                    Disposition::Referenced => report.referenced_objects += 1,
+                    Disposition::Recent => report.recent_objects += 1,
+                    Disposition::Orphaned => {
+                        report.reclaimable_objects += 1;
+                        report.reclaimable_bytes += object.size;
+                        if input.dry_run {
+                            tracing::info!(
+                                key = %object.key,
+                                size = object.size,
+                                "Would delete orphaned object"
+                            );
+                            continue;
+                        }
+                        match self.s3.delete(&object.key).await {
+                            Ok(()) => {
+                                report.deleted_objects += 1;
+                                report.deleted_bytes += object.size;
+                            }
+                            Err(e) => {
+                                tracing::warn!(
+                                    key = %object.key,
+                                    error = %e,
+                                    "Failed to delete orphaned object"
+                                );
+                                report.failed_deletes += 1;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        tracing::info!(
+            dry_run = report.dry_run,
+            scanned = report.scanned_objects,
+            reclaimable = report.reclaimable_objects,
+            reclaimable_bytes = report.reclaimable_bytes,
+            deleted = report.deleted_objects,
+            failed = report.failed_deletes,
+            "Garbage collection finished"
+        );
+        Ok(report)
+    }
+
+    /// Package keys of every skill and plugin version and their revisions, plus
+    /// their detached signatures.
+    async fn referenced_keys(&self) -> Result<HashSet<String>> {
+        let mut keys: Vec<Option<String>> = SkillVersions::find()
+            .select_only()
+            .column(skill_versions::Column::S3Key)
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+        keys.extend(
+            SkillVersionRevisions::find()
+                .select_only()
+                .column(skill_version_revisions::Column::S3Key)
+                .into_tuple::<Option<String>>()
+                .all(&self.db)
+                .await?,
+        );
+        keys.extend(
+            PluginVersions::find()
+                .select_only()
+                .column(plugin_versions::Column::S3Key)
+                .into_tuple::<Option<String>>()
+                .all(&self.db)
+                .await?,
+        );
+        keys.extend(
+            PluginVersionRevisions::find()
+                .select_only()
+                .column(plugin_version_revisions::Column::S3Key)
+                .into_tuple::<Option<String>>()
+                .all(&self.db)
+                .await?,
+        );
+
+        let mut referenced = HashSet::new();
+        for key in keys.into_iter().flatten() {
+            referenced.insert(signature_key(&key));
+            referenced.insert(key);
+        }
+        Ok(referenced)
+    }
+}
+
+fn grace_cutoff(now: DateTime<Utc>, grace_period_hours: u64) -> DateTime<Utc> {
+    i64::try_from(grace_period_hours)
+        .ok()
+        .and_then(chrono::Duration::try_hours)
+        .and_then(|grace| now.checked_sub_signed(grace))
+        .unwrap_or(DateTime::<Utc>::MIN_UTC)
+}
+
+/// Objects without a modification time are treated as recent; their age is
+/// unknown, so they are never deleted.
+fn disposition(
+    object: &ListedObject,
+    referenced: &HashSet<String>,
+    cutoff: DateTime<Utc>,
+) -> Disposition {
+    if referenced.contains(&object.key) {
+        return Disposition::Referenced;
+    }
+    match object.last_modified {
+        Some(modified) if modified < cutoff => Disposition::Orphaned,
+        _ => Disposition::Recent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::MockStorage;
+    use common::entities::{plugins, skill_registry, skills};
+    use migration::MigratorTrait;
+    use sea_orm::{ActiveModelTrait, Database, Set};
+
+    fn object(key: &str, size: u64, age_hours: Option<i64>) -> ListedObject {
+        ListedObject {
+            key: key.to_string(),
+            size,
+            last_modified: age_hours.map(|hours| Utc::now() - chrono::Duration::hours(hours)),
+        }
+    }
+
+    async fn seed_versions(db: &DatabaseConnection) {
+        let now = Utc::now().naive_utc();
+        let registry = skill_registry::ActiveModel {
+            platform: Set(skill_registry::Platform::Github),
+            owner: Set("acme".to_string()),
+            name: Set("gc-repo".to_string()),
+            url: Set("https://github.com/acme/gc-repo".to_string()),
+            status: Set("active".to_string()),
+            stars: Set(0),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap();
+        let skill = skills::ActiveModel {
+            name: Set("demo".to_string()),
+            skill_registry_id: Set(registry.id),
+            install_count: Set(0),
+            is_active: Set(1),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap();
+        let version = skill_versions::ActiveModel {
+            skill_id: Set(skill.id),
+            version: Set("1.0.0".to_string()),
+            s3_key: Set(Some(
+                "skills/github.com/acme/gc-repo/demo/a.zip".to_string(),
+            )),
+            install_count: Set(0),
+            created_at: Set(now),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap();
+        skill_version_revisions::ActiveModel {
+            skill_version_id: Set(version.id),
+            revision: Set(2),
+            file_hash: Set("b".to_string()),
+            s3_key: Set(Some(
+                "skills/github.com/acme/gc-repo/demo/b.zip".to_string(),
+            )),
+            created_at: Set(now),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap();
+        let plugin = plugins::ActiveModel {
+            skill_registry_id: Set(registry.id),
+            name: Set("tools".to_string()),
+            strict: Set(1),
+            install_count: Set(0),
+            is_active: Set(1),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap();
+        let version = plugin_versions::ActiveModel {
+            plugin_id: Set(plugin.id),
+            version: Set("1.0.0".to_string()),
+            s3_key: Set(Some(
+                "plugins/github.com/acme/gc-repo/tools/p.zip".to_string(),
+            )),
+            install_count: Set(0),
+            created_at: Set(now),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap();
+        plugin_version_revisions::ActiveModel {
+            plugin_version_id: Set(version.id),
+            revision: Set(2),
+            file_hash: Set("q".to_string()),
+            s3_key: Set(Some(
+                "plugins/github.com/acme/gc-repo/tools/q.zip".to_string(),
+            )),
+            created_at: Set(now),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .unwrap();
+    }
+
+    fn bucket_storage() -> MockStorage {
+        let mut storage = MockStorage::new();
+        storage.expect_list().returning(|prefix| {
+            let objects = match prefix {
+                "skills/" => vec![
+                    object("skills/github.com/acme/gc-repo/demo/a.zip", 10, Some(500)),
+                    object(
+                        "skills/github.com/acme/gc-repo/demo/a.zip.sig",
+                        64,
+                        Some(500),
+                    ),
+                    object("skills/github.com/acme/gc-repo/demo/b.zip", 20, Some(500)),
+                    object("skills/github.com/acme/gc-repo/demo/old.zip", 30, Some(500)),
+                    object(
+                        "skills/github.com/acme/gc-repo/demo/old.zip.sig",
+                        64,
+                        Some(500),
+                    ),
+                    object("skills/github.com/acme/gc-repo/demo/new.zip", 40, Some(1)),
+                ],
+                "plugins/" => vec![
+                    object("plugins/github.com/acme/gc-repo/tools/p.zip", 50, Some(500)),
+                    object("plugins/github.com/acme/gc-repo/tools/q.zip", 70, Some(500)),
+                    object(
+                        "plugins/github.com/acme/gc-repo/tools/unknown.zip",
+                        60,
+                        None,
+                    ),
+                ],
+                "repo-snapshots/" => vec![
+                    object("repo-snapshots/1/old.zip", 100, Some(200)),
+                    object("repo-snapshots/1/new.zip", 200, Some(2)),
+                ],
+                other => panic!("unexpected prefix {other}"),
+            };
+            Ok(objects)
+        });
+        storage
+    }
+
+    #[tokio::test]
+    async fn dry_run_reports_reclaimable_objects_without_deleting() -> Result<()> {
+        let db = Database::connect("sqlite::memory:").await?;
+        migration::Migrator::up(&db, None).await?;
+        seed_versions(&db).await;
+
+        let gc = GcService::new(db, Arc::new(bucket_storage()));
+        let report = gc.collect(&GcInput::default()).await?;
+
+        assert_eq!(
+            report,
+            GcReport {
+                dry_run: true,
+                scanned_objects: 11,
+                referenced_objects: 5,
+                recent_objects: 3,
+                reclaimable_objects: 3,
+                reclaimable_bytes: 30 + 64 + 100,
+                ..Default::default()
+            }
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn collect_deletes_orphans_older_than_the_grace_period() -> Result<()> {
+        let db = Database::connect("sqlite::memory:").await?;
+        migration::Migrator::up(&db, None).await?;
+        seed_versions(&db).await;
+
+        let mut storage = bucket_storage();
+        storage.expect_delete().times(3).returning(|key| match key {
+            "skills/github.com/acme/gc-repo/demo/old.zip" => Ok(()),
+            "skills/github.com/acme/gc-repo/demo/old.zip.sig" => {
+                Err(anyhow::anyhow!("access denied"))
+            }
+            "repo-snapshots/1/old.zip" => Ok(()),
+            other => panic!("deleted referenced or recent object {other}"),
+        });
+
+        let gc = GcService::new(db, Arc::new(storage)).with_grace_period_hours(24);
+        let report = gc
+            .collect(&GcInput {
+                dry_run: false,
+                grace_period_hours: None,
+            })
+            .await?;
+
+        assert_eq!(report.reclaimable_objects, 3);
+        assert_eq!(report.deleted_objects, 2);
+        assert_eq!(report.deleted_bytes, 130);
+        assert_eq!(report.failed_deletes, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn gc_input_defaults_to_dry_run_and_grace_cutoff_saturates() {
+        let input: GcInput = serde_json::from_str("{}").unwrap();
+        assert!(input.dry_run);
+        assert_eq!(input.grace_period_hours, None);
+
+        let now = Utc::now();
+        assert_eq!(grace_cutoff(now, 2), now - chrono::Duration::hours(2));
+        assert_eq!(grace_cutoff(now, u64::MAX), DateTime::<Utc>::MIN_UTC);
+    }
+}
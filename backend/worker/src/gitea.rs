@@ -0,0 +1,266 @@
+use crate::git::{self, GitAuth};
+use crate::ports::{parse_search_query, SourceRepo};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use common::infra::github_http::send_request_with_retry;
+use common::settings::IngestLimits;
+use reqwest::{Client, RequestBuilder};
+use serde::Deserialize;
+use std::collections::HashSet;
+
+const PER_PAGE: usize = 50;
+const MAX_RESULTS: usize = 1000;
+
+#[derive(Deserialize, Debug)]
+pub struct GiteaSearchResponse {
+    #[serde(default)]
+    pub data: Vec<GiteaRepo>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GiteaRepo {
+    pub name: String,
+    pub html_url: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub stars_count: i32,
+    #[serde(default = "utc_now")]
+    pub created_at: DateTime<Utc>,
+    #[serde(default = "utc_now")]
+    pub updated_at: DateTime<Utc>,
+    pub owner: GiteaOwner,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GiteaOwner {
+    pub login: String,
+}
+
+fn utc_now() -> DateTime<Utc> {
+    Utc::now()
+}
+
+impl From<GiteaRepo> for SourceRepo {
+    fn from(repo: GiteaRepo) -> Self {
+        SourceRepo {
+            owner: repo.owner.login,
+            name: repo.name,
+            html_url: repo.html_url,
+            description: repo.description.filter(|d| !d.trim().is_empty()),
+            stars: repo.stars_count,
+            created_at: repo.created_at,
+            updated_at: repo.updated_at,
+        }
+    }
+}
+
+pub struct GiteaClient {
+    client: Client,
+    api_url: String,
+    token: Option<String>,
+}
+
+impl GiteaClient {
+    pub fn new(token: Option<String>, api_url: String) -> Result<Self> {
+        let token = token
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty());
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::USER_AGENT, "SkillRegistry/1.0".parse()?);
+        headers.insert(reqwest::header::ACCEPT, "application/json".parse()?);
+
+        let client = Client::builder().default_headers(headers).build()?;
+        Ok(Self {
+            client,
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token,
+        })
+    }
+
+    // Set per request so rate-limit tracking can tell tokens apart.
+    fn get(&self, url: &str) -> RequestBuilder {
+        match self.token.as_deref() {
+            Some(token) => self
+                .client
+                .get(url)
+                .header(reqwest::header::AUTHORIZATION, format!("token {}", token)),
+            None => self.client.get(url),
+        }
+    }
+
+    pub async fn search_repositories(&self, query: &str) -> Result<Vec<SourceRepo>> {
+        let parsed = parse_search_query(query);
+
+        // Gitea matches a single keyword against topics, so each topic is its own search.
+        let searches: Vec<(String, bool)> = if parsed.topics.is_empty() {
+            vec![(parsed.search.unwrap_or_default(), false)]
+        } else {
+            parsed.topics.into_iter().map(|t| (t, true)).collect()
+        };
+
+        let mut all_repos = Vec::new();
+        let mut seen = HashSet::new();
+        for (keyword, topic) in searches {
+            for repo in self.search_pages(&keyword, topic).await? {
+                if seen.insert(repo.html_url.clone()) {
+                    all_repos.push(repo);
+                }
+            }
+        }
+
+        all_repos.truncate(MAX_RESULTS);
+        Ok(all_repos)
+    }
+
+    async fn search_pages(&self, keyword: &str, topic: bool) -> Result<Vec<SourceRepo>> {
+        let url = format!("{}/repos/search", self.api_url);
+        let mut repos = Vec::new();
+        let mut page = 1;
+
+        loop {
+            tracing::debug!(
+                page,
+                keyword,
+                topic,
+                "Fetching Gitea repository search page"
+            );
+
+            let req = self.get(&url).query(&[
+                ("q", keyword.to_string()),
+                ("topic", topic.to_string()),
+                ("mode", "source".to_string()),
+                ("archived", "false".to_string()),
+                ("sort", "updated".to_string()),
+                ("order", "desc".to_string()),
+                ("limit", PER_PAGE.to_string()),
+                ("page", page.to_string()),
+            ]);
+            let resp = send_request_with_retry(req, "worker gitea search repositories").await?;
+            let search_resp: GiteaSearchResponse = resp.json().await?;
+
+            let fetched = search_resp.data.len();
+            repos.extend(search_resp.data.into_iter().map(SourceRepo::from));
+
+            if fetched < PER_PAGE || repos.len() >= MAX_RESULTS {
+                break;
+            }
+
+            page += 1;
+        }
+
+        Ok(repos)
+    }
+
+    pub async fn search_code(&self, query: &str) -> Result<Vec<SourceRepo>> {
+        Err(anyhow::anyhow!(
+            "Gitea/Forgejo does not provide instance-wide code search (query: '{}')",
+            query
+        ))
+    }
+
+    pub async fn clone_repository_files(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        token: Option<String>,
+        limits: &IngestLimits,
+    ) -> Result<git::Checkout> {
+        let clone_url = git::build_clone_url(&self.web_base_url(), owner, repo, repo_url);
+        git::clone_repository_files(&clone_url, self.git_auth(token), limits).await
+    }
+
+    pub async fn head_commit_sha(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        token: Option<String>,
+    ) -> Result<String> {
+        let clone_url = git::build_clone_url(&self.web_base_url(), owner, repo, repo_url);
+        git::remote_head_sha(&clone_url, self.git_auth(token)).await
+    }
+
+    pub async fn list_tags(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        token: Option<String>,
+    ) -> Result<Vec<String>> {
+        let clone_url = git::build_clone_url(&self.web_base_url(), owner, repo, repo_url);
+        git::remote_tags(&clone_url, self.git_auth(token)).await
+    }
+
+    pub async fn clone_tag_files(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        tag: &str,
+        token: Option<String>,
+        limits: &IngestLimits,
+    ) -> Result<git::Checkout> {
+        let clone_url = git::build_clone_url(&self.web_base_url(), owner, repo, repo_url);
+        git::clone_tag_files(&clone_url, self.git_auth(token), tag, limits).await
+    }
+
+    fn git_auth(&self, token: Option<String>) -> Option<GitAuth> {
+        token
+            .or_else(|| self.token.clone())
+            .map(|password| GitAuth::Basic {
+                username: "oauth2".to_string(),
+                password,
+            })
+    }
+
+    fn web_base_url(&self) -> String {
+        self.api_url
+            .strip_suffix("/api/v1")
+            .unwrap_or(&self.api_url)
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gitea_repo_maps_to_source_repo() {
+        let resp: GiteaSearchResponse = serde_json::from_value(serde_json::json!({
+            "ok": true,
+            "data": [{
+                "name": "skills",
+                "full_name": "acme/skills",
+                "html_url": "https://codeberg.org/acme/skills",
+                "description": "",
+                "stars_count": 4,
+                "created_at": "2025-01-01T00:00:00Z",
+                "updated_at": "2025-02-01T00:00:00Z",
+                "owner": { "login": "acme" }
+            }]
+        }))
+        .unwrap();
+
+        let repo = SourceRepo::from(resp.data[0].clone());
+        assert_eq!(repo.owner, "acme");
+        assert_eq!(repo.name, "skills");
+        assert_eq!(repo.stars, 4);
+        assert_eq!(repo.description, None);
+    }
+
+    #[test]
+    fn web_base_url_strips_api_suffix() {
+        let client = GiteaClient::new(None, "https://codeberg.org/api/v1/".to_string()).unwrap();
+        assert_eq!(client.web_base_url(), "https://codeberg.org");
+    }
+
+    #[tokio::test]
+    async fn search_code_is_unsupported() {
+        let client = GiteaClient::new(None, "https://codeberg.org/api/v1".to_string()).unwrap();
+        let err = client.search_code("path:SKILL.md").await.unwrap_err();
+        assert!(err.to_string().contains("code search"));
+    }
+}
@@ -1,13 +1,15 @@
+use crate::git::{self, GitAuth};
+use crate::ports::SourceRepo;
 use anyhow::Result;
-use chrono::{DateTime, Utc};
-use common::infra::github_http::{build_github_client, send_request_with_retry};
-use reqwest::Client;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use common::infra::github_http::{
+    build_github_client, send_cached_request, send_request_with_retry, with_github_token, HttpCache,
+};
+use common::settings::IngestLimits;
+use reqwest::{Client, RequestBuilder};
 use serde::Deserialize;
-use std::collections::BTreeMap;
-use std::path::Path;
-use tokio::process::Command;
-use url::Url;
-use walkdir::WalkDir;
+use std::collections::HashSet;
+use std::sync::Arc;
 
 #[derive(Deserialize, Debug)]
 pub struct GithubSearchResponse {
@@ -49,92 +51,256 @@ pub struct GithubOwner {
     pub login: String,
 }
 
+impl From<GithubRepo> for SourceRepo {
+    fn from(repo: GithubRepo) -> Self {
+        SourceRepo {
+            owner: repo.owner.login,
+            name: repo.name,
+            html_url: repo.html_url,
+            description: repo.description,
+            stars: repo.stargazers_count,
+            created_at: repo.created_at,
+            updated_at: repo.updated_at,
+        }
+    }
+}
+
+const PER_PAGE: usize = 100;
+// GitHub search never pages past the first 1000 hits of a query.
+const MAX_SEARCH_RESULTS: usize = 1000;
+const MAX_SEARCH_SLICES: u32 = 256;
+// Files larger than this are not indexed by code search.
+const MAX_CODE_SEARCH_FILE_SIZE: i64 = 384 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SearchKind {
+    Repositories,
+    Code,
+}
+
+/// Inclusive range of days since the common era (repositories) or bytes (code).
+type SearchWindow = (i64, i64);
+
+impl SearchKind {
+    fn endpoint(self) -> &'static str {
+        match self {
+            SearchKind::Repositories => "search/repositories",
+            SearchKind::Code => "search/code",
+        }
+    }
+
+    fn context(self) -> &'static str {
+        match self {
+            SearchKind::Repositories => "worker search repositories",
+            SearchKind::Code => "worker search code",
+        }
+    }
+
+    fn has_window_qualifier(self, query: &str) -> bool {
+        let qualifiers: &[&str] = match self {
+            SearchKind::Repositories => &["created:", "pushed:"],
+            SearchKind::Code => &["size:"],
+        };
+        query
+            .split_whitespace()
+            .any(|token| qualifiers.iter().any(|q| token.starts_with(q)))
+    }
+
+    fn full_window(self) -> SearchWindow {
+        match self {
+            SearchKind::Repositories => {
+                let start = NaiveDate::from_ymd_opt(2007, 10, 1).expect("valid date");
+                (
+                    start.num_days_from_ce() as i64,
+                    Utc::now().date_naive().num_days_from_ce() as i64,
+                )
+            }
+            SearchKind::Code => (0, MAX_CODE_SEARCH_FILE_SIZE),
+        }
+    }
+
+    fn qualifier(self, (start, end): SearchWindow) -> String {
+        match self {
+            SearchKind::Repositories => {
+                let day = |n: i64| {
+                    NaiveDate::from_num_days_from_ce_opt(n as i32)
+                        .map(|d| d.format("%Y-%m-%d").to_string())
+                        .unwrap_or_default()
+                };
+                format!("created:{}..{}", day(start), day(end))
+            }
+            SearchKind::Code => format!("size:{}..{}", start, end),
+        }
+    }
+}
+
+fn split_window((start, end): SearchWindow) -> Option<(SearchWindow, SearchWindow)> {
+    if start >= end {
+        return None;
+    }
+    let mid = start + (end - start) / 2;
+    Some(((start, mid), (mid + 1, end)))
+}
+
 pub struct GithubClient {
     client: Client,
     api_url: String,
+    token: Option<String>,
+    cache: Option<Arc<dyn HttpCache>>,
 }
 
 impl GithubClient {
     pub fn new(token: Option<String>, api_url: String) -> Result<Self> {
-        let client = build_github_client(token.as_deref())?;
-        Ok(Self { client, api_url })
+        let client = build_github_client(None)?;
+        Ok(Self {
+            client,
+            api_url,
+            token,
+            cache: None,
+        })
     }
 
-    pub async fn search_repositories(&self, query: &str) -> Result<Vec<GithubRepo>> {
-        let mut all_repos = Vec::new();
-        let mut page = 1;
-        let per_page = 100;
-
-        loop {
-            let url = format!("{}/search/repositories", self.api_url);
-            tracing::debug!(page, query, "Fetching GitHub repository search page");
-
-            let per_page_str = per_page.to_string();
-            let page_str = page.to_string();
-            let req = self.client.get(&url).query(&[
-                ("q", query),
-                ("per_page", per_page_str.as_str()),
-                ("page", page_str.as_str()),
-            ]);
-            let resp = send_request_with_retry(req, "worker search repositories").await?;
-            let search_resp: GithubSearchResponse = resp.json().await?;
-
-            if search_resp.items.is_empty() {
-                break;
-            }
-
-            all_repos.extend(search_resp.items);
+    fn get(&self, url: &str) -> RequestBuilder {
+        with_github_token(self.client.get(url), self.token.as_deref())
+    }
 
-            if all_repos.len() >= search_resp.total_count as usize || all_repos.len() >= 1000 {
-                break;
-            }
+    pub fn with_cache(mut self, cache: Option<Arc<dyn HttpCache>>) -> Self {
+        self.cache = cache;
+        self
+    }
 
-            page += 1;
-        }
+    /// Searches repositories, splitting the query into `created:` windows when it
+    /// matches more results than GitHub will page through. Returns the merged,
+    /// deduplicated repos and the number of slices that were fetched.
+    pub async fn search_repositories(&self, query: &str) -> Result<(Vec<GithubRepo>, u32)> {
+        self.sliced_search(SearchKind::Repositories, query).await
+    }
 
-        Ok(all_repos)
+    /// Code search has no date qualifiers, so oversized queries are split on `size:`.
+    pub async fn search_code(&self, query: &str) -> Result<(Vec<GithubRepo>, u32)> {
+        self.sliced_search(SearchKind::Code, query).await
     }
 
-    pub async fn search_code(&self, query: &str) -> Result<Vec<GithubRepo>> {
+    async fn sliced_search(&self, kind: SearchKind, query: &str) -> Result<(Vec<GithubRepo>, u32)> {
+        let (total, first_page) = self.search_page(kind, query, 1).await?;
+        if total as usize <= MAX_SEARCH_RESULTS || kind.has_window_qualifier(query) {
+            let repos = self.collect_pages(kind, query, total, first_page).await?;
+            return Ok((repos, 1));
+        }
+
+        let mut windows = vec![kind.full_window()];
+        let mut seen = HashSet::new();
         let mut all_repos = Vec::new();
-        let mut page = 1;
-        let per_page = 100;
-
-        loop {
-            let url = format!("{}/search/code", self.api_url);
-            tracing::debug!(page, query, "Fetching GitHub code search page");
-
-            let per_page_str = per_page.to_string();
-            let page_str = page.to_string();
-            let req = self.client.get(&url).query(&[
-                ("q", query),
-                ("per_page", per_page_str.as_str()),
-                ("page", page_str.as_str()),
-            ]);
-            let resp = send_request_with_retry(req, "worker search code").await?;
-            let search_resp: GithubCodeSearchResponse = resp.json().await?;
-
-            if search_resp.items.is_empty() {
-                break;
+        let mut slices = 0;
+
+        while let Some(window) = windows.pop() {
+            let sliced_query = format!("{} {}", query, kind.qualifier(window));
+            let (total, first_page) = self.search_page(kind, &sliced_query, 1).await?;
+
+            if total as usize > MAX_SEARCH_RESULTS {
+                if let Some((lower, upper)) = split_window(window) {
+                    windows.push(upper);
+                    windows.push(lower);
+                    continue;
+                }
+                tracing::warn!(
+                    query = sliced_query,
+                    total,
+                    "Search window cannot be narrowed further, results will be truncated"
+                );
             }
 
-            for item in search_resp.items {
-                all_repos.push(item.repository);
+            slices += 1;
+            for repo in self
+                .collect_pages(kind, &sliced_query, total, first_page)
+                .await?
+            {
+                if seen.insert(repo.html_url.clone()) {
+                    all_repos.push(repo);
+                }
             }
 
-            if all_repos.len() >= search_resp.total_count as usize || all_repos.len() >= 1000 {
+            if slices >= MAX_SEARCH_SLICES {
+                tracing::warn!(query, slices, "Reached search slice limit");
                 break;
             }
+        }
+
+        tracing::info!(
+            query,
+            slices,
+            found = all_repos.len(),
+            "Merged sliced search results"
+        );
+        Ok((all_repos, slices))
+    }
+
+    async fn collect_pages(
+        &self,
+        kind: SearchKind,
+        query: &str,
+        total: u32,
+        first_page: Vec<GithubRepo>,
+    ) -> Result<Vec<GithubRepo>> {
+        let limit = std::cmp::min(total as usize, MAX_SEARCH_RESULTS);
+        let mut all_repos = first_page;
+        let mut page = 1;
 
+        while !all_repos.is_empty() && all_repos.len() < limit {
             page += 1;
+            let (_, items) = self.search_page(kind, query, page).await?;
+            if items.is_empty() {
+                break;
+            }
+            all_repos.extend(items);
         }
 
         Ok(all_repos)
     }
 
+    async fn search_page(
+        &self,
+        kind: SearchKind,
+        query: &str,
+        page: u32,
+    ) -> Result<(u32, Vec<GithubRepo>)> {
+        let url = format!("{}/{}", self.api_url, kind.endpoint());
+        tracing::debug!(
+            page,
+            query,
+            endpoint = kind.endpoint(),
+            "Fetching GitHub search page"
+        );
+
+        let per_page_str = PER_PAGE.to_string();
+        let page_str = page.to_string();
+        let req = self.get(&url).query(&[
+            ("q", query),
+            ("per_page", per_page_str.as_str()),
+            ("page", page_str.as_str()),
+        ]);
+
+        let body = send_cached_request(req, self.cache.as_deref(), kind.context()).await?;
+        match kind {
+            SearchKind::Repositories => {
+                let search_resp: GithubSearchResponse = serde_json::from_slice(&body)?;
+                Ok((search_resp.total_count, search_resp.items))
+            }
+            SearchKind::Code => {
+                let search_resp: GithubCodeSearchResponse = serde_json::from_slice(&body)?;
+                let repos = search_resp
+                    .items
+                    .into_iter()
+                    .map(|item| item.repository)
+                    .collect();
+                Ok((search_resp.total_count, repos))
+            }
+        }
+    }
+
     pub async fn download_zipball(&self, owner: &str, repo: &str) -> Result<Vec<u8>> {
         let url = format!("{}/repos/{}/{}/zipball", self.api_url, owner, repo);
-        let req = self.client.get(&url);
+        let req = self.get(&url);
         let resp = send_request_with_retry(req, "worker download zipball").await?;
         let bytes = resp.bytes().await?;
         Ok(bytes.to_vec())
@@ -146,156 +312,93 @@ impl GithubClient {
         repo: &str,
         repo_url: &str,
         token: Option<String>,
-    ) -> Result<BTreeMap<String, Vec<u8>>> {
-        let temp_dir = tempfile::tempdir()?;
-        let checkout_dir = temp_dir.path().join("repo");
+        limits: &IngestLimits,
+    ) -> Result<git::Checkout> {
         let clone_url = Self::build_clone_url(owner, repo, repo_url);
-
-        let output = Self::run_git_clone(&clone_url, &checkout_dir, token.as_deref()).await?;
-        if output.status.success() {
-            return collect_repository_files(&checkout_dir);
-        }
-
-        let primary_error = Self::sanitize_git_stderr(&output.stderr, token.as_deref());
-        if token.is_some() && Self::should_retry_without_token(&primary_error) {
-            tracing::warn!(
-                owner,
-                repo,
-                "git clone with token failed, retrying without token"
-            );
-            if checkout_dir.exists() {
-                let _ = std::fs::remove_dir_all(&checkout_dir);
-            }
-
-            let fallback_output = Self::run_git_clone(&clone_url, &checkout_dir, None).await?;
-            if fallback_output.status.success() {
-                return collect_repository_files(&checkout_dir);
-            }
-
-            let fallback_error = Self::sanitize_git_stderr(&fallback_output.stderr, None);
-            return Err(anyhow::anyhow!(
-                "git clone failed with token and without token. token_error='{}', fallback_error='{}'",
-                primary_error.trim(),
-                fallback_error.trim()
-            ));
-        }
-
-        Err(anyhow::anyhow!(
-            "git clone failed: {}",
-            primary_error.trim()
-        ))
+        git::clone_repository_files(&clone_url, token.map(GitAuth::Bearer), limits).await
     }
 
-    async fn run_git_clone(
-        clone_url: &str,
-        checkout_dir: &Path,
-        token: Option<&str>,
-    ) -> Result<std::process::Output> {
-        let mut cmd = Command::new("git");
-        cmd.env("GIT_TERMINAL_PROMPT", "0");
-
-        if let Some(auth_token) = token {
-            cmd.arg("-c").arg(format!(
-                "http.extraheader=Authorization: Bearer {}",
-                auth_token
-            ));
-        }
-
-        cmd.arg("clone")
-            .arg("--depth")
-            .arg("1")
-            .arg("--quiet")
-            .arg(clone_url)
-            .arg(checkout_dir);
-
-        Ok(cmd.output().await?)
+    pub async fn head_commit_sha(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        token: Option<String>,
+    ) -> Result<String> {
+        let clone_url = Self::build_clone_url(owner, repo, repo_url);
+        git::remote_head_sha(&clone_url, token.map(GitAuth::Bearer)).await
     }
 
-    fn build_clone_url(owner: &str, repo: &str, repo_url: &str) -> String {
-        let fallback = format!("https://github.com/{}/{}.git", owner, repo);
-
-        let Ok(mut parsed) = Url::parse(repo_url) else {
-            return fallback;
-        };
-
-        if !matches!(parsed.scheme(), "http" | "https") {
-            return fallback;
-        }
-
-        if parsed.host_str().is_none() {
-            return fallback;
-        }
-
-        parsed.set_query(None);
-        parsed.set_fragment(None);
-
-        let mut path = parsed.path().trim_end_matches('/').to_string();
-        if path.is_empty() || path == "/" {
-            path = format!("/{}/{}", owner, repo);
-        }
-
-        if !path.ends_with(".git") {
-            path.push_str(".git");
-        }
-
-        parsed.set_path(&path);
-        parsed.to_string()
+    pub async fn list_tags(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        token: Option<String>,
+    ) -> Result<Vec<String>> {
+        let clone_url = Self::build_clone_url(owner, repo, repo_url);
+        git::remote_tags(&clone_url, token.map(GitAuth::Bearer)).await
     }
 
-    fn should_retry_without_token(stderr: &str) -> bool {
-        let lower = stderr.to_ascii_lowercase();
-        lower.contains("invalid credentials")
-            || lower.contains("authentication failed")
-            || lower.contains("http basic: access denied")
-            || lower.contains("could not read username")
-            || lower.contains("repository not found")
+    pub async fn clone_tag_files(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        tag: &str,
+        token: Option<String>,
+        limits: &IngestLimits,
+    ) -> Result<git::Checkout> {
+        let clone_url = Self::build_clone_url(owner, repo, repo_url);
+        git::clone_tag_files(&clone_url, token.map(GitAuth::Bearer), tag, limits).await
     }
 
-    fn sanitize_git_stderr(stderr: &[u8], token: Option<&str>) -> String {
-        let mut text = String::from_utf8_lossy(stderr).to_string();
-        if let Some(auth_token) = token {
-            text = text.replace(auth_token, "***");
-        }
-        text
+    fn build_clone_url(owner: &str, repo: &str, repo_url: &str) -> String {
+        git::build_clone_url("https://github.com", owner, repo, repo_url)
     }
 }
 
-fn collect_repository_files(repo_dir: &Path) -> Result<BTreeMap<String, Vec<u8>>> {
-    let mut files = BTreeMap::new();
-
-    for entry in WalkDir::new(repo_dir).follow_links(false) {
-        let entry = entry?;
-        let path = entry.path();
-        if entry.file_type().is_dir() {
-            continue;
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        if path
-            .components()
-            .any(|c| c.as_os_str().to_string_lossy() == ".git")
-        {
-            continue;
-        }
+    #[test]
+    fn split_window_halves_until_single_unit() {
+        assert_eq!(split_window((0, 9)), Some(((0, 4), (5, 9))));
+        assert_eq!(split_window((4, 5)), Some(((4, 4), (5, 5))));
+        assert_eq!(split_window((7, 7)), None);
+    }
 
-        let rel = path
-            .strip_prefix(repo_dir)
-            .map_err(|e| anyhow::anyhow!("failed to strip repo root: {}", e))?;
-        let rel = rel.to_string_lossy().replace('\\', "/");
-        if rel.is_empty() {
-            continue;
-        }
+    #[test]
+    fn search_kind_formats_window_qualifiers() {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .num_days_from_ce() as i64;
+        let end = NaiveDate::from_ymd_opt(2024, 6, 30)
+            .unwrap()
+            .num_days_from_ce() as i64;
+        assert_eq!(
+            SearchKind::Repositories.qualifier((start, end)),
+            "created:2024-01-01..2024-06-30"
+        );
+        assert_eq!(SearchKind::Code.qualifier((0, 1024)), "size:0..1024");
 
-        files.insert(rel, std::fs::read(path)?);
+        let (full_start, full_end) = SearchKind::Repositories.full_window();
+        assert!(full_start < full_end);
+        assert_eq!(
+            SearchKind::Code.full_window(),
+            (0, MAX_CODE_SEARCH_FILE_SIZE)
+        );
     }
 
-    Ok(files)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::{collect_repository_files, GithubClient};
-    use std::fs;
-    use std::io::Write;
+    #[test]
+    fn queries_with_explicit_windows_are_not_sliced() {
+        assert!(SearchKind::Repositories.has_window_qualifier("topic:a created:>2024-01-01"));
+        assert!(SearchKind::Repositories.has_window_qualifier("topic:a pushed:2024-01-01..*"));
+        assert!(!SearchKind::Repositories.has_window_qualifier("topic:a sort:updated"));
+        assert!(SearchKind::Code.has_window_qualifier("path:SKILL.md size:<1000"));
+        assert!(!SearchKind::Code.has_window_qualifier("path:SKILL.md"));
+    }
 
     #[test]
     fn build_clone_url_uses_repository_host() {
@@ -336,41 +439,4 @@ mod tests {
             GithubClient::build_clone_url("acme", "skill-registry", "https://ghe.example.com");
         assert_eq!(no_path, "https://ghe.example.com/acme/skill-registry.git");
     }
-
-    #[test]
-    fn should_retry_without_token_matches_auth_failures() {
-        assert!(GithubClient::should_retry_without_token(
-            "invalid credentials"
-        ));
-        assert!(GithubClient::should_retry_without_token(
-            "HTTP Basic: Access denied"
-        ));
-        assert!(!GithubClient::should_retry_without_token("network timeout"));
-    }
-
-    #[test]
-    fn sanitize_git_stderr_redacts_token() {
-        let raw = b"fatal: token abc123 rejected";
-        let sanitized = GithubClient::sanitize_git_stderr(raw, Some("abc123"));
-        assert!(!sanitized.contains("abc123"));
-        assert!(sanitized.contains("***"));
-    }
-
-    #[test]
-    fn collect_repository_files_skips_git_directory() {
-        let temp = tempfile::tempdir().unwrap();
-        let repo_dir = temp.path();
-        fs::create_dir_all(repo_dir.join("src")).unwrap();
-        fs::create_dir_all(repo_dir.join(".git")).unwrap();
-
-        let mut file = fs::File::create(repo_dir.join("src/lib.rs")).unwrap();
-        writeln!(file, "fn main() {{}}").unwrap();
-
-        let mut git_file = fs::File::create(repo_dir.join(".git/config")).unwrap();
-        writeln!(git_file, "[core]").unwrap();
-
-        let files = collect_repository_files(repo_dir).unwrap();
-        assert!(files.contains_key("src/lib.rs"));
-        assert!(!files.contains_key(".git/config"));
-    }
 }
@@ -0,0 +1,298 @@
+use crate::git::{self, GitAuth};
+use crate::ports::{parse_search_query, SourceRepo};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use common::infra::github_http::send_request_with_retry;
+use common::settings::IngestLimits;
+use reqwest::{Client, RequestBuilder, Response};
+use serde::Deserialize;
+use std::collections::HashSet;
+
+const PER_PAGE: usize = 100;
+const MAX_RESULTS: usize = 1000;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GitlabProject {
+    pub id: i64,
+    pub path: String,
+    pub web_url: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub star_count: i32,
+    #[serde(default = "utc_now")]
+    pub created_at: DateTime<Utc>,
+    #[serde(default = "utc_now")]
+    pub last_activity_at: DateTime<Utc>,
+    pub namespace: GitlabNamespace,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct GitlabNamespace {
+    pub full_path: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GitlabBlobItem {
+    pub project_id: i64,
+}
+
+fn utc_now() -> DateTime<Utc> {
+    Utc::now()
+}
+
+impl From<GitlabProject> for SourceRepo {
+    fn from(project: GitlabProject) -> Self {
+        SourceRepo {
+            owner: project.namespace.full_path,
+            name: project.path,
+            html_url: project.web_url,
+            description: project.description,
+            stars: project.star_count,
+            created_at: project.created_at,
+            updated_at: project.last_activity_at,
+        }
+    }
+}
+
+fn next_page(resp: &Response) -> Option<u32> {
+    resp.headers()
+        .get("x-next-page")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u32>().ok())
+}
+
+pub struct GitlabClient {
+    client: Client,
+    api_url: String,
+    token: Option<String>,
+}
+
+impl GitlabClient {
+    pub fn new(token: Option<String>, api_url: String) -> Result<Self> {
+        let token = token
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty());
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::USER_AGENT, "SkillRegistry/1.0".parse()?);
+
+        let client = Client::builder().default_headers(headers).build()?;
+        Ok(Self {
+            client,
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token,
+        })
+    }
+
+    // Set per request so rate-limit tracking can tell tokens apart.
+    fn get(&self, url: &str) -> RequestBuilder {
+        match self.token.as_deref() {
+            Some(token) => self.client.get(url).header("PRIVATE-TOKEN", token),
+            None => self.client.get(url),
+        }
+    }
+
+    pub async fn search_projects(&self, query: &str) -> Result<Vec<SourceRepo>> {
+        let parsed = parse_search_query(query);
+        let mut params = vec![
+            ("archived", "false".to_string()),
+            ("order_by", "last_activity_at".to_string()),
+        ];
+        if !parsed.topics.is_empty() {
+            params.push(("topic", parsed.topics.join(",")));
+        }
+        if let Some(search) = parsed.search {
+            params.push(("search", search));
+        }
+
+        let url = format!("{}/projects", self.api_url);
+        let mut all_repos = Vec::new();
+        let mut page = 1;
+
+        loop {
+            tracing::debug!(page, query, "Fetching GitLab project search page");
+
+            let req = self.get(&url).query(&params).query(&[
+                ("per_page", PER_PAGE.to_string()),
+                ("page", page.to_string()),
+            ]);
+            let resp = send_request_with_retry(req, "worker gitlab search projects").await?;
+            let next = next_page(&resp);
+            let projects: Vec<GitlabProject> = resp.json().await?;
+
+            if projects.is_empty() {
+                break;
+            }
+
+            all_repos.extend(projects.into_iter().map(SourceRepo::from));
+
+            match next {
+                Some(n) if all_repos.len() < MAX_RESULTS => page = n,
+                _ => break,
+            }
+        }
+
+        all_repos.truncate(MAX_RESULTS);
+        Ok(all_repos)
+    }
+
+    pub async fn search_blobs(&self, query: &str) -> Result<Vec<SourceRepo>> {
+        let url = format!("{}/search", self.api_url);
+        let mut project_ids = Vec::new();
+        let mut seen = HashSet::new();
+        let mut page = 1;
+
+        loop {
+            tracing::debug!(page, query, "Fetching GitLab blob search page");
+
+            let req = self.get(&url).query(&[
+                ("scope", "blobs".to_string()),
+                ("search", query.to_string()),
+                ("per_page", PER_PAGE.to_string()),
+                ("page", page.to_string()),
+            ]);
+            let resp = send_request_with_retry(req, "worker gitlab search blobs").await?;
+            let next = next_page(&resp);
+            let items: Vec<GitlabBlobItem> = resp.json().await?;
+
+            if items.is_empty() {
+                break;
+            }
+
+            for item in items {
+                if seen.insert(item.project_id) {
+                    project_ids.push(item.project_id);
+                }
+            }
+
+            match next {
+                Some(n) if project_ids.len() < MAX_RESULTS => page = n,
+                _ => break,
+            }
+        }
+
+        let mut repos = Vec::new();
+        for id in project_ids.into_iter().take(MAX_RESULTS) {
+            match self.get_project(id).await {
+                Ok(project) => repos.push(SourceRepo::from(project)),
+                Err(e) => tracing::warn!(project_id = id, "Failed to load GitLab project: {}", e),
+            }
+        }
+
+        Ok(repos)
+    }
+
+    pub async fn get_project(&self, id: i64) -> Result<GitlabProject> {
+        let url = format!("{}/projects/{}", self.api_url, id);
+        let req = self.get(&url);
+        let resp = send_request_with_retry(req, "worker gitlab get project").await?;
+        Ok(resp.json().await?)
+    }
+
+    pub async fn clone_repository_files(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        token: Option<String>,
+        limits: &IngestLimits,
+    ) -> Result<git::Checkout> {
+        let clone_url = git::build_clone_url(&self.web_base_url(), owner, repo, repo_url);
+        git::clone_repository_files(&clone_url, self.git_auth(token), limits).await
+    }
+
+    pub async fn head_commit_sha(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        token: Option<String>,
+    ) -> Result<String> {
+        let clone_url = git::build_clone_url(&self.web_base_url(), owner, repo, repo_url);
+        git::remote_head_sha(&clone_url, self.git_auth(token)).await
+    }
+
+    pub async fn list_tags(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        token: Option<String>,
+    ) -> Result<Vec<String>> {
+        let clone_url = git::build_clone_url(&self.web_base_url(), owner, repo, repo_url);
+        git::remote_tags(&clone_url, self.git_auth(token)).await
+    }
+
+    pub async fn clone_tag_files(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        tag: &str,
+        token: Option<String>,
+        limits: &IngestLimits,
+    ) -> Result<git::Checkout> {
+        let clone_url = git::build_clone_url(&self.web_base_url(), owner, repo, repo_url);
+        git::clone_tag_files(&clone_url, self.git_auth(token), tag, limits).await
+    }
+
+    fn git_auth(&self, token: Option<String>) -> Option<GitAuth> {
+        token
+            .or_else(|| self.token.clone())
+            .map(|password| GitAuth::Basic {
+                username: "oauth2".to_string(),
+                password,
+            })
+    }
+
+    fn web_base_url(&self) -> String {
+        self.api_url
+            .strip_suffix("/api/v4")
+            .unwrap_or(&self.api_url)
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gitlab_project_maps_namespace_to_owner() {
+        let project: GitlabProject = serde_json::from_value(serde_json::json!({
+            "id": 7,
+            "path": "skills",
+            "web_url": "https://gitlab.example.com/platform/ai/skills",
+            "description": "internal skills",
+            "star_count": 3,
+            "created_at": "2025-01-01T00:00:00Z",
+            "last_activity_at": "2025-02-01T00:00:00Z",
+            "namespace": { "full_path": "platform/ai" }
+        }))
+        .unwrap();
+
+        let repo = SourceRepo::from(project);
+        assert_eq!(repo.name, "skills");
+        assert_eq!(repo.owner, "platform/ai");
+        assert_eq!(repo.stars, 3);
+        assert_eq!(
+            repo.html_url,
+            "https://gitlab.example.com/platform/ai/skills"
+        );
+    }
+
+    #[test]
+    fn web_base_url_strips_api_suffix() {
+        let client =
+            GitlabClient::new(None, "https://gitlab.example.com/api/v4/".to_string()).unwrap();
+        assert_eq!(client.web_base_url(), "https://gitlab.example.com");
+    }
+
+    #[tokio::test]
+    async fn search_surfaces_connection_errors() {
+        let client =
+            GitlabClient::new(Some("glpat".to_string()), "http://127.0.0.1:1".to_string()).unwrap();
+        assert!(client.search_projects("topic:agent-skill").await.is_err());
+        assert!(client.search_blobs("filename:SKILL.md").await.is_err());
+    }
+}
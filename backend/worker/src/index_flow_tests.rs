@@ -1,6 +1,5 @@
 use crate::activities::discovery::DiscoveryActivities;
-use crate::github::{GithubOwner, GithubRepo};
-use crate::ports::{MockGithubApi, MockStorage};
+use crate::ports::{MockSourceProvider, MockStorage, SourceRepo};
 use crate::sync::SyncService;
 use anyhow::Result;
 use common::build_all;
@@ -50,7 +49,9 @@ async fn setup_db() -> Result<(DatabaseConnection, common::Services)> {
             access_key_id: None,
             secret_access_key: None,
             force_path_style: false,
+            download_mode: common::settings::DownloadMode::Presigned,
         },
+        storage: common::settings::StorageSettings::default(),
         github: common::settings::GithubSettings {
             search_keywords: "topic:agent-skill".to_string(),
             token: None,
@@ -58,12 +59,16 @@ async fn setup_db() -> Result<(DatabaseConnection, common::Services)> {
         },
         worker: common::settings::WorkerSettings {
             scan_interval_seconds: 3600,
+            tag_patterns: common::settings::default_tag_patterns(),
+            gc_grace_period_hours: common::settings::default_gc_grace_period_hours(),
+            ingest_limits: common::settings::IngestLimits::default(),
         },
         temporal: common::settings::TemporalSettings {
             server_url: "http://localhost:7233".to_string(),
             task_queue: "test".to_string(),
         },
         auth: common::settings::AuthSettings::default(),
+        signing: common::settings::SigningSettings::default(),
         debug: true,
     });
 
@@ -77,34 +82,39 @@ async fn setup_db() -> Result<(DatabaseConnection, common::Services)> {
 async fn index_flow_discovers_and_syncs_standalone_repo() -> Result<()> {
     let (db, services) = setup_db().await?;
 
-    let mut github = MockGithubApi::new();
+    let mut github = MockSourceProvider::new();
     github.expect_search_repositories().returning(|q| {
         assert_eq!(q, "topic:agent-skill fork:false sort:updated");
-        Ok(vec![GithubRepo {
+        Ok(vec![SourceRepo {
             name: "standalone".to_string(),
             html_url: "https://github.com/test-owner/standalone".to_string(),
             description: Some("desc".to_string()),
-            stargazers_count: 10,
+            stars: 10,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
-            owner: GithubOwner {
-                login: "test-owner".to_string(),
-            },
-        }])
+            owner: "test-owner".to_string(),
+        }]
+        .into())
     });
 
     let file_map = create_file_map(vec![(
         "skill-a/SKILL.md",
         b"---\nname: test-skill\ndescription: test description\nmetadata:\n  version: 1.0.0\n---\n# Body\n",
     )]);
+    github
+        .expect_head_commit_sha()
+        .returning(|_, _, _, _| Ok("0123456789abcdef0123456789abcdef01234567".to_string()));
+    github
+        .expect_list_tags()
+        .returning(|_, _, _, _| Ok(Vec::new()));
     github
         .expect_clone_repository_files()
-        .returning(move |owner, repo, repo_url, token| {
+        .returning(move |owner, repo, repo_url, token, _| {
             assert_eq!(owner, "test-owner");
             assert_eq!(repo, "standalone");
             assert_eq!(repo_url, "https://github.com/test-owner/standalone");
             assert!(token.is_none());
-            Ok(file_map.clone())
+            Ok(file_map.clone().into())
         });
 
     let mut s3 = MockStorage::new();
@@ -173,20 +183,19 @@ async fn index_flow_discovers_and_syncs_standalone_repo() -> Result<()> {
 async fn index_flow_discovers_and_syncs_marketplace_repo() -> Result<()> {
     let (db, services) = setup_db().await?;
 
-    let mut github = MockGithubApi::new();
+    let mut github = MockSourceProvider::new();
     github.expect_search_repositories().returning(|q| {
         assert_eq!(q, "topic:agent-skill fork:false sort:updated");
-        Ok(vec![GithubRepo {
+        Ok(vec![SourceRepo {
             name: "market".to_string(),
             html_url: "https://github.com/test-owner/market".to_string(),
             description: Some("desc".to_string()),
-            stargazers_count: 10,
+            stars: 10,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
-            owner: GithubOwner {
-                login: "test-owner".to_string(),
-            },
-        }])
+            owner: "test-owner".to_string(),
+        }]
+        .into())
     });
 
     let marketplace_json = br#"{
@@ -221,14 +230,20 @@ async fn index_flow_discovers_and_syncs_marketplace_repo() -> Result<()> {
             b"---\nname: s1\ndescription: s1 desc\n---\n# skill\n",
         ),
     ]);
+    github
+        .expect_head_commit_sha()
+        .returning(|_, _, _, _| Ok("0123456789abcdef0123456789abcdef01234567".to_string()));
+    github
+        .expect_list_tags()
+        .returning(|_, _, _, _| Ok(Vec::new()));
     github
         .expect_clone_repository_files()
-        .returning(move |owner, repo, repo_url, token| {
+        .returning(move |owner, repo, repo_url, token, _| {
             assert_eq!(owner, "test-owner");
             assert_eq!(repo, "market");
             assert_eq!(repo_url, "https://github.com/test-owner/market");
             assert!(token.is_none());
-            Ok(file_map.clone())
+            Ok(file_map.clone().into())
         });
 
     let mut s3 = MockStorage::new();
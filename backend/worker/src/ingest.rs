@@ -0,0 +1,212 @@
+use common::settings::IngestLimits;
+use std::collections::BTreeMap;
+use std::io::Read;
+
+const LFS_POINTER_PREFIX: &[u8] = b"version https://git-lfs.github.com/spec/v1\n";
+/// Pointer files are a few short lines; anything larger is real content.
+const MAX_LFS_POINTER_BYTES: usize = 1024;
+const MAX_LISTED_LFS_POINTERS: usize = 5;
+
+/// A repository broke one of the configured `IngestLimits`. Retrying cannot
+/// help, so callers blacklist the repository with this as the reason.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum IngestLimitError {
+    #[error("Repository has more than {limit} files")]
+    TooManyFiles { limit: usize },
+    #[error("File '{path}' is larger than {limit} bytes")]
+    FileTooLarge { path: String, limit: u64 },
+    #[error("Repository is larger than {limit} bytes")]
+    RepoTooLarge { limit: u64 },
+    #[error("Archive inflates to more than {limit} times its size")]
+    CompressionRatio { limit: u64 },
+}
+
+/// Files and bytes a checkout has loaded so far, checked against the limits
+/// before each file is read.
+pub struct IngestBudget<'a> {
+    limits: &'a IngestLimits,
+    files: usize,
+    bytes: u64,
+}
+
+impl<'a> IngestBudget<'a> {
+    pub fn new(limits: &'a IngestLimits) -> Self {
+        Self {
+            limits,
+            files: 0,
+            bytes: 0,
+        }
+    }
+
+    pub fn admit(&mut self, path: &str, size: u64) -> Result<(), IngestLimitError> {
+        if self.files >= self.limits.max_files {
+            return Err(IngestLimitError::TooManyFiles {
+                limit: self.limits.max_files,
+            });
+        }
+        if size > self.limits.max_file_bytes {
+            return Err(IngestLimitError::FileTooLarge {
+                path: path.to_string(),
+                limit: self.limits.max_file_bytes,
+            });
+        }
+        let bytes = self.bytes.saturating_add(size);
+        if bytes > self.limits.max_repo_bytes {
+            return Err(IngestLimitError::RepoTooLarge {
+                limit: self.limits.max_repo_bytes,
+            });
+        }
+        self.files += 1;
+        self.bytes = bytes;
+        Ok(())
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+}
+
+/// Reads `reader` to the end, failing once it yields more than `limit` bytes,
+/// so a size declared in an archive header is never trusted.
+pub fn read_bounded(reader: impl Read, path: &str, limit: u64) -> anyhow::Result<Vec<u8>> {
+    let mut content = Vec::new();
+    reader
+        .take(limit.saturating_add(1))
+        .read_to_end(&mut content)?;
+    if content.len() as u64 > limit {
+        return Err(IngestLimitError::FileTooLarge {
+            path: path.to_string(),
+            limit,
+        }
+        .into());
+    }
+    Ok(content)
+}
+
+pub fn check_compression_ratio(
+    compressed: u64,
+    uncompressed: u64,
+    limits: &IngestLimits,
+) -> Result<(), IngestLimitError> {
+    if uncompressed
+        > compressed
+            .max(1)
+            .saturating_mul(limits.max_compression_ratio)
+    {
+        return Err(IngestLimitError::CompressionRatio {
+            limit: limits.max_compression_ratio,
+        });
+    }
+    Ok(())
+}
+
+/// Whether `content` is a Git LFS pointer rather than the file it stands for.
+/// Clones do not fetch LFS objects, so such files are packaged as pointers.
+pub fn is_lfs_pointer(content: &[u8]) -> bool {
+    content.len() <= MAX_LFS_POINTER_BYTES && content.starts_with(LFS_POINTER_PREFIX)
+}
+
+/// A warning for maintainers naming the LFS pointer files in `files`, if any.
+pub fn lfs_pointer_warning(files: &BTreeMap<String, Vec<u8>>) -> Option<String> {
+    let pointers: Vec<&str> = files
+        .iter()
+        .filter(|(_, content)| is_lfs_pointer(content))
+        .map(|(path, _)| path.as_str())
+        .collect();
+    if pointers.is_empty() {
+        return None;
+    }
+    let mut listed = pointers[..pointers.len().min(MAX_LISTED_LFS_POINTERS)].join(", ");
+    if pointers.len() > MAX_LISTED_LFS_POINTERS {
+        listed.push_str(&format!(
+            " and {} more",
+            pointers.len() - MAX_LISTED_LFS_POINTERS
+        ));
+    }
+    Some(format!(
+        "Git LFS files are packaged as pointers without their content: {}",
+        listed
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> IngestLimits {
+        IngestLimits {
+            max_repo_bytes: 10,
+            max_file_bytes: 6,
+            max_files: 3,
+            max_compression_ratio: 4,
+        }
+    }
+
+    #[test]
+    fn budget_rejects_the_first_file_over_a_limit() {
+        let limits = limits();
+        let mut budget = IngestBudget::new(&limits);
+        assert_eq!(
+            budget.admit("big.bin", 7),
+            Err(IngestLimitError::FileTooLarge {
+                path: "big.bin".to_string(),
+                limit: 6,
+            })
+        );
+        budget.admit("a", 6).unwrap();
+        budget.admit("b", 4).unwrap();
+        assert_eq!(budget.bytes(), 10);
+        assert_eq!(
+            budget.admit("c", 1),
+            Err(IngestLimitError::RepoTooLarge { limit: 10 })
+        );
+        budget.admit("empty", 0).unwrap();
+        assert_eq!(
+            budget.admit("d", 0),
+            Err(IngestLimitError::TooManyFiles { limit: 3 })
+        );
+    }
+
+    #[test]
+    fn read_bounded_ignores_declared_sizes() {
+        assert_eq!(read_bounded(&b"abc"[..], "a", 3).unwrap(), b"abc");
+        let err = read_bounded(&b"abcd"[..], "a", 3).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<IngestLimitError>(),
+            Some(&IngestLimitError::FileTooLarge {
+                path: "a".to_string(),
+                limit: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn compression_ratio_is_bounded() {
+        let limits = limits();
+        assert!(check_compression_ratio(10, 40, &limits).is_ok());
+        assert_eq!(
+            check_compression_ratio(10, 41, &limits),
+            Err(IngestLimitError::CompressionRatio { limit: 4 })
+        );
+        assert!(check_compression_ratio(0, 4, &limits).is_ok());
+    }
+
+    #[test]
+    fn lfs_pointers_are_reported() {
+        let pointer = b"version https://git-lfs.github.com/spec/v1\noid sha256:4d7a\nsize 12345\n";
+        assert!(is_lfs_pointer(pointer));
+        assert!(!is_lfs_pointer(b"# Demo"));
+
+        let mut files = BTreeMap::new();
+        files.insert("demo/SKILL.md".to_string(), b"# Demo".to_vec());
+        assert_eq!(lfs_pointer_warning(&files), None);
+
+        for i in 0..7 {
+            files.insert(format!("demo/assets/{i}.png"), pointer.to_vec());
+        }
+        assert_eq!(
+            lfs_pointer_warning(&files).unwrap(),
+            "Git LFS files are packaged as pointers without their content: demo/assets/0.png, demo/assets/1.png, demo/assets/2.png, demo/assets/3.png, demo/assets/4.png and 2 more"
+        );
+    }
+}
@@ -2,9 +2,14 @@
 mod activities;
 mod bootstrap;
 mod contracts;
+mod gc;
+mod git;
+mod gitea;
 mod github;
+mod gitlab;
 #[cfg(test)]
 mod index_flow_tests;
+mod ingest;
 mod ports;
 mod sync;
 mod workflows;
@@ -13,7 +18,7 @@ use bootstrap::{
     build_temporal_worker, build_worker_context, build_worker_services, register_activities,
     register_workflows,
 };
-use common::settings::Settings;
+use common::settings::{Settings, StorageBackend};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 fn default_rust_log() -> String {
@@ -35,15 +40,23 @@ async fn main() -> anyhow::Result<()> {
 
     let settings = Settings::new()?;
 
-    let s3_bucket = settings.s3.bucket.clone();
-    let s3_region = settings.s3.region.clone();
-    let s3_endpoint = settings.s3.endpoint.clone();
-    tracing::info!(
-        s3_bucket = %s3_bucket,
-        s3_region = %s3_region,
-        s3_endpoint = %display_s3_endpoint(s3_endpoint.as_deref()),
-        "S3 config loaded"
-    );
+    match settings.storage.backend {
+        StorageBackend::S3 => {
+            let s3_bucket = settings.s3.bucket.clone();
+            let s3_region = settings.s3.region.clone();
+            let s3_endpoint = settings.s3.endpoint.clone();
+            tracing::info!(
+                s3_bucket = %s3_bucket,
+                s3_region = %s3_region,
+                s3_endpoint = %display_s3_endpoint(s3_endpoint.as_deref()),
+                "S3 config loaded"
+            );
+        }
+        StorageBackend::Local => tracing::info!(
+            path = %settings.storage.local_path,
+            "Local storage config loaded"
+        ),
+    }
 
     let ctx = build_worker_context(settings).await?;
     let worker_services = build_worker_services(&ctx);
@@ -55,6 +68,7 @@ async fn main() -> anyhow::Result<()> {
         &mut temporal_runtime.worker,
         worker_services.discovery,
         worker_services.sync,
+        worker_services.gc,
     );
     register_workflows(&mut temporal_runtime.worker);
 
@@ -1,34 +1,154 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 #[cfg(test)]
 use mockall::automock;
-use std::collections::BTreeMap;
+use std::sync::Arc;
 
-use crate::github::{GithubClient, GithubRepo};
-use common::s3::S3Service;
+use crate::git::Checkout;
+use crate::gitea::GiteaClient;
+use crate::github::GithubClient;
+use crate::gitlab::GitlabClient;
+use common::entities::skill_registry;
+use common::infra::github_http::HttpCache;
+use common::s3::{ListedObject, ObjectStore};
+use common::settings::IngestLimits;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceRepo {
+    pub owner: String,
+    pub name: String,
+    pub html_url: String,
+    pub description: Option<String>,
+    pub stars: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchResults {
+    pub repos: Vec<SourceRepo>,
+    /// Number of search requests the query was split into to stay under result caps.
+    pub slices: u32,
+}
+
+impl From<Vec<SourceRepo>> for SearchResults {
+    fn from(repos: Vec<SourceRepo>) -> Self {
+        Self { repos, slices: 1 }
+    }
+}
 
 #[cfg_attr(test, automock)]
 #[async_trait]
-pub trait GithubApi: Send + Sync {
-    async fn search_repositories(&self, query: &str) -> Result<Vec<GithubRepo>>;
-    async fn search_code(&self, query: &str) -> Result<Vec<GithubRepo>>;
+pub trait SourceProvider: Send + Sync {
+    async fn search_repositories(&self, query: &str) -> Result<SearchResults>;
+    async fn search_code(&self, query: &str) -> Result<SearchResults>;
     async fn clone_repository_files(
         &self,
         owner: &str,
         repo: &str,
         repo_url: &str,
         token: Option<String>,
-    ) -> Result<BTreeMap<String, Vec<u8>>>;
+        limits: &IngestLimits,
+    ) -> Result<Checkout>;
+    /// Commit SHA of the remote default branch, resolved without cloning.
+    async fn head_commit_sha(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        token: Option<String>,
+    ) -> Result<String>;
+    async fn list_tags(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        token: Option<String>,
+    ) -> Result<Vec<String>>;
+    async fn clone_tag_files(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        tag: &str,
+        token: Option<String>,
+        limits: &IngestLimits,
+    ) -> Result<Checkout>;
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    pub topics: Vec<String>,
+    pub search: Option<String>,
+}
+
+// Discovery queries use GitHub search syntax. Providers without an equivalent
+// keep `topic:` qualifiers and free-text terms and drop every other qualifier.
+pub fn parse_search_query(query: &str) -> SearchQuery {
+    let mut topics = Vec::new();
+    let mut terms = Vec::new();
+
+    for token in query.split_whitespace() {
+        match token.split_once(':') {
+            Some(("topic", value)) if !value.is_empty() => topics.push(value.to_string()),
+            Some((key, _)) if !key.is_empty() => {}
+            _ => terms.push(token),
+        }
+    }
+
+    SearchQuery {
+        topics,
+        search: if terms.is_empty() {
+            None
+        } else {
+            Some(terms.join(" "))
+        },
+    }
+}
+
+pub fn build_source_provider(
+    platform: &skill_registry::Platform,
+    token: Option<String>,
+    api_url: String,
+    cache: Option<Arc<dyn HttpCache>>,
+) -> Result<Arc<dyn SourceProvider>> {
+    Ok(match platform {
+        skill_registry::Platform::Github => {
+            Arc::new(GithubClient::new(token, api_url)?.with_cache(cache))
+        }
+        skill_registry::Platform::Gitlab => Arc::new(GitlabClient::new(token, api_url)?),
+        skill_registry::Platform::Gitea => Arc::new(GiteaClient::new(token, api_url)?),
+    })
+}
+
+pub fn default_api_url(platform: &skill_registry::Platform, host: &str) -> String {
+    match platform {
+        skill_registry::Platform::Github if host == "github.com" => {
+            "https://api.github.com".to_string()
+        }
+        skill_registry::Platform::Github => format!("https://{}/api/v3", host),
+        skill_registry::Platform::Gitlab => format!("https://{}/api/v4", host),
+        skill_registry::Platform::Gitea => format!("https://{}/api/v1", host),
+    }
 }
 
 #[async_trait]
-impl GithubApi for GithubClient {
-    async fn search_repositories(&self, query: &str) -> Result<Vec<GithubRepo>> {
-        self.search_repositories(query).await
+impl SourceProvider for GithubClient {
+    async fn search_repositories(&self, query: &str) -> Result<SearchResults> {
+        let (repos, slices) = self.search_repositories(query).await?;
+        Ok(SearchResults {
+            repos: repos.into_iter().map(SourceRepo::from).collect(),
+            slices,
+        })
     }
 
-    async fn search_code(&self, query: &str) -> Result<Vec<GithubRepo>> {
-        self.search_code(query).await
+    async fn search_code(&self, query: &str) -> Result<SearchResults> {
+        let (repos, slices) = self.search_code(query).await?;
+        Ok(SearchResults {
+            repos: repos.into_iter().map(SourceRepo::from).collect(),
+            slices,
+        })
     }
 
     async fn clone_repository_files(
@@ -37,8 +157,154 @@ impl GithubApi for GithubClient {
         repo: &str,
         repo_url: &str,
         token: Option<String>,
-    ) -> Result<BTreeMap<String, Vec<u8>>> {
-        self.clone_repository_files(owner, repo, repo_url, token)
+        limits: &IngestLimits,
+    ) -> Result<Checkout> {
+        self.clone_repository_files(owner, repo, repo_url, token, limits)
+            .await
+    }
+
+    async fn head_commit_sha(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        token: Option<String>,
+    ) -> Result<String> {
+        self.head_commit_sha(owner, repo, repo_url, token).await
+    }
+
+    async fn list_tags(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        token: Option<String>,
+    ) -> Result<Vec<String>> {
+        self.list_tags(owner, repo, repo_url, token).await
+    }
+
+    async fn clone_tag_files(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        tag: &str,
+        token: Option<String>,
+        limits: &IngestLimits,
+    ) -> Result<Checkout> {
+        self.clone_tag_files(owner, repo, repo_url, tag, token, limits)
+            .await
+    }
+}
+
+#[async_trait]
+impl SourceProvider for GitlabClient {
+    async fn search_repositories(&self, query: &str) -> Result<SearchResults> {
+        Ok(self.search_projects(query).await?.into())
+    }
+
+    async fn search_code(&self, query: &str) -> Result<SearchResults> {
+        Ok(self.search_blobs(query).await?.into())
+    }
+
+    async fn clone_repository_files(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        token: Option<String>,
+        limits: &IngestLimits,
+    ) -> Result<Checkout> {
+        self.clone_repository_files(owner, repo, repo_url, token, limits)
+            .await
+    }
+
+    async fn head_commit_sha(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        token: Option<String>,
+    ) -> Result<String> {
+        self.head_commit_sha(owner, repo, repo_url, token).await
+    }
+
+    async fn list_tags(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        token: Option<String>,
+    ) -> Result<Vec<String>> {
+        self.list_tags(owner, repo, repo_url, token).await
+    }
+
+    async fn clone_tag_files(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        tag: &str,
+        token: Option<String>,
+        limits: &IngestLimits,
+    ) -> Result<Checkout> {
+        self.clone_tag_files(owner, repo, repo_url, tag, token, limits)
+            .await
+    }
+}
+
+#[async_trait]
+impl SourceProvider for GiteaClient {
+    async fn search_repositories(&self, query: &str) -> Result<SearchResults> {
+        Ok(self.search_repositories(query).await?.into())
+    }
+
+    async fn search_code(&self, query: &str) -> Result<SearchResults> {
+        Ok(self.search_code(query).await?.into())
+    }
+
+    async fn clone_repository_files(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        token: Option<String>,
+        limits: &IngestLimits,
+    ) -> Result<Checkout> {
+        self.clone_repository_files(owner, repo, repo_url, token, limits)
+            .await
+    }
+
+    async fn head_commit_sha(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        token: Option<String>,
+    ) -> Result<String> {
+        self.head_commit_sha(owner, repo, repo_url, token).await
+    }
+
+    async fn list_tags(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        token: Option<String>,
+    ) -> Result<Vec<String>> {
+        self.list_tags(owner, repo, repo_url, token).await
+    }
+
+    async fn clone_tag_files(
+        &self,
+        owner: &str,
+        repo: &str,
+        repo_url: &str,
+        tag: &str,
+        token: Option<String>,
+        limits: &IngestLimits,
+    ) -> Result<Checkout> {
+        self.clone_tag_files(owner, repo, repo_url, tag, token, limits)
             .await
     }
 }
@@ -48,10 +314,12 @@ impl GithubApi for GithubClient {
 pub trait Storage: Send + Sync {
     async fn upload(&self, key: &str, body: Vec<u8>) -> Result<String>;
     async fn download(&self, key: &str) -> Result<Vec<u8>>;
+    async fn list(&self, prefix: &str) -> Result<Vec<ListedObject>>;
+    async fn delete(&self, key: &str) -> Result<()>;
 }
 
 #[async_trait]
-impl Storage for S3Service {
+impl Storage for ObjectStore {
     async fn upload(&self, key: &str, body: Vec<u8>) -> Result<String> {
         self.upload_file(key, body).await
     }
@@ -59,6 +327,14 @@ impl Storage for S3Service {
     async fn download(&self, key: &str) -> Result<Vec<u8>> {
         self.download_file(key).await
     }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<ListedObject>> {
+        self.list_files(prefix).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.delete_file(key).await
+    }
 }
 
 #[cfg(test)]
@@ -66,32 +342,136 @@ mod tests {
     use super::*;
 
     #[tokio::test]
-    async fn github_api_impl_forwards_errors() {
+    async fn github_provider_impl_forwards_errors() {
         let client = GithubClient::new(None, "http://127.0.0.1:1".to_string()).unwrap();
-        let api: &dyn GithubApi = &client;
+        let api: &dyn SourceProvider = &client;
+
+        assert!(api.search_repositories("topic:agent-skill").await.is_err());
+        assert!(api.search_code("path:SKILL.md").await.is_err());
+        assert!(api
+            .clone_repository_files(
+                "acme",
+                "skills",
+                "https://github.com/acme/skills",
+                None,
+                &IngestLimits::default(),
+            )
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn gitlab_provider_impl_forwards_errors() {
+        let client = GitlabClient::new(None, "http://127.0.0.1:1/api/v4".to_string()).unwrap();
+        let api: &dyn SourceProvider = &client;
+
+        assert!(api.search_repositories("topic:agent-skill").await.is_err());
+        assert!(api.search_code("filename:SKILL.md").await.is_err());
+        assert!(api
+            .clone_repository_files(
+                "acme",
+                "skills",
+                "http://127.0.0.1:1/acme/skills",
+                None,
+                &IngestLimits::default(),
+            )
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn gitea_provider_impl_forwards_errors() {
+        let client = GiteaClient::new(None, "http://127.0.0.1:1/api/v1".to_string()).unwrap();
+        let api: &dyn SourceProvider = &client;
 
         assert!(api.search_repositories("topic:agent-skill").await.is_err());
         assert!(api.search_code("path:SKILL.md").await.is_err());
         assert!(api
-            .clone_repository_files("acme", "skills", "https://github.com/acme/skills", None,)
+            .clone_repository_files(
+                "acme",
+                "skills",
+                "http://127.0.0.1:1/acme/skills",
+                None,
+                &IngestLimits::default(),
+            )
             .await
             .is_err());
     }
 
+    #[test]
+    fn parse_search_query_extracts_topics_and_drops_github_qualifiers() {
+        let parsed = parse_search_query("topic:agent-skill fork:false sort:updated");
+        assert_eq!(
+            parsed,
+            SearchQuery {
+                topics: vec!["agent-skill".to_string()],
+                search: None,
+            }
+        );
+
+        let parsed = parse_search_query("claude skills topic:a topic:b");
+        assert_eq!(parsed.topics, vec!["a", "b"]);
+        assert_eq!(parsed.search.as_deref(), Some("claude skills"));
+    }
+
+    #[test]
+    fn default_api_url_matches_platform_conventions() {
+        assert_eq!(
+            default_api_url(&skill_registry::Platform::Github, "github.com"),
+            "https://api.github.com"
+        );
+        assert_eq!(
+            default_api_url(&skill_registry::Platform::Github, "ghe.example.com"),
+            "https://ghe.example.com/api/v3"
+        );
+        assert_eq!(
+            default_api_url(&skill_registry::Platform::Gitlab, "gitlab.example.com"),
+            "https://gitlab.example.com/api/v4"
+        );
+        assert_eq!(
+            default_api_url(&skill_registry::Platform::Gitea, "codeberg.org"),
+            "https://codeberg.org/api/v1"
+        );
+    }
+
     #[tokio::test]
     async fn storage_impl_forwards_errors() {
-        let s3 = common::s3::S3Service::new(
-            "skills".to_string(),
-            "us-east-1".to_string(),
-            Some("http://127.0.0.1:1".to_string()),
-            None,
-            None,
-            true,
-        )
-        .await;
+        let s3 = ObjectStore::S3(
+            common::s3::S3Service::new(
+                "skills".to_string(),
+                "us-east-1".to_string(),
+                Some("http://127.0.0.1:1".to_string()),
+                None,
+                None,
+                true,
+            )
+            .await,
+        );
         let storage: &dyn Storage = &s3;
 
         assert!(storage.download("missing").await.is_err());
         assert!(storage.upload("artifact.zip", vec![1, 2, 3]).await.is_err());
     }
+
+    #[tokio::test]
+    async fn storage_impl_round_trips_through_local_storage() {
+        let root = std::env::temp_dir().join(format!("skillregistry-{}", uuid::Uuid::new_v4()));
+        let local = ObjectStore::Local(common::s3::LocalStorage::new(&root, None, None));
+        let storage: &dyn Storage = &local;
+
+        let url = storage
+            .upload("repo-snapshots/1/a.zip", vec![1, 2, 3])
+            .await
+            .unwrap();
+        assert_eq!(url, "/api/storage/repo-snapshots/1/a.zip");
+        assert_eq!(
+            storage.download("repo-snapshots/1/a.zip").await.unwrap(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(storage.list("repo-snapshots/").await.unwrap().len(), 1);
+        storage.delete("repo-snapshots/1/a.zip").await.unwrap();
+        assert!(storage.list("repo-snapshots/").await.unwrap().is_empty());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
 }
@@ -1,11 +1,16 @@
+use super::tags::TagRelease;
+use crate::git::SourceRevision;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SyncResult {
     pub status: String,
     pub version: Option<String>,
+    /// Problems maintainers should fix in the repository, e.g. reused versions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -16,6 +21,33 @@ pub struct RepoSnapshotRef {
     pub url: String,
     pub zip_hash: String,
     pub snapshot_s3_key: String,
+    #[serde(
+        default,
+        alias = "commit_sha",
+        deserialize_with = "revision_or_commit_sha"
+    )]
+    pub revision: Option<SourceRevision>,
+}
+
+/// Snapshots recorded in workflow histories before revisions were tracked
+/// only carry the commit SHA as a string.
+fn revision_or_commit_sha<'de, D>(deserializer: D) -> Result<Option<SourceRevision>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Compat {
+        Revision(SourceRevision),
+        CommitSha(String),
+    }
+
+    Ok(
+        Option::<Compat>::deserialize(deserializer)?.map(|compat| match compat {
+            Compat::Revision(revision) => revision,
+            Compat::CommitSha(commit_sha) => SourceRevision::from_sha(commit_sha),
+        }),
+    )
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -25,16 +57,43 @@ pub enum SnapshotResult {
     Snapshot(RepoSnapshotRef),
 }
 
+/// Where a file map being synced was checked out from.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SyncOrigin<'a> {
+    pub revision: Option<&'a SourceRevision>,
+    /// Set when indexing a release tag instead of the default branch. Tagged
+    /// versions are immutable.
+    pub release: Option<&'a TagRelease>,
+    /// Paths checked out with an executable bit, relative to the repository
+    /// root. Packaged as 0755.
+    pub executables: Option<&'a BTreeSet<String>>,
+}
+
+impl SyncOrigin<'_> {
+    /// Whether a stored version came from a release tag and must not be overwritten.
+    pub fn is_tagged(git_ref: Option<&str>) -> bool {
+        git_ref.is_some_and(|r| r.starts_with("refs/tags/"))
+    }
+
+    /// Whether this sync must leave a stored version alone. Only a tag sync
+    /// may rebuild a tagged version, and only once its artifact was reset.
+    pub fn keeps_pinned(&self, git_ref: Option<&str>, s3_key: Option<&str>) -> bool {
+        Self::is_tagged(git_ref) && (self.release.is_none() || s3_key.is_some())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SkillSyncOutcome {
     pub changed: bool,
     pub found_any: bool,
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct PluginSyncOutcome {
     pub changed: bool,
     pub plugin_root_prefixes: HashSet<String>,
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -46,3 +105,42 @@ pub struct NewPluginComponent {
     pub markdown_content: String,
     pub metadata: Option<Value>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_ref_accepts_legacy_commit_sha() {
+        let legacy = serde_json::json!({
+            "registry_id": 1,
+            "owner": "acme",
+            "name": "skills",
+            "url": "https://github.com/acme/skills",
+            "zip_hash": "abc",
+            "snapshot_s3_key": "snapshots/1.zip",
+            "commit_sha": "0123456789abcdef",
+        });
+        let snapshot: RepoSnapshotRef = serde_json::from_value(legacy.clone()).unwrap();
+        let revision = snapshot.revision.unwrap();
+        assert_eq!(revision.commit_sha, "0123456789abcdef");
+        assert_eq!(revision.git_ref, None);
+
+        let mut without = legacy.clone();
+        without.as_object_mut().unwrap().remove("commit_sha");
+        let snapshot: RepoSnapshotRef = serde_json::from_value(without).unwrap();
+        assert!(snapshot.revision.is_none());
+
+        let current = RepoSnapshotRef {
+            revision: Some(SourceRevision {
+                commit_sha: "fedcba".to_string(),
+                git_ref: Some("refs/heads/main".to_string()),
+                committed_at: None,
+            }),
+            ..serde_json::from_value(legacy).unwrap()
+        };
+        let round_trip: RepoSnapshotRef =
+            serde_json::from_value(serde_json::to_value(&current).unwrap()).unwrap();
+        assert_eq!(round_trip.revision, current.revision);
+    }
+}
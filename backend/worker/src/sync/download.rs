@@ -1,10 +1,18 @@
+use crate::git::Checkout;
+use crate::ingest::{check_compression_ratio, read_bounded, IngestBudget};
 use anyhow::Result;
-use std::collections::{BTreeMap, HashSet};
-use std::io::{Cursor, Read};
+use common::settings::IngestLimits;
+use std::collections::HashSet;
+use std::io::Cursor;
 use zip::ZipArchive;
 
-pub fn zip_to_file_map(zip_data: &[u8]) -> Result<BTreeMap<String, Vec<u8>>> {
+/// Files in a repository zip, with a single top-level directory (as in a
+/// GitHub zipball) stripped. Unix executable bits are kept. Declared sizes
+/// are checked against `limits` before anything is inflated, and actual sizes
+/// while inflating.
+pub fn zip_to_checkout(zip_data: &[u8], limits: &IngestLimits) -> Result<Checkout> {
     let mut archive = ZipArchive::new(Cursor::new(zip_data))?;
+    let mut declared = IngestBudget::new(limits);
     let mut saw_no_slash = false;
     let mut first_segments: HashSet<String> = HashSet::new();
     for i in 0..archive.len() {
@@ -13,6 +21,7 @@ pub fn zip_to_file_map(zip_data: &[u8]) -> Result<BTreeMap<String, Vec<u8>>> {
             continue;
         }
         let name = file.name();
+        declared.admit(name, file.size())?;
         if let Some((first, _)) = name.split_once('/') {
             first_segments.insert(first.to_string());
         } else {
@@ -26,8 +35,11 @@ pub fn zip_to_file_map(zip_data: &[u8]) -> Result<BTreeMap<String, Vec<u8>>> {
         None
     };
 
+    check_compression_ratio(zip_data.len() as u64, declared.bytes(), limits)?;
+
     let mut archive = ZipArchive::new(Cursor::new(zip_data))?;
-    let mut files = BTreeMap::new();
+    let mut inflated = IngestBudget::new(limits);
+    let mut checkout = Checkout::default();
     for i in 0..archive.len() {
         let mut file = archive.by_index(i)?;
         if file.is_dir() {
@@ -49,17 +61,21 @@ pub fn zip_to_file_map(zip_data: &[u8]) -> Result<BTreeMap<String, Vec<u8>>> {
             continue;
         }
 
-        let mut content = Vec::new();
-        file.read_to_end(&mut content)?;
-        files.insert(rel_name, content);
+        if file.unix_mode().is_some_and(|mode| mode & 0o111 != 0) {
+            checkout.executables.insert(rel_name.clone());
+        }
+        let content = read_bounded(&mut file, &rel_name, limits.max_file_bytes)?;
+        inflated.admit(&rel_name, content.len() as u64)?;
+        checkout.files.insert(rel_name, content);
     }
 
-    Ok(files)
+    Ok(checkout)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ingest::IngestLimitError;
     use std::io::Write;
     use zip::write::FileOptions;
 
@@ -83,7 +99,7 @@ mod tests {
             "repo-sha/skill-a/SKILL.md",
             b"---\nname: a\ndescription: b\n---\n",
         )]);
-        let m = zip_to_file_map(&zip)?;
+        let m = zip_to_checkout(&zip, &IngestLimits::default())?.files;
         assert!(m.contains_key("skill-a/SKILL.md"));
         assert!(!m.contains_key("repo-sha/skill-a/SKILL.md"));
         Ok(())
@@ -92,9 +108,65 @@ mod tests {
     #[test]
     fn does_not_strip_when_multiple_roots_present() -> Result<()> {
         let zip = create_zip(vec![("a/file.txt", b"x"), ("b/file.txt", b"y")]);
-        let m = zip_to_file_map(&zip)?;
+        let m = zip_to_checkout(&zip, &IngestLimits::default())?.files;
         assert!(m.contains_key("a/file.txt"));
         assert!(m.contains_key("b/file.txt"));
         Ok(())
     }
+
+    #[test]
+    fn keeps_executable_bits_from_packaged_snapshots() -> Result<()> {
+        let mut files = std::collections::BTreeMap::new();
+        files.insert("SKILL.md".to_string(), b"# Demo".to_vec());
+        files.insert("scripts/run.sh".to_string(), b"#!/bin/sh".to_vec());
+        let executables = std::collections::BTreeSet::from(["scripts/run.sh".to_string()]);
+        let zip = common::domain::archive::package_zip_with_executables(&files, &executables)?;
+
+        let checkout = zip_to_checkout(&zip, &IngestLimits::default())?;
+        assert_eq!(checkout.files, files);
+        assert_eq!(checkout.executables, executables);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_archives_over_ingest_limits() -> Result<()> {
+        let zip = create_zip(vec![
+            ("a/file.txt", &b"x"[..]),
+            ("b/big.txt", &[b'y'; 64][..]),
+        ]);
+        let limits = IngestLimits {
+            max_file_bytes: 32,
+            ..IngestLimits::default()
+        };
+        let err = zip_to_checkout(&zip, &limits).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<IngestLimitError>(),
+            Some(&IngestLimitError::FileTooLarge {
+                path: "b/big.txt".to_string(),
+                limit: 32,
+            })
+        );
+
+        let limits = IngestLimits {
+            max_files: 1,
+            ..IngestLimits::default()
+        };
+        assert!(zip_to_checkout(&zip, &limits).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_zip_bombs() -> Result<()> {
+        let mut files = std::collections::BTreeMap::new();
+        files.insert("a/zeros.bin".to_string(), vec![0u8; 1024 * 1024]);
+        files.insert("b/file.txt".to_string(), b"x".to_vec());
+        let zip = common::domain::archive::package_zip(&files)?;
+
+        let err = zip_to_checkout(&zip, &IngestLimits::default()).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<IngestLimitError>(),
+            Some(&IngestLimitError::CompressionRatio { limit: 100 })
+        );
+        Ok(())
+    }
 }
@@ -1,11 +1,19 @@
-use super::domain::{NewPluginComponent, PluginSyncOutcome};
+use super::domain::{NewPluginComponent, PluginSyncOutcome, SyncOrigin};
+use super::publish::{publish_package, PublishedPackage};
 use super::utils::{
-    compute_hash, json_string, normalize_dir_prefix, package_skill, parse_boolish,
-    parse_markdown_frontmatter, subtree_file_map,
+    compute_hash, compute_package_hash, json_string, normalize_dir_prefix, package_skill,
+    parse_boolish, parse_markdown_frontmatter, rebase_paths, subtree_file_map,
 };
+use crate::git::SourceRevision;
 use crate::ports::Storage;
 use anyhow::Result;
-use common::entities::{plugin_components, plugin_versions, plugins, prelude::*, skill_registry};
+use common::domain::artifact::{artifact_key, registry_locator, ArtifactKind};
+use common::domain::signing::{PackageSignature, PackageSigner};
+use common::domain::version::select_latest;
+use common::entities::{
+    plugin_components, plugin_version_revisions, plugin_versions, plugins, prelude::*,
+    skill_registry,
+};
 use sea_orm::*;
 use serde_json::Value;
 use std::collections::{BTreeMap, HashMap, HashSet};
@@ -13,8 +21,10 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 pub async fn sync_marketplace_plugins(
     db: &sea_orm::DatabaseConnection,
     s3: &dyn Storage,
+    signer: Option<&PackageSigner>,
     repo: &skill_registry::Model,
     all_files: &BTreeMap<String, Vec<u8>>,
+    origin: SyncOrigin<'_>,
     marketplace: &Value,
 ) -> Result<PluginSyncOutcome> {
     let plugin_entries = marketplace
@@ -24,8 +34,10 @@ pub async fn sync_marketplace_plugins(
         .unwrap_or_default();
 
     let mut changed = false;
+    let mut warnings = Vec::new();
     let mut seen_plugin_names = HashSet::new();
     let mut plugin_root_prefixes = HashSet::new();
+    let revision = origin.revision;
 
     for entry in plugin_entries {
         let plugin_name = match entry.get("name").and_then(|v| v.as_str()) {
@@ -41,15 +53,22 @@ pub async fn sync_marketplace_plugins(
             .cloned()
             .unwrap_or(Value::String("./".to_string()));
 
-        let plugin = upsert_plugin(
-            db,
-            repo.id,
-            &plugin_name,
-            description.clone(),
-            source_value.clone(),
-            strict,
-        )
-        .await?;
+        // Tagged trees may predate the default branch, so they never rewrite
+        // plugin rows; those are resolved below once the tag scope is known.
+        let upserted = match origin.release {
+            None => Some(
+                upsert_plugin(
+                    db,
+                    repo.id,
+                    &plugin_name,
+                    description.clone(),
+                    source_value.clone(),
+                    strict,
+                )
+                .await?,
+            ),
+            Some(_) => None,
+        };
 
         let source_str = match source_value.as_str() {
             Some(s) => s.to_string(),
@@ -100,6 +119,12 @@ pub async fn sync_marketplace_plugins(
             }
         }
 
+        if let Some(scope) = origin.release.and_then(|r| r.name.as_deref()) {
+            if scope != plugin_name {
+                continue;
+            }
+        }
+
         let plugin_files = subtree_file_map(all_files, &plugin_root);
         let content_hash = compute_hash(&plugin_files);
         let plugin_prefix = plugin_name.trim_matches('/');
@@ -115,11 +140,44 @@ pub async fn sync_marketplace_plugins(
             };
             prefixed_plugin_files.insert(prefixed_path, bytes);
         }
-        let package_hash = compute_hash(&prefixed_plugin_files);
+        let executables = origin
+            .executables
+            .map(|paths| rebase_paths(paths, &plugin_root, plugin_prefix))
+            .unwrap_or_default();
+        let package_hash = compute_package_hash(&prefixed_plugin_files, &executables);
         let derived_patch = u32::from_str_radix(&content_hash[..8], 16).unwrap_or(0);
-        let version_str = json_string(manifest.get("version"))
-            .or_else(|| json_string(entry.get("version")))
-            .unwrap_or_else(|| format!("0.0.{}", derived_patch));
+        let version_str = match origin.release {
+            Some(release) => release.version.clone(),
+            None => json_string(manifest.get("version"))
+                .or_else(|| json_string(entry.get("version")))
+                .unwrap_or_else(|| format!("0.0.{}", derived_patch)),
+        };
+
+        let plugin = match upserted {
+            Some(plugin) => plugin,
+            None => {
+                find_or_insert_tagged_plugin(
+                    db,
+                    repo.id,
+                    &plugin_name,
+                    description.clone(),
+                    source_value.clone(),
+                    strict,
+                    &version_str,
+                )
+                .await?
+            }
+        };
+        // Only the default branch moves the `main` channel.
+        let plugin = if origin.release.is_none()
+            && plugin.head_version.as_deref() != Some(version_str.as_str())
+        {
+            let mut active: plugins::ActiveModel = plugin.into();
+            active.head_version = Set(Some(version_str.clone()));
+            active.update(db).await?
+        } else {
+            plugin
+        };
 
         let existing_version = PluginVersions::find()
             .filter(plugin_versions::Column::PluginId.eq(plugin.id))
@@ -127,19 +185,86 @@ pub async fn sync_marketplace_plugins(
             .one(db)
             .await?;
 
-        let unchanged = existing_version
+        if existing_version
             .as_ref()
-            .and_then(|v| v.file_hash.as_ref())
-            .map(|h| h == &package_hash)
-            .unwrap_or(false);
+            .is_some_and(|v| origin.keeps_pinned(v.git_ref.as_deref(), v.s3_key.as_deref()))
+        {
+            tracing::info!(
+                "Plugin '{}' version {} is pinned to a release tag; skipping",
+                plugin_name,
+                version_str
+            );
+            continue;
+        }
+
+        if let Some(released) = existing_version.as_ref().filter(|v| {
+            v.file_hash
+                .as_ref()
+                .is_some_and(|hash| hash != &package_hash)
+        }) {
+            // Hashes recorded before executable bits were hashed only differ in
+            // format; their new zip is kept as a revision without flagging reuse.
+            let legacy_format = released.file_hash.as_deref()
+                == Some(compute_hash(&prefixed_plugin_files).as_str());
+            let number = match find_revision(db, released.id, &package_hash).await? {
+                Some(recorded) => recorded.revision,
+                None => {
+                    let zip = package_skill(&prefixed_plugin_files, &executables)?;
+                    let s3_key = artifact_key(
+                        ArtifactKind::Plugin,
+                        &registry_locator(repo),
+                        &plugin_name,
+                        &package_hash,
+                    );
+                    let package = publish_package(s3, signer, &s3_key, zip).await?;
+                    changed = true;
+                    insert_revision(db, released.id, &package_hash, s3_key, package, revision)
+                        .await?
+                }
+            };
+            if legacy_format {
+                continue;
+            }
+            flag_reused_version(db, plugin.id, &version_str).await?;
+            let warning = format!(
+                "Plugin '{}' reuses version {} for different content (build revision {}); bump the version to publish it",
+                plugin_name, version_str, number
+            );
+            tracing::warn!(repo_id = repo.id, "{}", warning);
+            warnings.push(warning);
+            continue;
+        }
+
+        // Released versions are immutable, so the only remaining case is
+        // identical content; a tag claims it from the default branch.
+        let unchanged = origin.release.is_none()
+            && existing_version
+                .as_ref()
+                .and_then(|v| v.file_hash.as_ref())
+                .map(|h| h == &package_hash)
+                .unwrap_or(false);
 
         if unchanged {
             continue;
         }
 
-        let new_zip_buffer = package_skill(&prefixed_plugin_files)?;
-        let s3_key = format!("plugins/{}/{}.zip", plugin_name, version_str);
-        let oss_url = s3.upload(&s3_key, new_zip_buffer).await?;
+        let new_zip_buffer = package_skill(&prefixed_plugin_files, &executables)?;
+        let file_manifest = serde_json::to_value(common::domain::manifest::file_manifest(
+            &prefixed_plugin_files,
+        ))?;
+        let s3_key = artifact_key(
+            ArtifactKind::Plugin,
+            &registry_locator(repo),
+            &plugin_name,
+            &package_hash,
+        );
+        let PublishedPackage {
+            oss_url,
+            digest,
+            signature,
+        } = publish_package(s3, signer, &s3_key, new_zip_buffer).await?;
+        let signature_key_id = signature.as_ref().map(|s| s.key_id.clone());
+        let signature = signature.as_ref().map(PackageSignature::to_base64);
 
         let metadata = serde_json::json!({
             "marketplace_entry": entry,
@@ -148,6 +273,11 @@ pub async fn sync_marketplace_plugins(
             "source": source_value,
             "strict": strict,
         });
+        let source_path = if plugin_root.is_empty() {
+            ".".to_string()
+        } else {
+            plugin_root.clone()
+        };
 
         let txn = db.begin().await?;
         let plugin_version_id = if let Some(v) = existing_version {
@@ -157,7 +287,16 @@ pub async fn sync_marketplace_plugins(
             active.s3_key = Set(Some(s3_key));
             active.oss_url = Set(Some(oss_url));
             active.file_hash = Set(Some(package_hash));
+            active.sha256 = Set(Some(digest.sha256));
+            active.file_size = Set(Some(digest.size));
+            active.signature = Set(signature);
+            active.signature_key_id = Set(signature_key_id);
             active.metadata = Set(Some(metadata));
+            active.file_manifest = Set(Some(file_manifest));
+            active.commit_sha = Set(revision.map(|r| r.commit_sha.clone()));
+            active.git_ref = Set(revision.and_then(|r| r.git_ref.clone()));
+            active.committed_at = Set(revision.and_then(|r| r.committed_at));
+            active.source_path = Set(Some(source_path));
             active.update(&txn).await?.id
         } else {
             let new_version = plugin_versions::ActiveModel {
@@ -168,7 +307,16 @@ pub async fn sync_marketplace_plugins(
                 s3_key: Set(Some(s3_key)),
                 oss_url: Set(Some(oss_url)),
                 file_hash: Set(Some(package_hash)),
+                sha256: Set(Some(digest.sha256)),
+                file_size: Set(Some(digest.size)),
+                signature: Set(signature),
+                signature_key_id: Set(signature_key_id),
                 metadata: Set(Some(metadata)),
+                file_manifest: Set(Some(file_manifest)),
+                commit_sha: Set(revision.map(|r| r.commit_sha.clone())),
+                git_ref: Set(revision.and_then(|r| r.git_ref.clone())),
+                committed_at: Set(revision.and_then(|r| r.committed_at)),
+                source_path: Set(Some(source_path)),
                 created_at: Set(chrono::Utc::now().naive_utc()),
                 ..Default::default()
             };
@@ -203,19 +351,32 @@ pub async fn sync_marketplace_plugins(
                 .await?;
         }
 
-        let mut plugin_active: plugins::ActiveModel = plugin.into();
-        plugin_active.latest_version = Set(Some(version_str));
-        plugin_active.updated_at = Set(chrono::Utc::now().naive_utc());
-        let _ = plugin_active.update(&txn).await?;
+        let latest_version = resolve_latest_version(&txn, plugin.id, &version_str).await?;
+        if origin.release.is_none() || plugin.latest_version.as_ref() != Some(&latest_version) {
+            let reuse_resolved = plugin.reused_version.is_some()
+                && plugin.reused_version.as_ref() != Some(&latest_version);
+            let mut plugin_active: plugins::ActiveModel = plugin.into();
+            if reuse_resolved {
+                plugin_active.reused_version = Set(None);
+            }
+            plugin_active.latest_version = Set(Some(latest_version));
+            plugin_active.updated_at = Set(chrono::Utc::now().naive_utc());
+            let _ = plugin_active.update(&txn).await?;
+        }
         txn.commit().await?;
 
         changed = true;
     }
 
-    let existing_plugins = Plugins::find()
-        .filter(plugins::Column::SkillRegistryId.eq(repo.id))
-        .all(db)
-        .await?;
+    let existing_plugins = match origin.release {
+        None => {
+            Plugins::find()
+                .filter(plugins::Column::SkillRegistryId.eq(repo.id))
+                .all(db)
+                .await?
+        }
+        Some(_) => Vec::new(),
+    };
     for p in existing_plugins {
         if !seen_plugin_names.contains(&p.name) && p.is_active != 0 {
             let mut active: plugins::ActiveModel = p.into();
@@ -228,9 +389,97 @@ pub async fn sync_marketplace_plugins(
     Ok(PluginSyncOutcome {
         changed,
         plugin_root_prefixes,
+        warnings,
     })
 }
 
+// Derived `0.0.<hash>` versions only become latest when the plugin has never
+// declared a version; otherwise the highest declared release wins.
+async fn resolve_latest_version(
+    db: &impl ConnectionTrait,
+    plugin_id: i32,
+    current: &str,
+) -> Result<String> {
+    let versions = PluginVersions::find()
+        .filter(plugin_versions::Column::PluginId.eq(plugin_id))
+        .all(db)
+        .await?;
+    let declared = versions
+        .iter()
+        .filter(|v| is_declared_version(v))
+        .map(|v| v.version.as_str());
+    Ok(select_latest(declared).unwrap_or(current).to_string())
+}
+
+async fn find_revision(
+    db: &DatabaseConnection,
+    plugin_version_id: i32,
+    file_hash: &str,
+) -> Result<Option<plugin_version_revisions::Model>> {
+    Ok(PluginVersionRevisions::find()
+        .filter(plugin_version_revisions::Column::PluginVersionId.eq(plugin_version_id))
+        .filter(plugin_version_revisions::Column::FileHash.eq(file_hash))
+        .one(db)
+        .await?)
+}
+
+async fn insert_revision(
+    db: &DatabaseConnection,
+    plugin_version_id: i32,
+    file_hash: &str,
+    s3_key: String,
+    package: PublishedPackage,
+    revision: Option<&SourceRevision>,
+) -> Result<i32> {
+    let recorded = PluginVersionRevisions::find()
+        .filter(plugin_version_revisions::Column::PluginVersionId.eq(plugin_version_id))
+        .count(db)
+        .await?;
+    // Revision 1 is the released version row itself.
+    let number = recorded as i32 + 2;
+    plugin_version_revisions::ActiveModel {
+        plugin_version_id: Set(plugin_version_id),
+        revision: Set(number),
+        file_hash: Set(file_hash.to_string()),
+        s3_key: Set(Some(s3_key)),
+        oss_url: Set(Some(package.oss_url)),
+        sha256: Set(Some(package.digest.sha256)),
+        file_size: Set(Some(package.digest.size)),
+        signature: Set(package.signature.as_ref().map(PackageSignature::to_base64)),
+        signature_key_id: Set(package.signature.map(|s| s.key_id)),
+        commit_sha: Set(revision.map(|r| r.commit_sha.clone())),
+        git_ref: Set(revision.and_then(|r| r.git_ref.clone())),
+        committed_at: Set(revision.and_then(|r| r.committed_at)),
+        created_at: Set(chrono::Utc::now().naive_utc()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok(number)
+}
+
+async fn flag_reused_version(db: &DatabaseConnection, plugin_id: i32, version: &str) -> Result<()> {
+    plugins::ActiveModel {
+        id: Set(plugin_id),
+        reused_version: Set(Some(version.to_string())),
+        ..Default::default()
+    }
+    .update(db)
+    .await?;
+    Ok(())
+}
+
+fn is_declared_version(version: &plugin_versions::Model) -> bool {
+    if SyncOrigin::is_tagged(version.git_ref.as_deref()) {
+        return true;
+    }
+    let Some(metadata) = version.metadata.as_ref() else {
+        return false;
+    };
+    json_string(metadata.pointer("/resolved_manifest/version")).is_some()
+        || json_string(metadata.pointer("/marketplace_entry/version")).is_some()
+}
+
 async fn upsert_plugin(
     db: &DatabaseConnection,
     registry_id: i32,
@@ -269,6 +518,41 @@ async fn upsert_plugin(
     }
 }
 
+// A plugin only seen in a release tag is recorded inactive so its tagged
+// versions stay reachable without surfacing it in listings.
+async fn find_or_insert_tagged_plugin(
+    db: &DatabaseConnection,
+    registry_id: i32,
+    name: &str,
+    description: Option<String>,
+    source: Value,
+    strict: bool,
+    version: &str,
+) -> Result<plugins::Model> {
+    if let Some(existing) = Plugins::find()
+        .filter(plugins::Column::SkillRegistryId.eq(registry_id))
+        .filter(plugins::Column::Name.eq(name))
+        .one(db)
+        .await?
+    {
+        return Ok(existing);
+    }
+
+    let active = plugins::ActiveModel {
+        skill_registry_id: Set(registry_id),
+        name: Set(name.to_string()),
+        description: Set(description),
+        source: Set(Some(source)),
+        strict: Set(if strict { 1 } else { 0 }),
+        latest_version: Set(Some(version.to_string())),
+        is_active: Set(0),
+        created_at: Set(chrono::Utc::now().naive_utc()),
+        updated_at: Set(chrono::Utc::now().naive_utc()),
+        ..Default::default()
+    };
+    Ok(active.insert(db).await?)
+}
+
 fn collect_plugin_components(
     all_files: &BTreeMap<String, Vec<u8>>,
     plugin_root: &str,
@@ -613,7 +897,9 @@ fn parse_component_file(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::git::SourceRevision;
     use crate::ports::MockStorage;
+    use crate::sync::tags::TagRelease;
     use common::entities::{plugin_components, plugin_versions, plugins, skill_registry};
     use migration::MigratorTrait;
     use sea_orm::{
@@ -837,14 +1123,26 @@ mod tests {
 
         let mut storage = MockStorage::new();
         storage.expect_upload().times(1).returning(|key, body| {
-            assert!(key.starts_with("plugins/alpha/1.2.3.zip"));
+            assert!(key.starts_with("plugins/github.com/acme/"));
+            assert!(key.contains("/alpha/"));
             assert!(!body.is_empty());
             Ok(format!("https://oss.local/{key}"))
         });
 
-        let first = sync_marketplace_plugins(&db, &storage, &repo, &files, &marketplace)
-            .await
-            .unwrap();
+        let revision = SourceRevision {
+            commit_sha: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            git_ref: Some("refs/heads/main".to_string()),
+            committed_at: None,
+        };
+        let origin = SyncOrigin {
+            revision: Some(&revision),
+            release: None,
+            executables: None,
+        };
+        let first =
+            sync_marketplace_plugins(&db, &storage, None, &repo, &files, origin, &marketplace)
+                .await
+                .unwrap();
         assert!(first.changed);
         assert!(first
             .plugin_root_prefixes
@@ -852,9 +1150,10 @@ mod tests {
         assert!(first.plugin_root_prefixes.contains("plugins/alpha/agents"));
         assert!(first.plugin_root_prefixes.contains("plugins/alpha/skills"));
 
-        let second = sync_marketplace_plugins(&db, &storage, &repo, &files, &marketplace)
-            .await
-            .unwrap();
+        let second =
+            sync_marketplace_plugins(&db, &storage, None, &repo, &files, origin, &marketplace)
+                .await
+                .unwrap();
         assert!(!second.changed);
 
         let plugin = Plugins::find()
@@ -874,6 +1173,14 @@ mod tests {
             .unwrap();
         assert_eq!(version.version, "1.2.3");
         assert!(version.metadata.is_some());
+        assert_eq!(
+            version.commit_sha.as_deref(),
+            Some(revision.commit_sha.as_str())
+        );
+        assert_eq!(version.git_ref.as_deref(), Some("refs/heads/main"));
+        assert_eq!(version.source_path.as_deref(), Some("plugins/alpha"));
+        assert_eq!(version.sha256.as_ref().map(String::len), Some(64));
+        assert!(version.file_size.is_some_and(|size| size > 0));
 
         let components = PluginComponents::find()
             .filter(plugin_components::Column::PluginVersionId.eq(version.id))
@@ -883,6 +1190,197 @@ mod tests {
         assert_eq!(components.len(), 3);
     }
 
+    #[tokio::test]
+    async fn sync_marketplace_plugins_indexes_scoped_tags_without_moving_latest() {
+        let db = setup_db().await;
+        let repo = insert_registry(&db, "tagged-market").await;
+
+        let marketplace = serde_json::json!({
+            "plugins": [
+                {"name": "alpha", "description": "Alpha plugin", "source": "plugins/alpha"}
+            ]
+        });
+        let files = file_map(&[
+            (
+                "plugins/alpha/.claude-plugin/plugin.json",
+                &serde_json::json!({"name": "alpha", "version": "1.2.3"}).to_string(),
+            ),
+            ("plugins/alpha/commands/hello.md", &markdown("hello", "cmd")),
+        ]);
+
+        let mut storage = MockStorage::new();
+        storage
+            .expect_upload()
+            .times(2)
+            .returning(|key, _| Ok(format!("https://oss.local/{key}")));
+
+        sync_marketplace_plugins(
+            &db,
+            &storage,
+            None,
+            &repo,
+            &files,
+            SyncOrigin::default(),
+            &marketplace,
+        )
+        .await
+        .unwrap();
+
+        let release = TagRelease {
+            tag: "alpha@1.0.0".to_string(),
+            name: Some("alpha".to_string()),
+            version: "1.0.0".to_string(),
+        };
+        let revision = SourceRevision {
+            commit_sha: "89abcdef0123456789abcdef0123456789abcdef".to_string(),
+            git_ref: Some("refs/tags/alpha@1.0.0".to_string()),
+            committed_at: None,
+        };
+        let tagged = sync_marketplace_plugins(
+            &db,
+            &storage,
+            None,
+            &repo,
+            &files,
+            SyncOrigin {
+                revision: Some(&revision),
+                release: Some(&release),
+                executables: None,
+            },
+            &marketplace,
+        )
+        .await
+        .unwrap();
+        assert!(tagged.changed);
+        assert!(tagged
+            .plugin_root_prefixes
+            .contains("plugins/alpha/commands"));
+
+        let other = TagRelease {
+            tag: "beta@1.0.0".to_string(),
+            name: Some("beta".to_string()),
+            version: "1.0.0".to_string(),
+        };
+        let skipped = sync_marketplace_plugins(
+            &db,
+            &storage,
+            None,
+            &repo,
+            &files,
+            SyncOrigin {
+                revision: None,
+                release: Some(&other),
+                executables: None,
+            },
+            &marketplace,
+        )
+        .await
+        .unwrap();
+        assert!(!skipped.changed);
+
+        let plugin = Plugins::find()
+            .filter(plugins::Column::SkillRegistryId.eq(repo.id))
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(plugin.latest_version.as_deref(), Some("1.2.3"));
+        assert_eq!(plugin.head_version.as_deref(), Some("1.2.3"));
+        assert_eq!(plugin.is_active, 1);
+
+        let version = PluginVersions::find()
+            .filter(plugin_versions::Column::PluginId.eq(plugin.id))
+            .filter(plugin_versions::Column::Version.eq("1.0.0"))
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(version.git_ref.as_deref(), Some("refs/tags/alpha@1.0.0"));
+    }
+
+    #[tokio::test]
+    async fn sync_marketplace_plugins_keeps_released_versions_immutable() {
+        let db = setup_db().await;
+        let repo = insert_registry(&db, "reuse-market").await;
+
+        let marketplace = serde_json::json!({
+            "plugins": [{"name": "alpha", "source": "plugins/alpha"}]
+        });
+        let manifest =
+            |version: &str| serde_json::json!({"name": "alpha", "version": version}).to_string();
+        let released = file_map(&[
+            (
+                "plugins/alpha/.claude-plugin/plugin.json",
+                &manifest("1.0.0"),
+            ),
+            ("plugins/alpha/commands/hello.md", &markdown("hello", "cmd")),
+        ]);
+        let rebuilt = file_map(&[
+            (
+                "plugins/alpha/.claude-plugin/plugin.json",
+                &manifest("1.0.0"),
+            ),
+            (
+                "plugins/alpha/commands/hello.md",
+                &markdown("hello", "changed"),
+            ),
+        ]);
+        let bumped = file_map(&[
+            (
+                "plugins/alpha/.claude-plugin/plugin.json",
+                &manifest("1.1.0"),
+            ),
+            (
+                "plugins/alpha/commands/hello.md",
+                &markdown("hello", "changed"),
+            ),
+        ]);
+
+        let mut storage = MockStorage::new();
+        storage
+            .expect_upload()
+            .times(3)
+            .returning(|key, _| Ok(format!("https://oss.local/{key}")));
+        let sync = |files| {
+            sync_marketplace_plugins(
+                &db,
+                &storage,
+                None,
+                &repo,
+                files,
+                SyncOrigin::default(),
+                &marketplace,
+            )
+        };
+
+        sync(&released).await.unwrap();
+        let original = PluginVersions::find().one(&db).await.unwrap().unwrap();
+
+        let reused = sync(&rebuilt).await.unwrap();
+        assert!(reused.changed);
+        assert_eq!(reused.warnings.len(), 1);
+        assert!(reused.warnings[0].contains("reuses version 1.0.0"));
+        let again = sync(&rebuilt).await.unwrap();
+        assert!(!again.changed);
+        assert!(again.warnings[0].contains("build revision 2"));
+
+        let version = PluginVersions::find().one(&db).await.unwrap().unwrap();
+        assert_eq!(version, original);
+        let revisions = PluginVersionRevisions::find().all(&db).await.unwrap();
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].plugin_version_id, original.id);
+        assert_eq!(revisions[0].revision, 2);
+        assert_ne!(revisions[0].s3_key, original.s3_key);
+        assert_eq!(revisions[0].sha256.as_ref().map(String::len), Some(64));
+        let plugin = Plugins::find().one(&db).await.unwrap().unwrap();
+        assert_eq!(plugin.reused_version.as_deref(), Some("1.0.0"));
+
+        sync(&bumped).await.unwrap();
+        let plugin = Plugins::find().one(&db).await.unwrap().unwrap();
+        assert_eq!(plugin.latest_version.as_deref(), Some("1.1.0"));
+        assert_eq!(plugin.reused_version, None);
+    }
+
     #[tokio::test]
     async fn sync_marketplace_plugins_deactivates_removed_plugins() {
         let db = setup_db().await;
@@ -907,8 +1405,10 @@ mod tests {
         let outcome = sync_marketplace_plugins(
             &db,
             &MockStorage::new(),
+            None,
             &repo,
             &BTreeMap::new(),
+            SyncOrigin::default(),
             &serde_json::json!({"plugins": []}),
         )
         .await
@@ -1,40 +1,57 @@
 pub mod domain;
 pub mod download;
 pub mod marketplace;
+pub mod publish;
 pub mod standalone;
+pub mod tags;
 pub mod utils;
 
-use self::download::zip_to_file_map;
+use self::domain::SyncOrigin;
+use self::download::zip_to_checkout;
 use self::marketplace::sync_marketplace_plugins;
 use self::standalone::sync_standalone_skills;
-use crate::ports::{GithubApi, Storage};
+use self::tags::{match_tag, TagRelease};
+use crate::git::{Checkout, SourceRevision};
+use crate::ingest::{lfs_pointer_warning, IngestLimitError};
+use crate::ports::{build_source_provider, default_api_url, SourceProvider, Storage};
 use anyhow::Result;
 use common::domain::archive;
+use common::domain::signing::PackageSigner;
 use common::entities::{
     blacklist,
     prelude::{Blacklist, SkillRegistry},
     skill_registry,
 };
+use common::infra::github_http::{ensure_rate_limit_budget, RateLimitResource};
+use common::repositories::eq_ignore_case;
+use common::settings::IngestLimits;
 use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
 use serde_json::Value;
 use std::collections::HashSet;
 
 pub use self::domain::{SnapshotResult, SyncResult};
 
+/// Bounds the clones a single sync spends on tags; the rest are picked up by
+/// later syncs.
+const MAX_TAGS_PER_SYNC: usize = 10;
+
 pub struct SyncService {
     db: sea_orm::DatabaseConnection,
     s3: std::sync::Arc<dyn Storage>,
-    github: std::sync::Arc<dyn GithubApi>,
+    source: std::sync::Arc<dyn SourceProvider>,
     registry_service: std::sync::Arc<dyn common::services::registry::RegistryService>,
     discovery_registry_service:
         std::sync::Arc<dyn common::services::discovery_registries::DiscoveryRegistryService>,
+    tag_patterns: Vec<String>,
+    signer: Option<std::sync::Arc<PackageSigner>>,
+    ingest_limits: IngestLimits,
 }
 
 impl SyncService {
     pub fn new(
         db: sea_orm::DatabaseConnection,
         s3: std::sync::Arc<dyn Storage>,
-        github: std::sync::Arc<dyn GithubApi>,
+        source: std::sync::Arc<dyn SourceProvider>,
         registry_service: std::sync::Arc<dyn common::services::registry::RegistryService>,
         discovery_registry_service: std::sync::Arc<
             dyn common::services::discovery_registries::DiscoveryRegistryService,
@@ -43,12 +60,31 @@ impl SyncService {
         Self {
             db,
             s3,
-            github,
+            source,
             registry_service,
             discovery_registry_service,
+            tag_patterns: common::settings::default_tag_patterns(),
+            signer: None,
+            ingest_limits: IngestLimits::default(),
         }
     }
 
+    pub fn with_tag_patterns(mut self, tag_patterns: Vec<String>) -> Self {
+        self.tag_patterns = tag_patterns;
+        self
+    }
+
+    /// Signs every uploaded package and stores the signature next to it.
+    pub fn with_signer(mut self, signer: Option<std::sync::Arc<PackageSigner>>) -> Self {
+        self.signer = signer;
+        self
+    }
+
+    pub fn with_ingest_limits(mut self, ingest_limits: IngestLimits) -> Self {
+        self.ingest_limits = ingest_limits;
+        self
+    }
+
     pub async fn fetch_pending(&self) -> Result<Vec<i32>> {
         let expiry_date = chrono::Utc::now().naive_utc() - chrono::Duration::days(30);
         self.registry_service
@@ -78,11 +114,12 @@ impl SyncService {
             return Ok(SyncResult {
                 status: "SkippedBlacklisted".to_string(),
                 version: None,
+                warnings: Vec::new(),
             });
         }
 
         let is_blacklisted = Blacklist::find()
-            .filter(blacklist::Column::RepositoryUrl.eq(&repo.url))
+            .filter(eq_ignore_case(blacklist::Column::RepositoryUrl, &repo.url))
             .one(&self.db)
             .await?
             .is_some();
@@ -98,18 +135,198 @@ impl SyncService {
             return Ok(SyncResult {
                 status: "SkippedBlacklisted".to_string(),
                 version: None,
+                warnings: Vec::new(),
             });
         }
 
-        let token = self.resolve_repo_token(&repo).await?;
-        let file_map = self
-            .github
-            .clone_repository_files(&repo.owner, &repo.name, &repo.url, token)
-            .await?;
+        let (source, token) = self.resolve_repo_source(&repo).await?;
+        let head_sha = self
+            .remote_head_sha(source.as_ref(), &repo, token.clone())
+            .await;
+        let mut result = if is_head_unchanged(&repo, head_sha.as_deref()) {
+            tracing::info!(repo_id = repo.id, "Remote HEAD unchanged, skipping clone");
+            SyncResult {
+                status: "Unchanged".to_string(),
+                version: None,
+                warnings: Vec::new(),
+            }
+        } else {
+            let checkout = match source
+                .clone_repository_files(
+                    &repo.owner,
+                    &repo.name,
+                    &repo.url,
+                    token,
+                    &self.ingest_limits,
+                )
+                .await
+            {
+                Ok(checkout) => checkout,
+                Err(e) => match e.downcast_ref::<IngestLimitError>() {
+                    Some(limit) => return self.blacklist(&repo, limit.to_string()).await,
+                    None => return Err(e),
+                },
+            };
+            let revision = checkout
+                .revision
+                .or_else(|| head_sha.map(SourceRevision::from_sha));
+
+            let result = self
+                .sync_from_file_map(&repo, &checkout, revision.as_ref())
+                .await;
+            tracing::info!("sync_from_file_map result (from git clone): {:?}", result);
+            result?
+        };
+
+        if result.status == "Blacklisted" {
+            return Ok(result);
+        }
+        match self.index_tags(registry_id).await {
+            Ok(tags) => {
+                if tags.status == "Updated" {
+                    result.status = tags.status;
+                }
+                result.warnings.extend(tags.warnings);
+            }
+            Err(e) => tracing::warn!(repo_id = repo.id, "Failed to index release tags: {}", e),
+        }
+        Ok(result)
+    }
+
+    /// Indexes release tags matching the configured patterns as immutable
+    /// versions. Runs after the default branch has been synced.
+    pub async fn index_tags(&self, registry_id: i32) -> Result<SyncResult> {
+        let repo = SkillRegistry::find()
+            .filter(skill_registry::Column::Id.eq(registry_id))
+            .one(&self.db)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Registry entry not found"))?;
+
+        if repo.status != "active" {
+            return Ok(SyncResult {
+                status: "Skipped".to_string(),
+                version: None,
+                warnings: Vec::new(),
+            });
+        }
+
+        let (source, token) = self.resolve_repo_source(&repo).await?;
+        let mut indexed = indexed_tags(&repo);
+        let pending = source
+            .list_tags(&repo.owner, &repo.name, &repo.url, token.clone())
+            .await?
+            .into_iter()
+            .filter(|tag| !indexed.contains(tag))
+            .filter_map(|tag| match_tag(&self.tag_patterns, &tag))
+            .take(MAX_TAGS_PER_SYNC)
+            .collect::<Vec<_>>();
+
+        let mut changed = false;
+        let mut warnings = Vec::new();
+        for release in pending {
+            let checkout = match source
+                .clone_tag_files(
+                    &repo.owner,
+                    &repo.name,
+                    &repo.url,
+                    &release.tag,
+                    token.clone(),
+                    &self.ingest_limits,
+                )
+                .await
+            {
+                Ok(checkout) => checkout,
+                Err(e) if e.is::<IngestLimitError>() => {
+                    // Retrying cannot help, so the tag is recorded as indexed.
+                    warnings.push(format!("Tag '{}' was not indexed: {}", release.tag, e));
+                    indexed.push(release.tag);
+                    self.save_indexed_tags(&repo, &indexed).await?;
+                    continue;
+                }
+                Err(e) => {
+                    // Left unrecorded so the next sync retries it.
+                    tracing::warn!(repo_id = repo.id, tag = %release.tag, "Failed to clone tag: {}", e);
+                    continue;
+                }
+            };
 
-        let result = self.sync_from_file_map(&repo, &file_map).await;
-        tracing::info!("sync_from_file_map result (from git clone): {:?}", result);
-        result
+            let (release_changed, release_warnings) =
+                self.sync_release(&repo, &checkout, &release).await?;
+            changed |= release_changed;
+            warnings.extend(release_warnings);
+
+            indexed.push(release.tag);
+            self.save_indexed_tags(&repo, &indexed).await?;
+        }
+
+        Ok(SyncResult {
+            status: if changed {
+                "Updated".to_string()
+            } else {
+                "Unchanged".to_string()
+            },
+            version: None,
+            warnings,
+        })
+    }
+
+    async fn sync_release(
+        &self,
+        repo: &skill_registry::Model,
+        checkout: &Checkout,
+        release: &TagRelease,
+    ) -> Result<(bool, Vec<String>)> {
+        let origin = SyncOrigin {
+            revision: checkout.revision.as_ref(),
+            release: Some(release),
+            executables: Some(&checkout.executables),
+        };
+        let mut changed = false;
+        let mut plugin_root_prefixes = HashSet::new();
+        let mut warnings = Vec::new();
+
+        if let Some(marketplace_bytes) = checkout.files.get(".claude-plugin/marketplace.json") {
+            match serde_json::from_slice::<Value>(marketplace_bytes) {
+                Ok(marketplace_json) => {
+                    let outcome = sync_marketplace_plugins(
+                        &self.db,
+                        &*self.s3,
+                        self.signer.as_deref(),
+                        repo,
+                        &checkout.files,
+                        origin,
+                        &marketplace_json,
+                    )
+                    .await?;
+                    changed |= outcome.changed;
+                    plugin_root_prefixes = outcome.plugin_root_prefixes;
+                    warnings = outcome.warnings;
+                }
+                Err(e) => tracing::warn!(
+                    repo_id = repo.id,
+                    tag = %release.tag,
+                    "Ignoring invalid marketplace.json in tag: {}",
+                    e
+                ),
+            }
+        }
+
+        let outcome = sync_standalone_skills(
+            &self.db,
+            &*self.s3,
+            self.signer.as_deref(),
+            repo,
+            &checkout.files,
+            origin,
+            &plugin_root_prefixes,
+            false,
+        )
+        .await?;
+        warnings.extend(outcome.warnings);
+        if let Some(warning) = lfs_pointer_warning(&checkout.files) {
+            warnings.push(format!("Tag '{}': {}", release.tag, warning));
+        }
+        Ok((changed || outcome.changed, warnings))
     }
 
     pub async fn fetch_repo_snapshot(&self, registry_id: i32) -> Result<SnapshotResult> {
@@ -126,7 +343,7 @@ impl SyncService {
         }
 
         let is_blacklisted = Blacklist::find()
-            .filter(blacklist::Column::RepositoryUrl.eq(&repo.url))
+            .filter(eq_ignore_case(blacklist::Column::RepositoryUrl, &repo.url))
             .one(&self.db)
             .await?
             .is_some();
@@ -137,12 +354,43 @@ impl SyncService {
             });
         }
 
-        let token = self.resolve_repo_token(&repo).await?;
-        let file_map = self
-            .github
-            .clone_repository_files(&repo.owner, &repo.name, &repo.url, token)
-            .await?;
-        let zip_data = archive::package_zip(&file_map)?;
+        let (source, token) = self.resolve_repo_source(&repo).await?;
+        let head_sha = self
+            .remote_head_sha(source.as_ref(), &repo, token.clone())
+            .await;
+        if is_head_unchanged(&repo, head_sha.as_deref()) {
+            tracing::info!(repo_id = repo.id, "Remote HEAD unchanged, skipping clone");
+            return Ok(SnapshotResult::Skipped {
+                status: "Unchanged".to_string(),
+            });
+        }
+
+        let checkout = match source
+            .clone_repository_files(
+                &repo.owner,
+                &repo.name,
+                &repo.url,
+                token,
+                &self.ingest_limits,
+            )
+            .await
+        {
+            Ok(checkout) => checkout,
+            Err(e) => match e.downcast_ref::<IngestLimitError>() {
+                Some(limit) => {
+                    let result = self.blacklist(&repo, limit.to_string()).await?;
+                    return Ok(SnapshotResult::Skipped {
+                        status: result.status,
+                    });
+                }
+                None => return Err(e),
+            },
+        };
+        let revision = checkout
+            .revision
+            .or_else(|| head_sha.map(SourceRevision::from_sha));
+        let zip_data =
+            archive::package_zip_with_executables(&checkout.files, &checkout.executables)?;
         let zip_hash = format!("{:x}", md5::compute(&zip_data));
         let snapshot_s3_key = format!("repo-snapshots/{}/{}.zip", repo.id, zip_hash);
         let _ = self.s3.upload(&snapshot_s3_key, zip_data).await?;
@@ -154,21 +402,99 @@ impl SyncService {
             url: repo.url,
             zip_hash,
             snapshot_s3_key,
+            revision,
         }))
     }
 
-    async fn resolve_repo_token(&self, repo: &skill_registry::Model) -> Result<Option<String>> {
-        let Some(discovery_registry_id) = repo.discovery_registry_id else {
-            return Ok(None);
-        };
+    async fn blacklist(&self, repo: &skill_registry::Model, reason: String) -> Result<SyncResult> {
+        tracing::warn!(repo_id = repo.id, "Blacklisting repository: {}", reason);
+        let mut active: skill_registry::ActiveModel = repo.clone().into();
+        active.status = Set("blacklisted".to_string());
+        active.blacklist_reason = Set(Some(reason));
+        active.blacklisted_at = Set(Some(chrono::Utc::now().naive_utc()));
+        active.update(&self.db).await?;
+        Ok(SyncResult {
+            status: "Blacklisted".to_string(),
+            version: None,
+            warnings: Vec::new(),
+        })
+    }
 
-        let cfg = self
-            .discovery_registry_service
-            .find_by_id(discovery_registry_id)
+    async fn save_indexed_tags(
+        &self,
+        repo: &skill_registry::Model,
+        indexed: &[String],
+    ) -> Result<()> {
+        let mut active: skill_registry::ActiveModel = repo.clone().into();
+        active.indexed_tags = Set(Some(serde_json::json!(indexed)));
+        active.update(&self.db).await?;
+        Ok(())
+    }
+
+    /// Failures fall back to a full clone, so a flaky `ls-remote` never blocks a sync.
+    async fn remote_head_sha(
+        &self,
+        source: &dyn SourceProvider,
+        repo: &skill_registry::Model,
+        token: Option<String>,
+    ) -> Option<String> {
+        match source
+            .head_commit_sha(&repo.owner, &repo.name, &repo.url, token)
             .await
-            .map_err(|e| anyhow::anyhow!(e))?;
+        {
+            Ok(sha) => Some(sha),
+            Err(e) => {
+                tracing::warn!(repo_id = repo.id, "Failed to resolve remote HEAD: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn resolve_repo_source(
+        &self,
+        repo: &skill_registry::Model,
+    ) -> Result<(std::sync::Arc<dyn SourceProvider>, Option<String>)> {
+        let cfg = match repo.discovery_registry_id {
+            Some(discovery_registry_id) => self
+                .discovery_registry_service
+                .find_by_id(discovery_registry_id)
+                .await
+                .map_err(|e| anyhow::anyhow!(e))?,
+            None => None,
+        };
 
-        Ok(cfg.map(|c| c.token))
+        let api_url = match cfg.as_ref() {
+            Some(c) => c.api_url.clone(),
+            None => {
+                let host = repo
+                    .host
+                    .clone()
+                    .or_else(|| {
+                        url::Url::parse(&repo.url)
+                            .ok()
+                            .and_then(|u| u.host_str().map(str::to_string))
+                    })
+                    .unwrap_or_default();
+                default_api_url(&repo.platform, &host)
+            }
+        };
+        let token = cfg.map(|c| c.token);
+
+        // Defer clones while the token's API budget is spent; the activity
+        // is retried by Temporal once the limit resets.
+        ensure_rate_limit_budget(
+            &api_url,
+            token.as_deref(),
+            RateLimitResource::Core,
+            "clone repository",
+        )?;
+
+        let source = match repo.platform {
+            skill_registry::Platform::Github => self.source.clone(),
+            ref platform => build_source_provider(platform, None, api_url, None)?,
+        };
+
+        Ok((source, token))
     }
 
     pub async fn apply_sync_from_snapshot(
@@ -176,40 +502,39 @@ impl SyncService {
         snapshot: &domain::RepoSnapshotRef,
     ) -> Result<SyncResult> {
         let zip_data = self.s3.download(&snapshot.snapshot_s3_key).await?;
-        let file_map = match zip_to_file_map(&zip_data) {
-            Ok(m) => m,
-            Err(e) => {
-                let repo = SkillRegistry::find()
-                    .filter(skill_registry::Column::Id.eq(snapshot.registry_id))
-                    .one(&self.db)
-                    .await?
-                    .ok_or_else(|| anyhow::anyhow!("Registry entry not found"))?;
-                let mut active: skill_registry::ActiveModel = repo.clone().into();
-                active.status = Set("blacklisted".to_string());
-                active.blacklist_reason = Set(Some(format!("Invalid zip archive: {}", e)));
-                active.blacklisted_at = Set(Some(chrono::Utc::now().naive_utc()));
-                active.update(&self.db).await?;
-                return Ok(SyncResult {
-                    status: "Blacklisted".to_string(),
-                    version: None,
-                });
-            }
-        };
-
         let repo = SkillRegistry::find()
             .filter(skill_registry::Column::Id.eq(snapshot.registry_id))
             .one(&self.db)
             .await?
             .ok_or_else(|| anyhow::anyhow!("Registry entry not found"))?;
+        let checkout = match zip_to_checkout(&zip_data, &self.ingest_limits) {
+            Ok(checkout) => checkout,
+            Err(e) => {
+                let reason = match e.downcast_ref::<IngestLimitError>() {
+                    Some(limit) => limit.to_string(),
+                    None => format!("Invalid zip archive: {}", e),
+                };
+                return self.blacklist(&repo, reason).await;
+            }
+        };
 
-        self.sync_from_file_map(&repo, &file_map).await
+        self.sync_from_file_map(&repo, &checkout, snapshot.revision.as_ref())
+            .await
     }
 
     async fn sync_from_file_map(
         &self,
         repo: &skill_registry::Model,
-        file_map: &std::collections::BTreeMap<String, Vec<u8>>,
+        checkout: &Checkout,
+        revision: Option<&SourceRevision>,
     ) -> Result<SyncResult> {
+        let file_map = &checkout.files;
+        let lfs_warning = lfs_pointer_warning(file_map);
+        let origin = SyncOrigin {
+            revision,
+            release: None,
+            executables: Some(&checkout.executables),
+        };
         let mut changed = false;
 
         if let Some(marketplace_bytes) = file_map.get(".claude-plugin/marketplace.json") {
@@ -226,20 +551,30 @@ impl SyncService {
                     return Ok(SyncResult {
                         status: "Blacklisted".to_string(),
                         version: None,
+                        warnings: Vec::new(),
                     });
                 }
             };
 
-            let plugin_outcome =
-                sync_marketplace_plugins(&self.db, &*self.s3, repo, file_map, &marketplace_json)
-                    .await?;
+            let plugin_outcome = sync_marketplace_plugins(
+                &self.db,
+                &*self.s3,
+                self.signer.as_deref(),
+                repo,
+                file_map,
+                origin,
+                &marketplace_json,
+            )
+            .await?;
             changed |= plugin_outcome.changed;
 
             let skill_outcome = sync_standalone_skills(
                 &self.db,
                 &*self.s3,
+                self.signer.as_deref(),
                 repo,
                 file_map,
+                origin,
                 &plugin_outcome.plugin_root_prefixes,
                 false,
             )
@@ -249,6 +584,7 @@ impl SyncService {
             let mut active: skill_registry::ActiveModel = repo.clone().into();
             active.status = Set("active".to_string());
             active.repo_type = Set(Some("marketplace".to_string()));
+            active.last_commit_sha = Set(revision.map(|r| r.commit_sha.clone()));
             active.update(&self.db).await?;
             return Ok(SyncResult {
                 status: if changed {
@@ -257,12 +593,25 @@ impl SyncService {
                     "Unchanged".to_string()
                 },
                 version: None,
+                warnings: lfs_warning
+                    .into_iter()
+                    .chain(plugin_outcome.warnings)
+                    .chain(skill_outcome.warnings)
+                    .collect(),
             });
         }
 
-        let skill_outcome =
-            sync_standalone_skills(&self.db, &*self.s3, repo, file_map, &HashSet::new(), true)
-                .await?;
+        let skill_outcome = sync_standalone_skills(
+            &self.db,
+            &*self.s3,
+            self.signer.as_deref(),
+            repo,
+            file_map,
+            origin,
+            &HashSet::new(),
+            true,
+        )
+        .await?;
         changed |= skill_outcome.changed;
 
         if !skill_outcome.found_any {
@@ -273,6 +622,7 @@ impl SyncService {
             return Ok(SyncResult {
                 status: "Blacklisted".to_string(),
                 version: None,
+                warnings: Vec::new(),
             });
         }
 
@@ -280,6 +630,7 @@ impl SyncService {
         let mut active: skill_registry::ActiveModel = repo.clone().into();
         active.status = Set("active".to_string());
         active.repo_type = Set(Some("skill".to_string()));
+        active.last_commit_sha = Set(revision.map(|r| r.commit_sha.clone()));
         active.update(&self.db).await?;
         Ok(SyncResult {
             status: if changed {
@@ -288,14 +639,31 @@ impl SyncService {
                 "Unchanged".to_string()
             },
             version: None,
+            warnings: lfs_warning
+                .into_iter()
+                .chain(skill_outcome.warnings)
+                .collect(),
         })
     }
 }
 
+fn indexed_tags(repo: &skill_registry::Model) -> Vec<String> {
+    repo.indexed_tags
+        .clone()
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+// Only repos that were fully indexed at this commit can be skipped; anything
+// else (pending, blacklisted, unknown HEAD) goes through a fresh clone.
+fn is_head_unchanged(repo: &skill_registry::Model, head_sha: Option<&str>) -> bool {
+    repo.status == "active" && head_sha.is_some() && repo.last_commit_sha.as_deref() == head_sha
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ports::{MockGithubApi, MockStorage};
+    use crate::ports::{MockSourceProvider, MockStorage};
     use common::build_all;
     use common::entities::{blacklist, prelude::*, skill_registry};
     use common::settings::Settings;
@@ -322,6 +690,8 @@ mod tests {
         buf
     }
 
+    const HEAD_SHA: &str = "0123456789abcdef0123456789abcdef01234567";
+
     fn create_file_map(entries: &[(&str, &str)]) -> BTreeMap<String, Vec<u8>> {
         entries
             .iter()
@@ -342,7 +712,9 @@ mod tests {
                 access_key_id: None,
                 secret_access_key: None,
                 force_path_style: false,
+                download_mode: common::settings::DownloadMode::Presigned,
             },
+            storage: common::settings::StorageSettings::default(),
             github: common::settings::GithubSettings {
                 search_keywords: "topic:agent-skill".to_string(),
                 token: None,
@@ -350,12 +722,16 @@ mod tests {
             },
             worker: common::settings::WorkerSettings {
                 scan_interval_seconds: 3600,
+                tag_patterns: common::settings::default_tag_patterns(),
+                gc_grace_period_hours: common::settings::default_gc_grace_period_hours(),
+                ingest_limits: common::settings::IngestLimits::default(),
             },
             temporal: common::settings::TemporalSettings {
                 server_url: "http://localhost:7233".to_string(),
                 task_queue: "test".to_string(),
             },
             auth: common::settings::AuthSettings::default(),
+            signing: common::settings::SigningSettings::default(),
             debug: true,
         })
     }
@@ -420,7 +796,7 @@ mod tests {
         let sync_service = SyncService::new(
             db.clone(),
             Arc::new(MockStorage::new()),
-            Arc::new(MockGithubApi::new()),
+            Arc::new(MockSourceProvider::new()),
             services.registry_service,
             services.discovery_registry_service,
         );
@@ -442,7 +818,7 @@ mod tests {
         let sync_service = SyncService::new(
             db,
             Arc::new(MockStorage::new()),
-            Arc::new(MockGithubApi::new()),
+            Arc::new(MockSourceProvider::new()),
             services.registry_service,
             services.discovery_registry_service,
         );
@@ -467,7 +843,7 @@ mod tests {
         let sync_service = SyncService::new(
             db,
             Arc::new(MockStorage::new()),
-            Arc::new(MockGithubApi::new()),
+            Arc::new(MockSourceProvider::new()),
             services.registry_service,
             services.discovery_registry_service,
         );
@@ -489,16 +865,20 @@ mod tests {
         )
         .await;
 
-        let mut github = MockGithubApi::new();
+        let mut github = MockSourceProvider::new();
+        github
+            .expect_head_commit_sha()
+            .times(1)
+            .returning(|_, _, _, _| Err(anyhow::anyhow!("ls-remote failed")));
         github
             .expect_clone_repository_files()
             .times(1)
-            .returning(|owner, repo, url, token| {
+            .returning(|owner, repo, url, token, _| {
                 assert_eq!(owner, "acme");
                 assert_eq!(repo, "empty-repo");
                 assert_eq!(url, "https://github.com/acme/empty-repo");
                 assert!(token.is_none());
-                Ok(BTreeMap::new())
+                Ok(BTreeMap::new().into())
             });
 
         let sync_service = SyncService::new(
@@ -543,11 +923,15 @@ description: demo
 ",
         )]);
 
-        let mut github = MockGithubApi::new();
+        let mut github = MockSourceProvider::new();
+        github
+            .expect_head_commit_sha()
+            .times(1)
+            .returning(|_, _, _, _| Ok(HEAD_SHA.to_string()));
         github
             .expect_clone_repository_files()
             .times(1)
-            .returning(move |_, _, _, _| Ok(files.clone()));
+            .returning(move |_, _, _, _, _| Ok(files.clone().into()));
 
         let mut storage = MockStorage::new();
         storage.expect_upload().times(1).returning(|key, body| {
@@ -570,6 +954,10 @@ description: demo
             SnapshotResult::Snapshot(snapshot_ref) => {
                 assert_eq!(snapshot_ref.registry_id, repo.id);
                 assert!(snapshot_ref.snapshot_s3_key.starts_with("repo-snapshots/"));
+                assert_eq!(
+                    snapshot_ref.revision.map(|r| r.commit_sha).as_deref(),
+                    Some(HEAD_SHA)
+                );
             }
             SnapshotResult::Skipped { .. } => panic!("expected snapshot output"),
         }
@@ -577,6 +965,60 @@ description: demo
         Ok(())
     }
 
+    #[tokio::test]
+    async fn fetch_repo_snapshot_blacklists_repo_over_ingest_limits() -> Result<()> {
+        let (db, services) = setup_db_and_services().await?;
+        let repo = insert_registry(
+            &db,
+            "acme",
+            "huge-repo",
+            "active",
+            "https://github.com/acme/huge-repo",
+        )
+        .await;
+
+        let mut github = MockSourceProvider::new();
+        github
+            .expect_head_commit_sha()
+            .times(1)
+            .returning(|_, _, _, _| Ok(HEAD_SHA.to_string()));
+        github
+            .expect_clone_repository_files()
+            .times(1)
+            .returning(|_, _, _, _, limits| {
+                Err(IngestLimitError::RepoTooLarge {
+                    limit: limits.max_repo_bytes,
+                }
+                .into())
+            });
+
+        let sync_service = SyncService::new(
+            db.clone(),
+            Arc::new(MockStorage::new()),
+            Arc::new(github),
+            services.registry_service,
+            services.discovery_registry_service,
+        )
+        .with_ingest_limits(IngestLimits {
+            max_repo_bytes: 1024,
+            ..IngestLimits::default()
+        });
+
+        match sync_service.fetch_repo_snapshot(repo.id).await? {
+            SnapshotResult::Skipped { status } => assert_eq!(status, "Blacklisted"),
+            SnapshotResult::Snapshot(_) => panic!("expected oversized repo to be skipped"),
+        }
+
+        let updated = SkillRegistry::find_by_id(repo.id).one(&db).await?.unwrap();
+        assert_eq!(updated.status, "blacklisted");
+        assert_eq!(
+            updated.blacklist_reason.as_deref(),
+            Some("Repository is larger than 1024 bytes")
+        );
+        assert!(updated.blacklisted_at.is_some());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn apply_sync_from_snapshot_blacklists_invalid_zip() -> Result<()> {
         let (db, services) = setup_db_and_services().await?;
@@ -598,7 +1040,7 @@ description: demo
         let sync_service = SyncService::new(
             db.clone(),
             Arc::new(storage),
-            Arc::new(MockGithubApi::new()),
+            Arc::new(MockSourceProvider::new()),
             services.registry_service,
             services.discovery_registry_service,
         );
@@ -610,6 +1052,7 @@ description: demo
             url: repo.url.clone(),
             zip_hash: "deadbeef".to_string(),
             snapshot_s3_key: "repo-snapshots/invalid.zip".to_string(),
+            revision: None,
         };
 
         let result = sync_service.apply_sync_from_snapshot(&snapshot).await?;
@@ -663,11 +1106,14 @@ metadata:
         let sync_service = SyncService::new(
             db.clone(),
             Arc::new(storage),
-            Arc::new(MockGithubApi::new()),
+            Arc::new(MockSourceProvider::new()),
             services.registry_service,
             services.discovery_registry_service,
         );
 
+        let committed_at = chrono::DateTime::from_timestamp(1_760_572_800, 0)
+            .unwrap()
+            .naive_utc();
         let snapshot = domain::RepoSnapshotRef {
             registry_id: repo.id,
             owner: repo.owner.clone(),
@@ -675,6 +1121,11 @@ metadata:
             url: repo.url.clone(),
             zip_hash: "hash".to_string(),
             snapshot_s3_key: "repo-snapshots/valid.zip".to_string(),
+            revision: Some(SourceRevision {
+                commit_sha: HEAD_SHA.to_string(),
+                git_ref: Some("refs/heads/main".to_string()),
+                committed_at: Some(committed_at),
+            }),
         };
 
         let result = sync_service.apply_sync_from_snapshot(&snapshot).await?;
@@ -683,6 +1134,7 @@ metadata:
         let updated = SkillRegistry::find_by_id(repo.id).one(&db).await?.unwrap();
         assert_eq!(updated.status, "active");
         assert_eq!(updated.repo_type.as_deref(), Some("skill"));
+        assert_eq!(updated.last_commit_sha.as_deref(), Some(HEAD_SHA));
 
         let skills = Skills::find()
             .filter(common::entities::skills::Column::SkillRegistryId.eq(repo.id))
@@ -691,6 +1143,213 @@ metadata:
         assert_eq!(skills.len(), 1);
         assert_eq!(skills[0].name, "zip-skill");
 
+        let version = SkillVersions::find()
+            .filter(common::entities::skill_versions::Column::SkillId.eq(skills[0].id))
+            .one(&db)
+            .await?
+            .unwrap();
+        assert_eq!(version.commit_sha.as_deref(), Some(HEAD_SHA));
+        assert_eq!(version.git_ref.as_deref(), Some("refs/heads/main"));
+        assert_eq!(version.committed_at, Some(committed_at));
+        assert_eq!(version.source_path.as_deref(), Some("skill"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn index_tags_indexes_matching_tags_once() -> Result<()> {
+        let (db, services) = setup_db_and_services().await?;
+        let repo = insert_registry(
+            &db,
+            "acme",
+            "tagged-repo",
+            "active",
+            "https://github.com/acme/tagged-repo",
+        )
+        .await;
+
+        let files = create_file_map(&[(
+            "demo/SKILL.md",
+            "---\nname: demo-skill\ndescription: demo\n---\n# Demo\n",
+        )]);
+
+        let mut github = MockSourceProvider::new();
+        github
+            .expect_list_tags()
+            .times(2)
+            .returning(|_, _, _, _| Ok(vec!["nightly".to_string(), "v1.0.0".to_string()]));
+        github
+            .expect_clone_tag_files()
+            .times(1)
+            .returning(move |_, _, _, tag, _, _| {
+                assert_eq!(tag, "v1.0.0");
+                Ok(Checkout {
+                    files: files.clone(),
+                    revision: Some(SourceRevision {
+                        commit_sha: HEAD_SHA.to_string(),
+                        git_ref: Some("refs/tags/v1.0.0".to_string()),
+                        committed_at: None,
+                    }),
+                    ..Default::default()
+                })
+            });
+
+        let mut storage = MockStorage::new();
+        storage.expect_upload().times(1).returning(|key, _| {
+            assert!(key.starts_with("skills/github.com/acme/tagged-repo/demo-skill/"));
+            Ok(format!("https://oss.local/{key}"))
+        });
+
+        let sync_service = SyncService::new(
+            db.clone(),
+            Arc::new(storage),
+            Arc::new(github),
+            services.registry_service,
+            services.discovery_registry_service,
+        );
+
+        let first = sync_service.index_tags(repo.id).await?;
+        assert_eq!(first.status, "Updated");
+        let second = sync_service.index_tags(repo.id).await?;
+        assert_eq!(second.status, "Unchanged");
+
+        let updated = SkillRegistry::find_by_id(repo.id).one(&db).await?.unwrap();
+        assert_eq!(indexed_tags(&updated), vec!["v1.0.0".to_string()]);
+
+        let version = SkillVersions::find()
+            .filter(common::entities::skill_versions::Column::Version.eq("1.0.0"))
+            .one(&db)
+            .await?
+            .unwrap();
+        assert_eq!(version.git_ref.as_deref(), Some("refs/tags/v1.0.0"));
+        assert_eq!(version.commit_sha.as_deref(), Some(HEAD_SHA));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unchanged_remote_head_skips_clone_and_snapshot() -> Result<()> {
+        let (db, services) = setup_db_and_services().await?;
+        let mut repo: skill_registry::ActiveModel = insert_registry(
+            &db,
+            "acme",
+            "indexed-repo",
+            "active",
+            "https://github.com/acme/indexed-repo",
+        )
+        .await
+        .into();
+        repo.last_commit_sha = Set(Some(HEAD_SHA.to_string()));
+        let repo = repo.update(&db).await?;
+
+        let mut github = MockSourceProvider::new();
+        github
+            .expect_head_commit_sha()
+            .times(2)
+            .returning(|_, _, _, _| Ok(HEAD_SHA.to_string()));
+        github.expect_clone_repository_files().never();
+        github
+            .expect_list_tags()
+            .times(1)
+            .returning(|_, _, _, _| Ok(Vec::new()));
+
+        let sync_service = SyncService::new(
+            db,
+            Arc::new(MockStorage::new()),
+            Arc::new(github),
+            services.registry_service,
+            services.discovery_registry_service,
+        );
+
+        let result = sync_service.process_one(repo.id).await?;
+        assert_eq!(result.status, "Unchanged");
+
+        match sync_service.fetch_repo_snapshot(repo.id).await? {
+            SnapshotResult::Skipped { status } => assert_eq!(status, "Unchanged"),
+            SnapshotResult::Snapshot(_) => panic!("expected unchanged repo to be skipped"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn is_head_unchanged_requires_active_repo_and_matching_sha() {
+        let repo = skill_registry::Model {
+            id: 1,
+            discovery_registry_id: None,
+            platform: skill_registry::Platform::Github,
+            owner: "acme".to_string(),
+            name: "skills".to_string(),
+            url: "https://github.com/acme/skills".to_string(),
+            host: None,
+            description: None,
+            repo_type: None,
+            status: "active".to_string(),
+            blacklist_reason: None,
+            blacklisted_at: None,
+            stars: 0,
+            last_scanned_at: None,
+            last_commit_sha: Some(HEAD_SHA.to_string()),
+            indexed_tags: None,
+            created_at: chrono::Utc::now().naive_utc(),
+            updated_at: chrono::Utc::now().naive_utc(),
+        };
+
+        assert!(is_head_unchanged(&repo, Some(HEAD_SHA)));
+        assert!(!is_head_unchanged(&repo, None));
+        assert!(!is_head_unchanged(
+            &repo,
+            Some("fedcba9876543210fedcba9876543210fedcba98")
+        ));
+
+        let pending = skill_registry::Model {
+            status: "pending".to_string(),
+            ..repo
+        };
+        assert!(!is_head_unchanged(&pending, Some(HEAD_SHA)));
+    }
+
+    #[tokio::test]
+    async fn resolve_repo_source_uses_platform_specific_provider() -> Result<()> {
+        let (db, services) = setup_db_and_services().await?;
+
+        let github_repo = insert_registry(
+            &db,
+            "acme",
+            "skills",
+            "active",
+            "https://github.com/acme/skills",
+        )
+        .await;
+
+        let mut gitea_repo: skill_registry::ActiveModel = insert_registry(
+            &db,
+            "acme",
+            "forge-skills",
+            "active",
+            "https://codeberg.org/acme/forge-skills",
+        )
+        .await
+        .into();
+        gitea_repo.platform = Set(skill_registry::Platform::Gitea);
+        let gitea_repo = gitea_repo.update(&db).await?;
+
+        let default_source: Arc<dyn SourceProvider> = Arc::new(MockSourceProvider::new());
+        let sync_service = SyncService::new(
+            db.clone(),
+            Arc::new(MockStorage::new()),
+            default_source.clone(),
+            services.registry_service,
+            services.discovery_registry_service,
+        );
+
+        let (source, token) = sync_service.resolve_repo_source(&github_repo).await?;
+        assert!(Arc::ptr_eq(&source, &default_source));
+        assert!(token.is_none());
+
+        let (source, token) = sync_service.resolve_repo_source(&gitea_repo).await?;
+        assert!(!Arc::ptr_eq(&source, &default_source));
+        assert!(token.is_none());
+
         Ok(())
     }
 }
@@ -0,0 +1,33 @@
+use crate::ports::Storage;
+use anyhow::Result;
+use common::domain::archive::PackageDigest;
+use common::domain::signing::{signature_key, PackageSignature, PackageSigner};
+
+/// A package zip stored in the bucket.
+pub struct PublishedPackage {
+    pub oss_url: String,
+    pub digest: PackageDigest,
+    pub signature: Option<PackageSignature>,
+}
+
+/// Uploads a package and, when a signer is configured, its raw detached
+/// signature to `<s3_key>.sig`.
+pub async fn publish_package(
+    s3: &dyn Storage,
+    signer: Option<&PackageSigner>,
+    s3_key: &str,
+    zip: Vec<u8>,
+) -> Result<PublishedPackage> {
+    let digest = PackageDigest::of(&zip);
+    let signature = signer.map(|signer| signer.sign(&zip));
+    let oss_url = s3.upload(s3_key, zip).await?;
+    if let Some(signature) = signature.as_ref() {
+        s3.upload(&signature_key(s3_key), signature.signature.to_vec())
+            .await?;
+    }
+    Ok(PublishedPackage {
+        oss_url,
+        digest,
+        signature,
+    })
+}
@@ -1,9 +1,20 @@
-use super::domain::SkillSyncOutcome;
+use super::domain::{SkillSyncOutcome, SyncOrigin};
+use super::publish::{publish_package, PublishedPackage};
+use crate::git::SourceRevision;
 use crate::ports::Storage;
 use anyhow::Result;
-use common::domain::{archive, markdown, skill};
-use common::entities::{prelude::*, skill_registry, skill_versions, skills};
-use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use common::domain::archive::PackageDigest;
+use common::domain::artifact::{artifact_key, registry_locator, ArtifactKind};
+use common::domain::signing::{PackageSignature, PackageSigner};
+use common::domain::version::select_latest;
+use common::domain::{archive, manifest, markdown, skill};
+use common::entities::{
+    prelude::*, skill_registry, skill_version_revisions, skill_versions, skills,
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    Set,
+};
 use serde_json::Value;
 use std::collections::{BTreeMap, HashSet};
 
@@ -123,6 +134,14 @@ fn normalize_skill_metadata(frontmatter: &skill::SkillFrontmatter) -> Option<Val
     }
 }
 
+fn is_declared_version(version: &skill_versions::Model) -> bool {
+    SyncOrigin::is_tagged(version.git_ref.as_deref())
+        || version
+            .metadata
+            .as_ref()
+            .is_some_and(|metadata| metadata.get("version").is_some())
+}
+
 struct SkillRepoHelper<'a> {
     db: &'a DatabaseConnection,
 }
@@ -148,12 +167,21 @@ impl<'a> SkillRepoHelper<'a> {
         registry_id: i32,
         name: &str,
         latest_version: Option<String>,
+        head_version: Option<String>,
         is_active: i32,
     ) -> Result<i32, sea_orm::DbErr> {
         if let Some(existing) = existing {
             let id = existing.id;
+            let reuse_resolved =
+                existing.reused_version.is_some() && existing.reused_version != latest_version;
             let mut updated = skills::ActiveModel::from(existing);
+            if reuse_resolved {
+                updated.reused_version = Set(None);
+            }
             updated.latest_version = Set(latest_version);
+            if head_version.is_some() {
+                updated.head_version = Set(head_version);
+            }
             updated.is_active = Set(is_active);
             updated.updated_at = Set(chrono::Utc::now().naive_utc());
             updated.update(self.db).await?;
@@ -164,6 +192,7 @@ impl<'a> SkillRepoHelper<'a> {
                 skill_registry_id: Set(registry_id),
                 name: Set(name.to_string()),
                 latest_version: Set(latest_version),
+                head_version: Set(head_version),
                 is_active: Set(is_active),
                 created_at: Set(now),
                 updated_at: Set(now),
@@ -197,7 +226,12 @@ impl<'a> SkillRepoHelper<'a> {
         s3_key: Option<String>,
         oss_url: Option<String>,
         file_hash: Option<String>,
+        digest: &PackageDigest,
+        signature: Option<&PackageSignature>,
         metadata: Option<serde_json::Value>,
+        file_manifest: serde_json::Value,
+        revision: Option<&SourceRevision>,
+        source_path: &str,
     ) -> Result<(), sea_orm::DbErr> {
         if let Some(existing) = existing {
             let mut updated = skill_versions::ActiveModel::from(existing);
@@ -206,7 +240,16 @@ impl<'a> SkillRepoHelper<'a> {
             updated.s3_key = Set(s3_key);
             updated.oss_url = Set(oss_url);
             updated.file_hash = Set(file_hash);
+            updated.sha256 = Set(Some(digest.sha256.clone()));
+            updated.file_size = Set(Some(digest.size));
+            updated.signature = Set(signature.map(PackageSignature::to_base64));
+            updated.signature_key_id = Set(signature.map(|s| s.key_id.clone()));
             updated.metadata = Set(metadata);
+            updated.file_manifest = Set(Some(file_manifest));
+            updated.commit_sha = Set(revision.map(|r| r.commit_sha.clone()));
+            updated.git_ref = Set(revision.and_then(|r| r.git_ref.clone()));
+            updated.committed_at = Set(revision.and_then(|r| r.committed_at));
+            updated.source_path = Set(Some(source_path.to_string()));
             updated.update(self.db).await?;
         } else {
             let now = chrono::Utc::now().naive_utc();
@@ -218,7 +261,16 @@ impl<'a> SkillRepoHelper<'a> {
                 s3_key: Set(s3_key),
                 oss_url: Set(oss_url),
                 file_hash: Set(file_hash),
+                sha256: Set(Some(digest.sha256.clone())),
+                file_size: Set(Some(digest.size)),
+                signature: Set(signature.map(PackageSignature::to_base64)),
+                signature_key_id: Set(signature.map(|s| s.key_id.clone())),
                 metadata: Set(metadata),
+                file_manifest: Set(Some(file_manifest)),
+                commit_sha: Set(revision.map(|r| r.commit_sha.clone())),
+                git_ref: Set(revision.and_then(|r| r.git_ref.clone())),
+                committed_at: Set(revision.and_then(|r| r.committed_at)),
+                source_path: Set(Some(source_path.to_string())),
                 created_at: Set(now),
                 ..Default::default()
             };
@@ -248,13 +300,103 @@ impl<'a> SkillRepoHelper<'a> {
         updated.update(self.db).await?;
         Ok(())
     }
+
+    // Derived `0.0.<hash>` versions only become latest when the skill has never
+    // declared a version; otherwise the highest declared release wins.
+    async fn resolve_latest_version(
+        &self,
+        skill: Option<&skills::Model>,
+        current: &str,
+        current_declared: bool,
+    ) -> Result<String, sea_orm::DbErr> {
+        let existing = match skill {
+            Some(skill) => {
+                SkillVersions::find()
+                    .filter(skill_versions::Column::SkillId.eq(skill.id))
+                    .all(self.db)
+                    .await?
+            }
+            None => Vec::new(),
+        };
+        let declared = existing
+            .iter()
+            .filter(|v| is_declared_version(v))
+            .map(|v| v.version.as_str())
+            .chain(current_declared.then_some(current));
+        Ok(select_latest(declared).unwrap_or(current).to_string())
+    }
+
+    async fn find_revision(
+        &self,
+        skill_version_id: i32,
+        file_hash: &str,
+    ) -> Result<Option<skill_version_revisions::Model>, sea_orm::DbErr> {
+        SkillVersionRevisions::find()
+            .filter(skill_version_revisions::Column::SkillVersionId.eq(skill_version_id))
+            .filter(skill_version_revisions::Column::FileHash.eq(file_hash))
+            .one(self.db)
+            .await
+    }
+
+    async fn insert_revision(
+        &self,
+        skill_version_id: i32,
+        file_hash: &str,
+        s3_key: String,
+        package: PublishedPackage,
+        revision: Option<&SourceRevision>,
+    ) -> Result<i32, sea_orm::DbErr> {
+        let recorded = SkillVersionRevisions::find()
+            .filter(skill_version_revisions::Column::SkillVersionId.eq(skill_version_id))
+            .count(self.db)
+            .await?;
+        // Revision 1 is the released version row itself.
+        let number = recorded as i32 + 2;
+        skill_version_revisions::ActiveModel {
+            skill_version_id: Set(skill_version_id),
+            revision: Set(number),
+            file_hash: Set(file_hash.to_string()),
+            s3_key: Set(Some(s3_key)),
+            oss_url: Set(Some(package.oss_url)),
+            sha256: Set(Some(package.digest.sha256)),
+            file_size: Set(Some(package.digest.size)),
+            signature: Set(package.signature.as_ref().map(PackageSignature::to_base64)),
+            signature_key_id: Set(package.signature.map(|s| s.key_id)),
+            commit_sha: Set(revision.map(|r| r.commit_sha.clone())),
+            git_ref: Set(revision.and_then(|r| r.git_ref.clone())),
+            committed_at: Set(revision.and_then(|r| r.committed_at)),
+            created_at: Set(chrono::Utc::now().naive_utc()),
+            ..Default::default()
+        }
+        .insert(self.db)
+        .await?;
+        Ok(number)
+    }
+
+    async fn flag_reused_version(
+        &self,
+        skill_id: i32,
+        version: &str,
+    ) -> Result<(), sea_orm::DbErr> {
+        skills::ActiveModel {
+            id: Set(skill_id),
+            reused_version: Set(Some(version.to_string())),
+            ..Default::default()
+        }
+        .update(self.db)
+        .await?;
+        Ok(())
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn sync_standalone_skills(
     db: &DatabaseConnection,
     s3: &dyn Storage,
+    signer: Option<&PackageSigner>,
     repo: &skill_registry::Model,
     all_files: &BTreeMap<String, Vec<u8>>,
+    origin: SyncOrigin<'_>,
     exclude_prefixes: &HashSet<String>,
     require_any_valid: bool,
 ) -> Result<SkillSyncOutcome> {
@@ -281,6 +423,7 @@ pub async fn sync_standalone_skills(
 
     let mut found_skill_names = HashSet::new();
     let mut changed = false;
+    let mut warnings = Vec::new();
 
     for path in candidate_paths {
         if normalized_excludes.iter().any(|ex| path.starts_with(ex)) {
@@ -312,6 +455,12 @@ pub async fn sync_standalone_skills(
             }
         };
 
+        if let Some(scope) = origin.release.and_then(|r| r.name.as_deref()) {
+            if scope != frontmatter.name {
+                continue;
+            }
+        }
+
         if found_skill_names.contains(&frontmatter.name) {
             tracing::warn!(
                 path = %path,
@@ -341,7 +490,11 @@ pub async fn sync_standalone_skills(
             };
             prefixed_skill_files.insert(prefixed_path, bytes);
         }
-        let package_hash = archive::compute_hash(&prefixed_skill_files);
+        let executables = origin
+            .executables
+            .map(|paths| archive::rebase_paths(paths, &skill_dir, prefix))
+            .unwrap_or_default();
+        let package_hash = archive::compute_package_hash(&prefixed_skill_files, &executables);
 
         let derived_patch = match u32::from_str_radix(&content_hash[..8], 16) {
             Ok(v) => v,
@@ -354,12 +507,17 @@ pub async fn sync_standalone_skills(
                 0
             }
         };
-        let version_str = frontmatter
-            .metadata
-            .as_ref()
-            .and_then(|m| m.get("version"))
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
+        let declared_version = match origin.release {
+            Some(release) => Some(release.version.clone()),
+            None => frontmatter
+                .metadata
+                .as_ref()
+                .and_then(|m| m.get("version"))
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+        };
+        let version_str = declared_version
+            .clone()
             .unwrap_or_else(|| format!("0.0.{}", derived_patch));
 
         let existing_skill = repo_store.find_skill(repo.id, &frontmatter.name).await?;
@@ -370,13 +528,29 @@ pub async fn sync_standalone_skills(
             version_str
         );
 
+        let latest_version = repo_store
+            .resolve_latest_version(
+                existing_skill.as_ref(),
+                &version_str,
+                declared_version.is_some(),
+            )
+            .await?;
+        // A skill only seen in tags is recorded inactive so its versions stay
+        // reachable; tags never change whether a skill is active.
+        let is_active = match (origin.release, existing_skill.as_ref()) {
+            (None, _) => 1,
+            (Some(_), Some(existing)) => existing.is_active,
+            (Some(_), None) => 0,
+        };
         let skill_id = repo_store
             .upsert_skill(
                 existing_skill,
                 repo.id,
                 &frontmatter.name,
-                Some(version_str.clone()),
-                1,
+                Some(latest_version),
+                // Only the default branch moves the `main` channel.
+                origin.release.is_none().then(|| version_str.clone()),
+                is_active,
             )
             .await?;
 
@@ -384,11 +558,78 @@ pub async fn sync_standalone_skills(
             .find_version_by_name(skill_id, &version_str)
             .await?;
 
-        let unchanged = existing_version
+        if existing_version
             .as_ref()
-            .and_then(|v| v.file_hash.as_ref())
-            .map(|h| h == &package_hash)
-            .unwrap_or(false);
+            .is_some_and(|v| origin.keeps_pinned(v.git_ref.as_deref(), v.s3_key.as_deref()))
+        {
+            tracing::info!(
+                "Skill '{}' version {} is pinned to a release tag; skipping",
+                frontmatter.name,
+                version_str
+            );
+            continue;
+        }
+
+        if let Some(released) = existing_version.as_ref().filter(|v| {
+            v.file_hash
+                .as_ref()
+                .is_some_and(|hash| hash != &package_hash)
+        }) {
+            // Hashes recorded before executable bits were hashed only differ in
+            // format; their new zip is kept as a revision without flagging reuse.
+            let legacy_format = released.file_hash.as_deref()
+                == Some(archive::compute_hash(&prefixed_skill_files).as_str());
+            let recorded = repo_store.find_revision(released.id, &package_hash).await?;
+            let number = match recorded {
+                Some(recorded) => recorded.revision,
+                None => {
+                    let zip = tokio::task::spawn_blocking(move || {
+                        archive::package_zip_with_executables(&prefixed_skill_files, &executables)
+                    })
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Zip packaging task failed: {}", e))??;
+                    let s3_key = artifact_key(
+                        ArtifactKind::Skill,
+                        &registry_locator(repo),
+                        &frontmatter.name,
+                        &package_hash,
+                    );
+                    let package = publish_package(s3, signer, &s3_key, zip).await?;
+                    changed = true;
+                    repo_store
+                        .insert_revision(
+                            released.id,
+                            &package_hash,
+                            s3_key,
+                            package,
+                            origin.revision,
+                        )
+                        .await?
+                }
+            };
+            if legacy_format {
+                continue;
+            }
+            repo_store
+                .flag_reused_version(skill_id, &version_str)
+                .await?;
+            let warning = format!(
+                "Skill '{}' reuses version {} for different content (build revision {}); bump the version to publish it",
+                frontmatter.name, version_str, number
+            );
+            tracing::warn!(repo_id = repo.id, "{}", warning);
+            warnings.push(warning);
+            continue;
+        }
+
+        // Released versions are immutable, so the only remaining case is
+        // identical content; a tag claims it from the default branch.
+        let unchanged = origin.release.is_none()
+            && existing_version
+                .as_ref()
+                .and_then(|v| v.file_hash.as_ref())
+                .map(|h| h == &package_hash)
+                .unwrap_or(false);
 
         let file_hash_match = existing_version
             .as_ref()
@@ -406,22 +647,29 @@ pub async fn sync_standalone_skills(
             tracing::info!("Skipping upload for {} (unchanged)", frontmatter.name);
             continue;
         }
-        let new_zip_buffer =
-            tokio::task::spawn_blocking(move || archive::package_zip(&prefixed_skill_files))
-                .await
-                .map_err(|e| anyhow::anyhow!("Zip packaging task failed: {}", e))??;
-        let s3_key = format!("skills/{}/{}.zip", frontmatter.name, version_str);
+        let file_manifest = serde_json::to_value(manifest::file_manifest(&prefixed_skill_files))?;
+        let new_zip_buffer = tokio::task::spawn_blocking(move || {
+            archive::package_zip_with_executables(&prefixed_skill_files, &executables)
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!("Zip packaging task failed: {}", e))??;
+        let s3_key = artifact_key(
+            ArtifactKind::Skill,
+            &registry_locator(repo),
+            &frontmatter.name,
+            &package_hash,
+        );
         tracing::info!(
             "Uploading skill {} ({} bytes) to s3_key={}",
             frontmatter.name,
             new_zip_buffer.len(),
             s3_key
         );
-        let oss_url = s3.upload(&s3_key, new_zip_buffer).await?;
+        let package = publish_package(s3, signer, &s3_key, new_zip_buffer).await?;
         tracing::info!(
             "Upload complete for {}: oss_url={}",
             frontmatter.name,
-            oss_url
+            package.oss_url
         );
 
         repo_store
@@ -432,9 +680,18 @@ pub async fn sync_standalone_skills(
                 Some(frontmatter.description.clone()),
                 Some(body.clone()),
                 Some(s3_key.clone()),
-                Some(oss_url.clone()),
+                Some(package.oss_url.clone()),
                 Some(package_hash.clone()),
+                &package.digest,
+                package.signature.as_ref(),
                 normalize_skill_metadata(&frontmatter),
+                file_manifest,
+                origin.revision,
+                if skill_dir.is_empty() {
+                    "."
+                } else {
+                    skill_dir.as_str()
+                },
             )
             .await?;
         changed = true;
@@ -444,10 +701,11 @@ pub async fn sync_standalone_skills(
         return Ok(SkillSyncOutcome {
             changed,
             found_any: false,
+            warnings,
         });
     }
 
-    if !found_skill_names.is_empty() {
+    if !found_skill_names.is_empty() && origin.release.is_none() {
         let existing = repo_store.list_skills_by_registry_id(repo.id).await?;
         for s in existing {
             let should_be_active = found_skill_names.contains(&s.name);
@@ -466,6 +724,7 @@ pub async fn sync_standalone_skills(
     Ok(SkillSyncOutcome {
         changed,
         found_any: !found_skill_names.is_empty(),
+        warnings,
     })
 }
 
@@ -473,6 +732,7 @@ pub async fn sync_standalone_skills(
 mod tests {
     use super::*;
     use crate::ports::MockStorage;
+    use crate::sync::tags::TagRelease;
     use common::domain::skill::SkillFrontmatter;
     use common::entities::{skill_registry, skills};
     use migration::MigratorTrait;
@@ -480,7 +740,7 @@ mod tests {
         ActiveModelTrait, ColumnTrait, Database, DatabaseConnection, EntityTrait, PaginatorTrait,
         QueryFilter, Set,
     };
-    use std::collections::{BTreeMap, HashSet};
+    use std::collections::{BTreeMap, BTreeSet, HashSet};
 
     async fn setup_db() -> DatabaseConnection {
         let db = Database::connect("sqlite::memory:").await.unwrap();
@@ -587,8 +847,11 @@ mod tests {
 
         let mut storage = MockStorage::new();
         storage.expect_upload().times(1).returning(|key, body| {
-            assert!(key.starts_with("skills/demo-skill/1.0.0.zip"));
-            assert!(!body.is_empty());
+            assert!(key.starts_with("skills/github.com/acme/standalone-repo/demo-skill/"));
+            assert!(key.ends_with(".zip"));
+            let mut zip = zip::ZipArchive::new(std::io::Cursor::new(body)).unwrap();
+            let script = zip.by_name("demo-skill/scripts/run.sh").unwrap();
+            assert_eq!(script.unix_mode().map(|mode| mode & 0o777), Some(0o755));
             Ok(format!("https://oss.local/{key}"))
         });
 
@@ -596,16 +859,45 @@ mod tests {
             ("demo/SKILL.md", &skill_md("demo-skill", Some("1.0.0"))),
             ("demo/scripts/run.sh", "echo ok"),
         ]);
+        let executables = BTreeSet::from(["demo/scripts/run.sh".to_string()]);
 
-        let first = sync_standalone_skills(&db, &storage, &repo, &files, &HashSet::new(), true)
-            .await
-            .unwrap();
+        let revision = SourceRevision {
+            commit_sha: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            git_ref: Some("refs/heads/main".to_string()),
+            committed_at: None,
+        };
+        let origin = SyncOrigin {
+            revision: Some(&revision),
+            release: None,
+            executables: Some(&executables),
+        };
+        let first = sync_standalone_skills(
+            &db,
+            &storage,
+            None,
+            &repo,
+            &files,
+            origin,
+            &HashSet::new(),
+            true,
+        )
+        .await
+        .unwrap();
         assert!(first.changed);
         assert!(first.found_any);
 
-        let second = sync_standalone_skills(&db, &storage, &repo, &files, &HashSet::new(), true)
-            .await
-            .unwrap();
+        let second = sync_standalone_skills(
+            &db,
+            &storage,
+            None,
+            &repo,
+            &files,
+            origin,
+            &HashSet::new(),
+            true,
+        )
+        .await
+        .unwrap();
         assert!(!second.changed);
         assert!(second.found_any);
 
@@ -625,11 +917,295 @@ mod tests {
             .unwrap()
             .unwrap();
         assert_eq!(version.version, "1.0.0");
+        assert_eq!(
+            version.commit_sha.as_deref(),
+            Some(revision.commit_sha.as_str())
+        );
+        assert_eq!(version.git_ref.as_deref(), Some("refs/heads/main"));
+        assert_eq!(version.source_path.as_deref(), Some("demo"));
+        assert_eq!(version.sha256.as_ref().map(String::len), Some(64));
+        assert!(version.file_size.is_some_and(|size| size > 0));
+        let manifest: Vec<manifest::FileEntry> =
+            serde_json::from_value(version.file_manifest.unwrap()).unwrap();
+        let paths: Vec<&str> = manifest.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["demo-skill/SKILL.md", "demo-skill/scripts/run.sh"]
+        );
+        assert_eq!(manifest[1].mime_type, "text/x-shellscript");
         let metadata = version.metadata.unwrap();
         assert_eq!(metadata["license"], "MIT");
         assert_eq!(metadata["allowed-tools"], serde_json::json!(["bash", "rg"]));
     }
 
+    #[tokio::test]
+    async fn sync_standalone_skills_treats_mode_changes_as_new_content() {
+        let db = setup_db().await;
+        let repo = insert_registry(&db, "mode-repo").await;
+
+        let mut storage = MockStorage::new();
+        storage
+            .expect_upload()
+            .times(2)
+            .returning(|key, _| Ok(format!("https://oss.local/{key}")));
+
+        let files = file_map(&[
+            ("demo/SKILL.md", &skill_md("demo-skill", Some("1.0.0"))),
+            ("demo/scripts/run.sh", "echo ok"),
+        ]);
+        let executables = BTreeSet::from(["demo/scripts/run.sh".to_string()]);
+        for executables in [None, Some(&executables)] {
+            sync_standalone_skills(
+                &db,
+                &storage,
+                None,
+                &repo,
+                &files,
+                SyncOrigin {
+                    revision: None,
+                    release: None,
+                    executables,
+                },
+                &HashSet::new(),
+                true,
+            )
+            .await
+            .unwrap();
+        }
+
+        let revisions = SkillVersionRevisions::find().all(&db).await.unwrap();
+        assert_eq!(revisions.len(), 1);
+        let version = SkillVersions::find().one(&db).await.unwrap().unwrap();
+        assert_ne!(version.file_hash.as_ref(), Some(&revisions[0].file_hash));
+    }
+
+    #[tokio::test]
+    async fn sync_standalone_skills_rehashes_legacy_rows_without_reuse_warning() {
+        let db = setup_db().await;
+        let repo = insert_registry(&db, "legacy-hash-repo").await;
+
+        let mut storage = MockStorage::new();
+        storage
+            .expect_upload()
+            .times(2)
+            .returning(|key, _| Ok(format!("https://oss.local/{key}")));
+
+        let files = file_map(&[
+            ("demo/SKILL.md", &skill_md("demo-skill", Some("1.0.0"))),
+            ("demo/scripts/run.sh", "echo ok"),
+        ]);
+        let executables = BTreeSet::from(["demo/scripts/run.sh".to_string()]);
+        // The first sync writes a row hashed without executable bits, as
+        // releases did before the hash covered them.
+        let mut legacy = None;
+        for executables in [None, Some(&executables), Some(&executables)] {
+            let outcome = sync_standalone_skills(
+                &db,
+                &storage,
+                None,
+                &repo,
+                &files,
+                SyncOrigin {
+                    revision: None,
+                    release: None,
+                    executables,
+                },
+                &HashSet::new(),
+                true,
+            )
+            .await
+            .unwrap();
+            assert!(outcome.warnings.is_empty());
+            if legacy.is_none() {
+                legacy = SkillVersions::find().one(&db).await.unwrap();
+            }
+        }
+        let legacy = legacy.unwrap();
+
+        let revisions = SkillVersionRevisions::find().all(&db).await.unwrap();
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].skill_version_id, legacy.id);
+        let versions = SkillVersions::find().all(&db).await.unwrap();
+        assert_eq!(versions, vec![legacy]);
+        let skill = Skills::find().one(&db).await.unwrap().unwrap();
+        assert_eq!(skill.reused_version, None);
+    }
+
+    #[tokio::test]
+    async fn sync_standalone_skills_stores_detached_signatures() {
+        use common::domain::signing::{signature_key, verify_package};
+        use std::sync::{Arc, Mutex};
+
+        let db = setup_db().await;
+        let repo = insert_registry(&db, "signed-repo").await;
+        let signer = PackageSigner::new([9; 32], Some("test-key".to_string()));
+
+        let uploads = Arc::new(Mutex::new(BTreeMap::new()));
+        let recorded = uploads.clone();
+        let mut storage = MockStorage::new();
+        storage
+            .expect_upload()
+            .times(2)
+            .returning(move |key, body| {
+                recorded.lock().unwrap().insert(key.to_string(), body);
+                Ok(format!("https://oss.local/{key}"))
+            });
+
+        let files = file_map(&[("demo/SKILL.md", &skill_md("demo-skill", Some("1.0.0")))]);
+        sync_standalone_skills(
+            &db,
+            &storage,
+            Some(&signer),
+            &repo,
+            &files,
+            SyncOrigin::default(),
+            &HashSet::new(),
+            true,
+        )
+        .await
+        .unwrap();
+
+        let version = SkillVersions::find().one(&db).await.unwrap().unwrap();
+        assert_eq!(version.signature_key_id.as_deref(), Some("test-key"));
+        let s3_key = version.s3_key.unwrap();
+        let uploads = uploads.lock().unwrap();
+        let zip = &uploads[&s3_key];
+        let raw: [u8; 64] = uploads[&signature_key(&s3_key)].clone().try_into().unwrap();
+        let signature = PackageSignature {
+            key_id: "test-key".to_string(),
+            signature: raw,
+        };
+        assert_eq!(version.signature, Some(signature.to_base64()));
+        assert!(verify_package(
+            &[signer.verification_key()],
+            zip,
+            &signature
+        ));
+    }
+
+    #[tokio::test]
+    async fn sync_standalone_skills_indexes_tags_as_immutable_versions() {
+        let db = setup_db().await;
+        let repo = insert_registry(&db, "tagged-repo").await;
+
+        let mut storage = MockStorage::new();
+        storage
+            .expect_upload()
+            .times(2)
+            .returning(|key, _| Ok(format!("https://oss.local/{key}")));
+
+        let head = file_map(&[("demo/SKILL.md", &skill_md("demo-skill", Some("1.0.0")))]);
+        sync_standalone_skills(
+            &db,
+            &storage,
+            None,
+            &repo,
+            &head,
+            SyncOrigin::default(),
+            &HashSet::new(),
+            true,
+        )
+        .await
+        .unwrap();
+
+        let tagged = file_map(&[
+            ("demo/SKILL.md", &skill_md("demo-skill", None)),
+            ("demo/OLD.md", "old release"),
+        ]);
+        let revision = SourceRevision {
+            commit_sha: "89abcdef0123456789abcdef0123456789abcdef".to_string(),
+            git_ref: Some("refs/tags/v0.9.0".to_string()),
+            committed_at: None,
+        };
+        let release = TagRelease {
+            tag: "v0.9.0".to_string(),
+            name: None,
+            version: "0.9.0".to_string(),
+        };
+        let origin = SyncOrigin {
+            revision: Some(&revision),
+            release: Some(&release),
+            executables: None,
+        };
+        let first = sync_standalone_skills(
+            &db,
+            &storage,
+            None,
+            &repo,
+            &tagged,
+            origin,
+            &HashSet::new(),
+            false,
+        )
+        .await
+        .unwrap();
+        assert!(first.changed);
+
+        let again = sync_standalone_skills(
+            &db,
+            &storage,
+            None,
+            &repo,
+            &head,
+            origin,
+            &HashSet::new(),
+            false,
+        )
+        .await
+        .unwrap();
+        assert!(!again.changed);
+
+        let scoped = TagRelease {
+            tag: "other-skill@2.0.0".to_string(),
+            name: Some("other-skill".to_string()),
+            version: "2.0.0".to_string(),
+        };
+        let outcome = sync_standalone_skills(
+            &db,
+            &storage,
+            None,
+            &repo,
+            &head,
+            SyncOrigin {
+                revision: None,
+                release: Some(&scoped),
+                executables: None,
+            },
+            &HashSet::new(),
+            false,
+        )
+        .await
+        .unwrap();
+        assert!(!outcome.found_any);
+
+        let skill = Skills::find()
+            .filter(skills::Column::SkillRegistryId.eq(repo.id))
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(skill.latest_version.as_deref(), Some("1.0.0"));
+        assert_eq!(skill.head_version.as_deref(), Some("1.0.0"));
+        assert_eq!(skill.is_active, 1);
+
+        let tagged_version = SkillVersions::find()
+            .filter(common::entities::skill_versions::Column::SkillId.eq(skill.id))
+            .filter(common::entities::skill_versions::Column::Version.eq("0.9.0"))
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(tagged_version.git_ref.as_deref(), Some("refs/tags/v0.9.0"));
+        assert_eq!(
+            tagged_version.s3_key,
+            Some(format!(
+                "skills/github.com/acme/{}/demo-skill/{}.zip",
+                repo.name,
+                tagged_version.file_hash.as_deref().unwrap()
+            ))
+        );
+    }
+
     #[tokio::test]
     async fn sync_standalone_skills_returns_not_found_when_required() {
         let db = setup_db().await;
@@ -639,8 +1215,10 @@ mod tests {
         let outcome = sync_standalone_skills(
             &db,
             &MockStorage::new(),
+            None,
             &repo,
             &files,
+            SyncOrigin::default(),
             &HashSet::new(),
             true,
         )
@@ -652,6 +1230,119 @@ mod tests {
         assert_eq!(Skills::find().count(&db).await.unwrap(), 0);
     }
 
+    #[tokio::test]
+    async fn sync_standalone_skills_keeps_released_versions_immutable() {
+        let db = setup_db().await;
+        let repo = insert_registry(&db, "reuse-repo").await;
+
+        let mut storage = MockStorage::new();
+        storage
+            .expect_upload()
+            .times(3)
+            .returning(|key, _| Ok(format!("https://oss.local/{key}")));
+
+        let released = file_map(&[("demo/SKILL.md", &skill_md("demo-skill", Some("1.0.0")))]);
+        let sync = |files: BTreeMap<String, Vec<u8>>| {
+            let (db, storage, repo) = (&db, &storage, &repo);
+            async move {
+                sync_standalone_skills(
+                    db,
+                    storage,
+                    None,
+                    repo,
+                    &files,
+                    SyncOrigin::default(),
+                    &HashSet::new(),
+                    true,
+                )
+                .await
+                .unwrap()
+            }
+        };
+        sync(released).await;
+        let original = SkillVersions::find().one(&db).await.unwrap().unwrap();
+
+        let rebuilt = file_map(&[
+            ("demo/SKILL.md", &skill_md("demo-skill", Some("1.0.0"))),
+            ("demo/extra.md", "changed"),
+        ]);
+        let first = sync(rebuilt.clone()).await;
+        assert!(first.changed);
+        assert_eq!(first.warnings.len(), 1);
+        assert!(first.warnings[0].contains("reuses version 1.0.0"));
+
+        let again = sync(rebuilt).await;
+        assert!(!again.changed);
+        assert_eq!(again.warnings.len(), 1);
+
+        let versions = SkillVersions::find().all(&db).await.unwrap();
+        assert_eq!(versions, vec![original.clone()]);
+        let revisions = SkillVersionRevisions::find().all(&db).await.unwrap();
+        assert_eq!(revisions.len(), 1);
+        assert_eq!(revisions[0].skill_version_id, original.id);
+        assert_eq!(revisions[0].revision, 2);
+        assert_ne!(Some(&revisions[0].file_hash), original.file_hash.as_ref());
+
+        let skill = Skills::find().one(&db).await.unwrap().unwrap();
+        assert_eq!(skill.reused_version.as_deref(), Some("1.0.0"));
+
+        let bumped = file_map(&[
+            ("demo/SKILL.md", &skill_md("demo-skill", Some("1.0.1"))),
+            ("demo/extra.md", "changed"),
+        ]);
+        let outcome = sync(bumped).await;
+        assert!(outcome.warnings.is_empty());
+        let skill = Skills::find().one(&db).await.unwrap().unwrap();
+        assert_eq!(skill.latest_version.as_deref(), Some("1.0.1"));
+        assert_eq!(skill.reused_version, None);
+    }
+
+    #[tokio::test]
+    async fn sync_standalone_skills_selects_highest_declared_version_as_latest() {
+        let db = setup_db().await;
+        let repo = insert_registry(&db, "semver-repo").await;
+
+        let mut storage = MockStorage::new();
+        storage
+            .expect_upload()
+            .times(4)
+            .returning(|key, _| Ok(format!("https://oss.local/{key}")));
+
+        let mut latest_after = Vec::new();
+        let mut head_after = Vec::new();
+        for (version, body) in [
+            (Some("1.2.0"), "a"),
+            (Some("1.1.0"), "b"),
+            (None, "c"),
+            (Some("2.0.0-rc.1"), "d"),
+        ] {
+            let files = file_map(&[
+                ("demo/SKILL.md", &skill_md("demo-skill", version)),
+                ("demo/notes.md", body),
+            ]);
+            sync_standalone_skills(
+                &db,
+                &storage,
+                None,
+                &repo,
+                &files,
+                SyncOrigin::default(),
+                &HashSet::new(),
+                true,
+            )
+            .await
+            .unwrap();
+            let skill = Skills::find().one(&db).await.unwrap().unwrap();
+            latest_after.push(skill.latest_version.unwrap());
+            head_after.push(skill.head_version.unwrap());
+        }
+
+        assert_eq!(latest_after, vec!["1.2.0"; 4]);
+        assert_eq!(head_after[..2], ["1.2.0", "1.1.0"]);
+        assert!(head_after[2].starts_with("0.0."));
+        assert_eq!(head_after[3], "2.0.0-rc.1");
+    }
+
     #[tokio::test]
     async fn sync_standalone_skills_deactivates_missing_skills() {
         let db = setup_db().await;
@@ -668,17 +1359,33 @@ mod tests {
             ("beta/SKILL.md", &skill_md("beta-skill", Some("1.0.0"))),
         ]);
 
-        let first =
-            sync_standalone_skills(&db, &storage, &repo, &initial_files, &HashSet::new(), true)
-                .await
-                .unwrap();
+        let first = sync_standalone_skills(
+            &db,
+            &storage,
+            None,
+            &repo,
+            &initial_files,
+            SyncOrigin::default(),
+            &HashSet::new(),
+            true,
+        )
+        .await
+        .unwrap();
         assert!(first.changed);
 
         let second_files = file_map(&[("alpha/SKILL.md", &skill_md("alpha-skill", Some("1.0.0")))]);
-        let second =
-            sync_standalone_skills(&db, &storage, &repo, &second_files, &HashSet::new(), true)
-                .await
-                .unwrap();
+        let second = sync_standalone_skills(
+            &db,
+            &storage,
+            None,
+            &repo,
+            &second_files,
+            SyncOrigin::default(),
+            &HashSet::new(),
+            true,
+        )
+        .await
+        .unwrap();
         assert!(!second.changed);
         assert!(second.found_any);
 
@@ -714,9 +1421,18 @@ mod tests {
         let mut excludes = HashSet::new();
         excludes.insert("plugins/p1/skills".to_string());
 
-        let outcome = sync_standalone_skills(&db, &storage, &repo, &files, &excludes, false)
-            .await
-            .unwrap();
+        let outcome = sync_standalone_skills(
+            &db,
+            &storage,
+            None,
+            &repo,
+            &files,
+            SyncOrigin::default(),
+            &excludes,
+            false,
+        )
+        .await
+        .unwrap();
         assert!(outcome.changed);
         assert!(outcome.found_any);
 
@@ -0,0 +1,182 @@
+/// A git tag resolved against the configured tag patterns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagRelease {
+    pub tag: String,
+    /// Skill or plugin the tag is scoped to; `None` applies to every package in the tree.
+    pub name: Option<String>,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    Name,
+    Version,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token<'a> {
+    Literal(&'a str),
+    Placeholder(Placeholder),
+}
+
+/// Returns the release described by the first pattern that matches `tag`.
+pub fn match_tag(patterns: &[String], tag: &str) -> Option<TagRelease> {
+    patterns.iter().find_map(|pattern| {
+        let tokens = tokenize(pattern)?;
+        let mut captures = Vec::new();
+        if !match_tokens(&tokens, tag, &mut captures) {
+            return None;
+        }
+
+        let mut name = None;
+        let mut version = None;
+        for (placeholder, value) in captures {
+            match placeholder {
+                Placeholder::Name => name = Some(value.to_string()),
+                Placeholder::Version => version = Some(value.to_string()),
+            }
+        }
+        Some(TagRelease {
+            tag: tag.to_string(),
+            name,
+            version: version?,
+        })
+    })
+}
+
+// Patterns without `{version}`, or with a repeated placeholder, never match.
+fn tokenize(pattern: &str) -> Option<Vec<Token<'_>>> {
+    let mut tokens = Vec::new();
+    let mut rest = pattern;
+    while !rest.is_empty() {
+        let Some(start) = rest.find('{') else {
+            tokens.push(Token::Literal(rest));
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Literal(&rest[..start]));
+        }
+        let end = start + rest[start..].find('}')?;
+        let placeholder = match &rest[start + 1..end] {
+            "name" => Placeholder::Name,
+            "version" => Placeholder::Version,
+            _ => return None,
+        };
+        if tokens.contains(&Token::Placeholder(placeholder)) {
+            return None;
+        }
+        tokens.push(Token::Placeholder(placeholder));
+        rest = &rest[end + 1..];
+    }
+
+    tokens
+        .contains(&Token::Placeholder(Placeholder::Version))
+        .then_some(tokens)
+}
+
+fn match_tokens<'a>(
+    tokens: &[Token<'_>],
+    input: &'a str,
+    captures: &mut Vec<(Placeholder, &'a str)>,
+) -> bool {
+    let Some((token, rest)) = tokens.split_first() else {
+        return input.is_empty();
+    };
+
+    match token {
+        Token::Literal(literal) => input
+            .strip_prefix(literal)
+            .is_some_and(|remaining| match_tokens(rest, remaining, captures)),
+        Token::Placeholder(placeholder) => {
+            // Prefer the longest capture so names may contain the separator
+            // characters of later literals (e.g. `my-skill-v1.0.0`).
+            let ends = input
+                .char_indices()
+                .map(|(idx, _)| idx)
+                .skip(1)
+                .chain(std::iter::once(input.len()))
+                .collect::<Vec<_>>();
+            for end in ends.into_iter().rev() {
+                let (value, remaining) = input.split_at(end);
+                if !is_valid_capture(*placeholder, value) {
+                    continue;
+                }
+                captures.push((*placeholder, value));
+                if match_tokens(rest, remaining, captures) {
+                    return true;
+                }
+                captures.pop();
+            }
+            false
+        }
+    }
+}
+
+fn is_valid_capture(placeholder: Placeholder, value: &str) -> bool {
+    match placeholder {
+        Placeholder::Name => value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')),
+        Placeholder::Version => {
+            value.starts_with(|c: char| c.is_ascii_digit())
+                && value
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+'))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns() -> Vec<String> {
+        common::settings::default_tag_patterns()
+    }
+
+    #[test]
+    fn match_tag_handles_repo_wide_and_scoped_tags() {
+        assert_eq!(
+            match_tag(&patterns(), "v1.2.0"),
+            Some(TagRelease {
+                tag: "v1.2.0".to_string(),
+                name: None,
+                version: "1.2.0".to_string(),
+            })
+        );
+        assert_eq!(
+            match_tag(&patterns(), "my-skill@1.2.0-rc.1"),
+            Some(TagRelease {
+                tag: "my-skill@1.2.0-rc.1".to_string(),
+                name: Some("my-skill".to_string()),
+                version: "1.2.0-rc.1".to_string(),
+            })
+        );
+
+        let scoped = match_tag(&patterns(), "my-skill-v2.0.0").unwrap();
+        assert_eq!(scoped.name.as_deref(), Some("my-skill"));
+        assert_eq!(scoped.version, "2.0.0");
+    }
+
+    #[test]
+    fn match_tag_rejects_non_release_tags() {
+        assert_eq!(match_tag(&patterns(), "nightly"), None);
+        assert_eq!(match_tag(&patterns(), "v"), None);
+        assert_eq!(match_tag(&patterns(), "release/1.0"), None);
+    }
+
+    #[test]
+    fn tokenize_requires_a_single_version_placeholder() {
+        assert!(tokenize("{name}").is_none());
+        assert!(tokenize("{version}-{version}").is_none());
+        assert!(tokenize("{unknown}@{version}").is_none());
+        assert!(tokenize("release-{version").is_none());
+        assert_eq!(
+            tokenize("rel/{version}"),
+            Some(vec![
+                Token::Literal("rel/"),
+                Token::Placeholder(Placeholder::Version)
+            ])
+        );
+    }
+}
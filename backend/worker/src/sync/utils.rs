@@ -1,7 +1,7 @@
 use anyhow::Result;
 use common::domain::{archive, json, markdown, skill};
 use serde_json::Value;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 pub use common::domain::markdown::ParsedMarkdown;
 pub use common::domain::skill::SkillFrontmatter;
@@ -10,8 +10,11 @@ pub fn verify_skill(expected_name: &str, frontmatter_str: &str) -> Result<SkillF
     skill::verify_skill(expected_name, frontmatter_str)
 }
 
-pub fn package_skill(file_map: &BTreeMap<String, Vec<u8>>) -> Result<Vec<u8>> {
-    archive::package_zip(file_map)
+pub fn package_skill(
+    file_map: &BTreeMap<String, Vec<u8>>,
+    executables: &BTreeSet<String>,
+) -> Result<Vec<u8>> {
+    archive::package_zip_with_executables(file_map, executables)
 }
 
 pub fn parse_markdown_frontmatter(input: &str) -> Result<ParsedMarkdown> {
@@ -29,10 +32,25 @@ pub fn subtree_file_map(
     archive::subtree_file_map(all_files, dir_prefix)
 }
 
+pub fn rebase_paths(
+    paths: &BTreeSet<String>,
+    dir_prefix: &str,
+    package_prefix: &str,
+) -> BTreeSet<String> {
+    archive::rebase_paths(paths, dir_prefix, package_prefix)
+}
+
 pub fn compute_hash(file_map: &BTreeMap<String, Vec<u8>>) -> String {
     archive::compute_hash(file_map)
 }
 
+pub fn compute_package_hash(
+    file_map: &BTreeMap<String, Vec<u8>>,
+    executables: &BTreeSet<String>,
+) -> String {
+    archive::compute_package_hash(file_map, executables)
+}
+
 pub fn parse_boolish(value: Option<&Value>) -> bool {
     json::parse_boolish(value)
 }
@@ -111,12 +111,14 @@ mod tests {
             new_count: 2,
             updated_count: 1,
             touched_repo_ids: vec![1],
+            search_slices: 1,
         })
         .unwrap();
         let valid_two = serde_json::to_vec(&DiscoveryResult {
             new_count: 0,
             updated_count: 3,
             touched_repo_ids: vec![2],
+            search_slices: 4,
         })
         .unwrap();
 
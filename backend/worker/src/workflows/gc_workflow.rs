@@ -0,0 +1,114 @@
+use crate::contracts;
+use crate::gc::{GcInput, GcReport};
+use crate::workflows::{create_json_payload, execute_activity};
+use std::time::Duration;
+use temporalio_sdk::{ActivityOptions, WfContext, WfExitValue};
+
+/// A run without input is a dry run.
+pub(crate) fn parse_gc_input_from_payloads(
+    payloads: &[temporalio_common::protos::temporal::api::common::v1::Payload],
+) -> Result<GcInput, &'static str> {
+    match payloads.first() {
+        Some(payload) => serde_json::from_slice(&payload.data).map_err(|_| "Invalid gc input"),
+        None => Ok(GcInput::default()),
+    }
+}
+
+pub(crate) fn build_collect_garbage_options(input: &GcInput) -> ActivityOptions {
+    ActivityOptions {
+        activity_type: contracts::activities::COLLECT_GARBAGE.to_string(),
+        input: create_json_payload(input),
+        start_to_close_timeout: Some(Duration::from_secs(3600)),
+        ..Default::default()
+    }
+}
+
+pub(crate) fn format_gc_summary(report: &GcReport) -> String {
+    if report.dry_run {
+        format!(
+            "GC Dry Run: scanned={}, reclaimable={} ({} bytes)",
+            report.scanned_objects, report.reclaimable_objects, report.reclaimable_bytes
+        )
+    } else {
+        format!(
+            "GC Completed: scanned={}, deleted={} ({} bytes), failed={}",
+            report.scanned_objects,
+            report.deleted_objects,
+            report.deleted_bytes,
+            report.failed_deletes
+        )
+    }
+}
+
+pub async fn gc_workflow(ctx: WfContext) -> Result<WfExitValue<String>, anyhow::Error> {
+    let input = match parse_gc_input_from_payloads(ctx.get_args()) {
+        Ok(input) => input,
+        Err(msg) => return Ok(WfExitValue::Normal(msg.to_string())),
+    };
+
+    let report: GcReport = match execute_activity(&ctx, build_collect_garbage_options(&input)).await
+    {
+        Ok(report) => report,
+        Err(e) => return Ok(WfExitValue::Normal(format!("GC Failed: {}", e))),
+    };
+
+    Ok(WfExitValue::Normal(format_gc_summary(&report)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gc_input_parsing_defaults_to_dry_run() {
+        assert_eq!(parse_gc_input_from_payloads(&[]), Ok(GcInput::default()));
+
+        let payload = create_json_payload(&serde_json::json!({
+            "dry_run": false,
+            "grace_period_hours": 12
+        }));
+        assert_eq!(
+            parse_gc_input_from_payloads(&[payload]),
+            Ok(GcInput {
+                dry_run: false,
+                grace_period_hours: Some(12),
+            })
+        );
+
+        let invalid = create_json_payload(&"delete everything");
+        assert_eq!(
+            parse_gc_input_from_payloads(&[invalid]),
+            Err("Invalid gc input")
+        );
+    }
+
+    #[test]
+    fn gc_options_and_summary_helpers() {
+        let opts = build_collect_garbage_options(&GcInput::default());
+        assert_eq!(opts.activity_type, contracts::activities::COLLECT_GARBAGE);
+        assert_eq!(opts.start_to_close_timeout, Some(Duration::from_secs(3600)));
+        let input: GcInput = serde_json::from_slice(&opts.input.data).unwrap();
+        assert!(input.dry_run);
+
+        let mut report = GcReport {
+            dry_run: true,
+            scanned_objects: 10,
+            reclaimable_objects: 3,
+            reclaimable_bytes: 194,
+            ..Default::default()
+        };
+        assert_eq!(
+            format_gc_summary(&report),
+            "GC Dry Run: scanned=10, reclaimable=3 (194 bytes)"
+        );
+
+        report.dry_run = false;
+        report.deleted_objects = 2;
+        report.deleted_bytes = 130;
+        report.failed_deletes = 1;
+        assert_eq!(
+            format_gc_summary(&report),
+            "GC Completed: scanned=10, deleted=2 (130 bytes), failed=1"
+        );
+    }
+}
@@ -1,4 +1,5 @@
 pub mod discovery_workflow;
+pub mod gc_workflow;
 pub mod sync_repo_workflow;
 pub mod sync_scheduler_workflow;
 pub mod trigger_registry_workflow;
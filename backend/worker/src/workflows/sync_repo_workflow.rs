@@ -34,7 +34,8 @@ pub(crate) fn build_fetch_snapshot_options(
         activity_type: contracts::activities::FETCH_REPO_SNAPSHOT.to_string(),
         input: create_json_payload(&registry_id),
         start_to_close_timeout: Some(Duration::from_secs(120)),
-        schedule_to_close_timeout: Some(Duration::from_secs(180)),
+        // Long enough to wait out an hourly API rate-limit window between attempts.
+        schedule_to_close_timeout: Some(Duration::from_secs(3900)),
         retry_policy,
         timer_backoff_threshold: Some(Duration::from_secs(5)),
         ..Default::default()
@@ -56,6 +57,22 @@ pub(crate) fn build_apply_snapshot_options(
     }
 }
 
+pub(crate) fn build_index_tags_options(
+    registry_id: i32,
+    retry_policy: RetryPolicy,
+) -> LocalActivityOptions {
+    LocalActivityOptions {
+        activity_type: contracts::activities::INDEX_REPO_TAGS.to_string(),
+        input: create_json_payload(&registry_id),
+        // Each pending tag is a separate shallow clone.
+        start_to_close_timeout: Some(Duration::from_secs(600)),
+        schedule_to_close_timeout: Some(Duration::from_secs(3900)),
+        retry_policy,
+        timer_backoff_threshold: Some(Duration::from_secs(5)),
+        ..Default::default()
+    }
+}
+
 pub(crate) fn format_sync_completed(res: &SyncResult) -> String {
     format!("Sync Completed: {:?}", res)
 }
@@ -77,21 +94,44 @@ pub async fn sync_repo_workflow(ctx: WfContext) -> Result<WfExitValue<String>, a
         Err(e) => return Ok(WfExitValue::Normal(format!("Fetch Snapshot Failed: {}", e))),
     };
 
-    let snapshot = match snapshot_res {
+    let res: SyncResult = match snapshot_res {
+        // An unchanged default branch can still have new release tags.
+        SnapshotResult::Skipped { status } if status == "Unchanged" => SyncResult {
+            status,
+            version: None,
+            warnings: Vec::new(),
+        },
         SnapshotResult::Skipped { status } => {
             return Ok(WfExitValue::Normal(format!("Sync Skipped: {}", status)))
         }
-        SnapshotResult::Snapshot(s) => s,
+        SnapshotResult::Snapshot(snapshot) => {
+            let apply_opts = build_apply_snapshot_options(&snapshot, retry_policy.clone());
+            match execute_local_activity(&ctx, apply_opts).await {
+                Ok(res) => res,
+                Err(e) => return Ok(WfExitValue::Normal(format!("Sync Failed: {}", e))),
+            }
+        }
     };
 
-    let apply_opts = build_apply_snapshot_options(&snapshot, retry_policy);
+    if res.status == "Blacklisted" {
+        return Ok(WfExitValue::Normal(format_sync_completed(&res)));
+    }
 
-    let res: SyncResult = match execute_local_activity(&ctx, apply_opts).await {
-        Ok(res) => res,
-        Err(e) => return Ok(WfExitValue::Normal(format!("Sync Failed: {}", e))),
+    let tags_opts = build_index_tags_options(registry_id, retry_policy);
+    let summary = match execute_local_activity::<SyncResult>(&ctx, tags_opts).await {
+        Ok(tags) if tags.warnings.is_empty() => {
+            format!("{} Tags: {}", format_sync_completed(&res), tags.status)
+        }
+        Ok(tags) => format!(
+            "{} Tags: {} Tag Warnings: {:?}",
+            format_sync_completed(&res),
+            tags.status,
+            tags.warnings
+        ),
+        Err(e) => format!("{} Tag Indexing Failed: {}", format_sync_completed(&res), e),
     };
 
-    Ok(WfExitValue::Normal(format_sync_completed(&res)))
+    Ok(WfExitValue::Normal(summary))
 }
 
 #[cfg(test)]
@@ -131,7 +171,7 @@ mod tests {
         assert_eq!(fetch.start_to_close_timeout, Some(Duration::from_secs(120)));
         assert_eq!(
             fetch.schedule_to_close_timeout,
-            Some(Duration::from_secs(180))
+            Some(Duration::from_secs(3900))
         );
         let id: i32 = serde_json::from_slice(&fetch.input.data).unwrap();
         assert_eq!(id, 11);
@@ -143,8 +183,9 @@ mod tests {
             url: "https://github.com/acme/skills".to_string(),
             zip_hash: "hash".to_string(),
             snapshot_s3_key: "repo-snapshots/hash.zip".to_string(),
+            revision: None,
         };
-        let apply = build_apply_snapshot_options(&snapshot, retry);
+        let apply = build_apply_snapshot_options(&snapshot, retry.clone());
         assert_eq!(
             apply.activity_type,
             contracts::activities::APPLY_SYNC_FROM_SNAPSHOT
@@ -159,11 +200,25 @@ mod tests {
             serde_json::from_slice(&apply.input.data).unwrap();
         assert_eq!(decoded.registry_id, 11);
 
+        let tags = build_index_tags_options(11, retry);
+        assert_eq!(tags.activity_type, contracts::activities::INDEX_REPO_TAGS);
+        assert_eq!(tags.start_to_close_timeout, Some(Duration::from_secs(600)));
+        let id: i32 = serde_json::from_slice(&tags.input.data).unwrap();
+        assert_eq!(id, 11);
+
         let summary = format_sync_completed(&SyncResult {
             status: "Updated".to_string(),
             version: Some("1.0.0".to_string()),
+            warnings: Vec::new(),
         });
         assert!(summary.contains("Sync Completed"));
         assert!(summary.contains("Updated"));
+
+        let summary = format_sync_completed(&SyncResult {
+            status: "Unchanged".to_string(),
+            version: None,
+            warnings: vec!["Skill 'demo' reuses version 1.0.0".to_string()],
+        });
+        assert!(summary.contains("reuses version 1.0.0"));
     }
 }
@@ -21,6 +21,7 @@ type DiscoveryRegistry = {
   queries: string[]
   schedule_interval_seconds: number
   token_configured: boolean
+  webhook_secret_configured: boolean
   last_health_status: string | null
   last_health_message: string | null
   last_health_checked_at: string | null
@@ -28,6 +29,10 @@ type DiscoveryRegistry = {
   last_run_status: string | null
   last_run_message: string | null
   next_run_at: string | null
+  rate_limit_remaining: number | null
+  rate_limit_reset_at: string | null
+  search_rate_limit_remaining: number | null
+  search_rate_limit_reset_at: string | null
   created_at: string
   updated_at: string
 }
@@ -51,6 +56,12 @@ type ValidateDeleteResponse = {
   reasons: string[]
 }
 
+const DEFAULT_API_URLS: Record<string, string> = {
+  github: "https://api.github.com",
+  gitlab: "https://gitlab.com/api/v4",
+  gitea: "https://codeberg.org/api/v1",
+}
+
 function formatDateTime(value: string | null) {
   if (!value) return "-"
   return new Date(value).toLocaleString()
@@ -81,6 +92,7 @@ export default function DiscoveryRegistries() {
   const [loading, setLoading] = useState(false)
   const [error, setError] = useState<string | null>(null)
 
+  const [createProvider, setCreateProvider] = useState("github")
   const [createToken, setCreateToken] = useState("")
   const [createUrl, setCreateUrl] = useState("https://api.github.com")
   const [createQueries, setCreateQueries] = useState("topic:agent-skill")
@@ -207,7 +219,7 @@ export default function DiscoveryRegistries() {
 
     try {
       const res = await api.post<ApiResponse<DiscoveryRegistry>>("/api/admin/discovery-registries", {
-        provider: "github",
+        provider: createProvider,
         token: createToken,
         url: createUrl,
         queries,
@@ -348,6 +360,35 @@ export default function DiscoveryRegistries() {
     }
   }
 
+  const onSetWebhookSecret = async (row: DiscoveryRegistry) => {
+    const input = window.prompt(
+      row.webhook_secret_configured
+        ? "Enter a new webhook secret (leave empty to remove it)"
+        : "Enter the secret used to sign GitHub push webhooks",
+    )
+    if (input === null) {
+      return
+    }
+
+    setBusyRowId(row.id)
+    try {
+      const res = await api.put<ApiResponse<DiscoveryRegistry>>(
+        `/api/admin/discovery-registries/${row.id}/webhook-secret`,
+        { webhook_secret: input.trim() || null },
+      )
+      if (res.data.code === 200) {
+        setActionMessage(input.trim() ? "Webhook secret saved" : "Webhook secret removed")
+        await fetchRegistries()
+      } else {
+        setActionMessage(res.data.message)
+      }
+    } catch (err: any) {
+      setActionMessage(err?.response?.data?.message ?? "Failed to update webhook secret")
+    } finally {
+      setBusyRowId(null)
+    }
+  }
+
   const actionIsError = actionMessage ? /(fail|error|denied|invalid)/i.test(actionMessage) : false
   const healthySources = registries.filter((registry) => isHealthyStatus(registry.last_health_status)).length
   const attentionSources = registries.length - healthySources
@@ -357,7 +398,7 @@ export default function DiscoveryRegistries() {
       <div className="space-y-2">
         <h1 className="text-3xl font-bold tracking-tight">Discovery Registries</h1>
         <p className="text-muted-foreground">
-          Manage GitHub discovery sources and trigger discovery workflows.
+          Manage GitHub, GitLab and Gitea discovery sources and trigger discovery workflows.
         </p>
       </div>
 
@@ -397,23 +438,39 @@ export default function DiscoveryRegistries() {
           <CardContent>
             <form className="space-y-3" onSubmit={onCreate}>
               <div className="space-y-1">
-                <label htmlFor="create-token" className="text-sm font-medium">GitHub Token</label>
+                <label htmlFor="create-provider" className="text-sm font-medium">Provider</label>
+                <select
+                  id="create-provider"
+                  value={createProvider}
+                  onChange={(e) => {
+                    setCreateProvider(e.target.value)
+                    setCreateUrl(DEFAULT_API_URLS[e.target.value] ?? "")
+                  }}
+                  className="h-9 w-full rounded-md border border-input bg-background px-3 text-sm"
+                >
+                  <option value="github">GitHub</option>
+                  <option value="gitlab">GitLab</option>
+                  <option value="gitea">Gitea / Forgejo</option>
+                </select>
+              </div>
+              <div className="space-y-1">
+                <label htmlFor="create-token" className="text-sm font-medium">Access Token</label>
                 <Input
                   id="create-token"
                   type="password"
                   value={createToken}
                   onChange={(e) => setCreateToken(e.target.value)}
-                  placeholder="ghp_..."
+                  placeholder={createProvider === "gitlab" ? "glpat-..." : "ghp_..."}
                   required
                 />
               </div>
               <div className="space-y-1">
-                <label htmlFor="create-api-url" className="text-sm font-medium">GitHub API URL</label>
+                <label htmlFor="create-api-url" className="text-sm font-medium">API URL</label>
                 <Input
                   id="create-api-url"
                   value={createUrl}
                   onChange={(e) => setCreateUrl(e.target.value)}
-                  placeholder="https://api.github.com"
+                  placeholder={DEFAULT_API_URLS[createProvider]}
                 />
               </div>
               <div className="space-y-1">
@@ -448,12 +505,12 @@ export default function DiscoveryRegistries() {
             <CardContent>
               <form className="space-y-3" onSubmit={onSaveEdit}>
                 <div className="space-y-1">
-                  <label htmlFor="edit-api-url" className="text-sm font-medium">GitHub API URL</label>
+                  <label htmlFor="edit-api-url" className="text-sm font-medium">API URL</label>
                   <Input
                     id="edit-api-url"
                     value={editUrl}
                     onChange={(e) => setEditUrl(e.target.value)}
-                    placeholder="https://api.github.com"
+                    placeholder={DEFAULT_API_URLS[editing.provider]}
                   />
                 </div>
                 <div className="space-y-1">
@@ -508,6 +565,7 @@ export default function DiscoveryRegistries() {
               <TableHead>Queries</TableHead>
               <TableHead>Interval</TableHead>
               <TableHead>Health</TableHead>
+              <TableHead>API Budget</TableHead>
               <TableHead>Run Status</TableHead>
               <TableHead>Run Message</TableHead>
               <TableHead>Started At</TableHead>
@@ -517,11 +575,11 @@ export default function DiscoveryRegistries() {
           <TableBody>
             {loading ? (
               <TableRow>
-                <TableCell colSpan={10} className="text-center py-6">Loading...</TableCell>
+                <TableCell colSpan={11} className="text-center py-6">Loading...</TableCell>
               </TableRow>
             ) : registries.length === 0 ? (
               <TableRow>
-                <TableCell colSpan={10} className="text-center py-6">No discovery registries</TableCell>
+                <TableCell colSpan={11} className="text-center py-6">No discovery registries</TableCell>
               </TableRow>
             ) : (
               registries.map((row) => {
@@ -542,6 +600,20 @@ export default function DiscoveryRegistries() {
                         {row.last_health_status ?? "unknown"}
                       </Badge>
                     </TableCell>
+                    <TableCell>
+                      <div title={row.rate_limit_reset_at ? `Resets at ${formatDateTime(row.rate_limit_reset_at)}` : undefined}>
+                        Core: {row.rate_limit_remaining ?? "-"}
+                      </div>
+                      <div
+                        title={
+                          row.search_rate_limit_reset_at
+                            ? `Resets at ${formatDateTime(row.search_rate_limit_reset_at)}`
+                            : undefined
+                        }
+                      >
+                        Search: {row.search_rate_limit_remaining ?? "-"}
+                      </div>
+                    </TableCell>
                     <TableCell>
                       {row.last_run_status ? (
                         <Badge variant={row.last_run_status === "success" ? "default" : "destructive"}>
@@ -563,6 +635,9 @@ export default function DiscoveryRegistries() {
                         <Button variant="outline" size="sm" onClick={() => onTestHealth(row.id)} disabled={rowBusy}>
                           Test
                         </Button>
+                        <Button variant="outline" size="sm" onClick={() => onSetWebhookSecret(row)} disabled={rowBusy}>
+                          {row.webhook_secret_configured ? "Webhook (set)" : "Webhook"}
+                        </Button>
                         <Button size="sm" onClick={() => onTrigger(row.id)} disabled={rowBusy}>
                           Trigger
                         </Button>
@@ -14,6 +14,7 @@ type SkillDetailPayload = {
     id: number
     name: string
     latest_version: string | null
+    reused_version?: string | null
     created_at: string
     updated_at: string
   }
@@ -36,6 +37,12 @@ type SkillDetailPayload = {
   allowed_tools: string[] | null
   homepage: string | null
   documentation_url: string | null
+  source: {
+    commit_sha: string
+    git_ref: string | null
+    committed_at: string | null
+    source_path: string | null
+  } | null
 }
 
 type DownloadSkillResponse = {
@@ -48,6 +55,8 @@ type DownloadSkillResponse = {
 
 export default function SkillDetail() {
   const { host, org, repo, name } = useParams()
+  // GitLab subgroup owners contain slashes, so they travel as one encoded segment.
+  const orgSegment = encodeURIComponent(org ?? "")
   const [data, setData] = useState<SkillDetailPayload | null>(null)
   const [loading, setLoading] = useState(true)
   const [activeTab, setActiveTab] = useState<"readme" | "versions">("readme")
@@ -64,7 +73,7 @@ export default function SkillDetail() {
   const fetchSkill = async () => {
     setLoading(true)
     try {
-      const res = await api.get<ApiResponse<SkillDetailPayload>>(`/api/${host}/${org}/${repo}/skill/${name}`)
+      const res = await api.get<ApiResponse<SkillDetailPayload>>(`/api/${host}/${orgSegment}/${repo}/skill/${name}`)
       if (res.data.code === 200 && res.data.data) {
         setData(res.data.data)
       } else {
@@ -121,7 +130,7 @@ export default function SkillDetail() {
     setDownloading(true)
     try {
       const res = await api.get<ApiResponse<DownloadSkillResponse>>(
-        `/api/${host}/${org}/${repo}/skill/${name}/download`,
+        `/api/${host}/${orgSegment}/${repo}/skill/${name}/download`,
       )
       if (res.data.code === 200 && res.data.data) {
         window.location.href = res.data.data.download_url
@@ -165,9 +174,9 @@ export default function SkillDetail() {
         <ChevronRight className="h-4 w-4 mx-2" />
         <Link to={`/${host}`} className="hover:text-primary">{host}</Link>
         <ChevronRight className="h-4 w-4 mx-2" />
-        <Link to={`/${host}/${org}`} className="hover:text-primary">{org}</Link>
+        <Link to={`/${host}/${orgSegment}`} className="hover:text-primary">{org}</Link>
         <ChevronRight className="h-4 w-4 mx-2" />
-        <Link to={`/${host}/${org}/${repo}`} className="hover:text-primary">{repo}</Link>
+        <Link to={`/${host}/${orgSegment}/${repo}`} className="hover:text-primary">{repo}</Link>
         <ChevronRight className="h-4 w-4 mx-2" />
         <span className="text-foreground font-medium">{data.skill.name}</span>
       </div>
@@ -185,6 +194,11 @@ export default function SkillDetail() {
               {data.registry.stars} stars
             </span>
             {data.skill.latest_version && <Badge variant="secondary">v{data.skill.latest_version}</Badge>}
+            {data.skill.reused_version && (
+              <Badge variant="destructive" title="The repository republished this version with different files">
+                v{data.skill.reused_version} reused
+              </Badge>
+            )}
           </div>
         </div>
         <div className="w-full md:w-auto space-y-2">
@@ -272,6 +286,15 @@ export default function SkillDetail() {
                   Last synced: {new Date(data.last_synced_at).toLocaleString()}
                 </div>
               )}
+              {data.source && (
+                <div className="text-sm text-muted-foreground">
+                  Source: <code className="font-mono">{data.source.commit_sha.slice(0, 12)}</code>
+                  {data.source.git_ref && ` (${data.source.git_ref.replace(/^refs\/(heads|tags)\//, "")})`}
+                  {data.source.source_path && data.source.source_path !== "." && (
+                    <div className="font-mono text-xs truncate">{data.source.source_path}</div>
+                  )}
+                </div>
+              )}
               {data.license && (
                 <div>
                   <span className="text-sm text-muted-foreground">License: </span>
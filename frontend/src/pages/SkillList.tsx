@@ -170,9 +170,11 @@ export default function SkillList() {
     }
   }
 
-  const skillPath = (skill: Skill) => `/${skill.host}/${skill.owner}/${skill.repo}/skill/${skill.name}`
-  const ownerPath = (skill: Skill) => `/${skill.host}/${skill.owner}`
-  const repoPath = (skill: Skill) => `/${skill.host}/${skill.owner}/${skill.repo}`
+  // GitLab subgroup owners contain slashes, so they travel as one encoded segment.
+  const ownerSegment = (skill: Skill) => encodeURIComponent(skill.owner)
+  const skillPath = (skill: Skill) => `/${skill.host}/${ownerSegment(skill)}/${skill.repo}/skill/${skill.name}`
+  const ownerPath = (skill: Skill) => `/${skill.host}/${ownerSegment(skill)}`
+  const repoPath = (skill: Skill) => `/${skill.host}/${ownerSegment(skill)}/${skill.repo}`
 
   return (
     <div className="container mx-auto py-8 space-y-6">